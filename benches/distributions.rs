@@ -142,6 +142,26 @@ distr_int!(distr_uniform_usize32, usize, Uniform::new(0usize, 0x548c0f43).unwrap
 distr_int!(distr_uniform_usize64, usize, Uniform::new(0usize, 0x3a42714f2bf927a8).unwrap());
 distr_int!(distr_uniform_isize, isize, Uniform::new(-1060478432isize, 1858574057).unwrap());
 
+macro_rules! distr_int_fill {
+    ($fnn:ident, $ty:ty, $distr:expr) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = Pcg64Mcg::from_entropy();
+            let distr = $distr;
+            let mut buf = [0 as $ty; RAND_BENCH_N as usize];
+
+            b.iter(|| {
+                distr.fill(&mut rng, &mut buf);
+                buf[0]
+            });
+            b.bytes = size_of::<$ty>() as u64 * RAND_BENCH_N;
+        }
+    };
+}
+
+distr_int_fill!(distr_uniform_fill_u8, u8, Uniform::new(20u8, 100).unwrap());
+distr_int_fill!(distr_uniform_fill_u16, u16, Uniform::new(500u16, 2000).unwrap());
+
 distr_float!(distr_uniform_f32, f32, Uniform::new(2.26f32, 2.319).unwrap());
 distr_float!(distr_uniform_f64, f64, Uniform::new(2.26f64, 2.319).unwrap());
 
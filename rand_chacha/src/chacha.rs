@@ -14,7 +14,7 @@
 use self::core::fmt;
 use crate::guts::ChaCha;
 use rand_core::block::{BlockRng, BlockRngCore, CryptoBlockRng};
-use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+use rand_core::{CryptoRng, Error, ParallelRng, RngCore, SeedableRng};
 
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
@@ -67,6 +67,32 @@ impl<T> fmt::Debug for Array64<T> {
     }
 }
 
+/// A position in the output of a ChaCha generator
+///
+/// This is the stream number and word position, as returned by
+/// `save_state` on [`ChaCha20Rng`] and the other ChaCha generators. It
+/// does not include the seed.
+///
+/// [`ChaCha20Rng`]: crate::ChaCha20Rng
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StreamPosition {
+    stream: u64,
+    word_pos: u128,
+}
+
+impl StreamPosition {
+    /// Get the stream number
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Get the offset from the start of the stream, in 32-bit words
+    pub fn word_pos(&self) -> u128 {
+        self.word_pos
+    }
+}
+
 macro_rules! chacha_impl {
     ($ChaChaXCore:ident, $ChaChaXRng:ident, $rounds:expr, $doc:expr, $abst:ident) => {
         #[doc=$doc]
@@ -101,6 +127,36 @@ macro_rules! chacha_impl {
 
         impl CryptoBlockRng for $ChaChaXCore {}
 
+        impl $ChaChaXCore {
+            // These allow seeking the core directly, e.g. when wrapped by a `BlockRng` other
+            // than that of the corresponding RNG. Unlike the methods of the RNG, they do not
+            // account for buffered output.
+
+            /// Get the position of the next block to be generated, in 16-word blocks.
+            #[inline]
+            pub fn get_block_pos(&self) -> u64 {
+                self.state.get_block_pos()
+            }
+
+            /// Set the position of the next block to be generated, in 16-word blocks.
+            #[inline]
+            pub fn set_block_pos(&mut self, block: u64) {
+                self.state.set_block_pos(block)
+            }
+
+            /// Get the stream number.
+            #[inline]
+            pub fn get_stream(&self) -> u64 {
+                self.state.get_nonce()
+            }
+
+            /// Set the stream number, affecting all blocks generated subsequently.
+            #[inline]
+            pub fn set_stream(&mut self, stream: u64) {
+                self.state.set_nonce(stream)
+            }
+        }
+
         /// A cryptographically secure random number generator that uses the ChaCha algorithm.
         ///
         /// ChaCha is a stream cipher designed by Daniel J. Bernstein[^1], that we use as an RNG. It is
@@ -256,6 +312,66 @@ macro_rules! chacha_impl {
                     .state
                     .get_seed()
             }
+
+            /// Save the current position in the output.
+            ///
+            /// Restoring this position via `restore_state` on a generator
+            /// with the same seed reproduces the output following this point.
+            /// This is cheaper than cloning the generator, and may be used to
+            /// run alternative scenarios of a simulation against identical
+            /// random inputs (common random numbers).
+            #[inline]
+            pub fn save_state(&self) -> StreamPosition {
+                StreamPosition {
+                    stream: self.get_stream(),
+                    word_pos: self.get_word_pos(),
+                }
+            }
+
+            /// Restore a position saved via `save_state`.
+            ///
+            /// This sets both the stream number and the word position.
+            #[inline]
+            pub fn restore_state(&mut self, pos: StreamPosition) {
+                self.rng
+                    .core
+                    .state
+                    .set_nonce(pos.stream);
+                self.set_word_pos(pos.word_pos);
+            }
+        }
+
+        impl ParallelRng for $ChaChaXRng {
+            /// Switch to the start of the next stream.
+            ///
+            /// Distinct streams of a ChaCha generator are independent, so
+            /// output after a jump never overlaps with output before it
+            /// (unless all 2<sup>64</sup> streams are exhausted).
+            #[inline]
+            fn jump(&mut self) {
+                self.rng
+                    .core
+                    .state
+                    .set_nonce(self.get_stream().wrapping_add(1));
+                self.set_word_pos(0);
+            }
+
+            /// Fork off a generator with a new key.
+            ///
+            /// The child is seeded from 32 bytes of output of `self`, as by
+            /// [`SeedableRng::from_rng`], rather than taking the next
+            /// stream: consecutive stream numbers would be reached again by
+            /// the parent's and children's own jumps. Since each generator
+            /// forked off this way has its own key, forking may be nested to
+            /// any depth without the streams of any two generators
+            /// overlapping (except with negligible probability, should two
+            /// keys collide).
+            #[inline]
+            fn fork(&mut self) -> Self {
+                let mut seed = [0u8; 32];
+                self.fill_bytes(&mut seed);
+                Self::from_seed(seed)
+            }
         }
 
         impl CryptoRng for $ChaChaXRng {}
@@ -551,6 +667,29 @@ mod test {
         assert_eq!(results, expected);
     }
 
+    #[test]
+    fn test_chacha_reduced_rounds_true_bytes() {
+        // Test vectors TC1 (all-zero key and IV) from
+        // https://datatracker.ietf.org/doc/html/draft-strombergson-chacha-test-vectors-00
+        fn first_block<R: RngCore>(mut rng: R) -> [u8; 32] {
+            let mut results = [0u8; 32];
+            rng.fill_bytes(&mut results);
+            results
+        }
+        let expected8 = [
+            0x3e, 0x00, 0xef, 0x2f, 0x89, 0x5f, 0x40, 0xd6, 0x7f, 0x5b, 0xb8, 0xe8, 0x1f, 0x09,
+            0xa5, 0xa1, 0x2c, 0x84, 0x0e, 0xc3, 0xce, 0x9a, 0x7f, 0x3b, 0x18, 0x1b, 0xe1, 0x88,
+            0xef, 0x71, 0x1a, 0x1e,
+        ];
+        assert_eq!(first_block(super::ChaCha8Rng::from_seed([0; 32])), expected8);
+        let expected12 = [
+            0x9b, 0xf4, 0x9a, 0x6a, 0x07, 0x55, 0xf9, 0x53, 0x81, 0x1f, 0xce, 0x12, 0x5f, 0x26,
+            0x83, 0xd5, 0x04, 0x29, 0xc3, 0xbb, 0x49, 0xe0, 0x74, 0x14, 0x7e, 0x00, 0x89, 0xa5,
+            0x2e, 0xae, 0x15, 0x5f,
+        ];
+        assert_eq!(first_block(super::ChaCha12Rng::from_seed([0; 32])), expected12);
+    }
+
     #[test]
     fn test_chacha_nonce() {
         // Test vector 5 from
@@ -624,6 +763,80 @@ mod test {
         assert_eq!(rng.get_word_pos(), 0);
     }
 
+    #[test]
+    fn test_chacha_core_seek() {
+        use super::ChaCha20Core;
+        use rand_core::block::BlockRng;
+        let mut core = ChaCha20Core::from_seed([5; 32]);
+        core.set_stream(9);
+        core.set_block_pos(3);
+        assert_eq!(core.get_stream(), 9);
+        assert_eq!(core.get_block_pos(), 3);
+        let mut block_rng = BlockRng::new(core);
+
+        let mut rng = ChaChaRng::from_seed([5; 32]);
+        rng.set_stream(9);
+        rng.set_word_pos(3 * 16);
+        for _ in 0..100 {
+            assert_eq!(block_rng.next_u32(), rng.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_chacha_save_restore_state() {
+        let mut rng = ChaChaRng::from_seed([7; 32]);
+        rng.set_stream(3);
+        for _ in 0..21 {
+            rng.next_u32();
+        }
+        let pos = rng.save_state();
+        assert_eq!((pos.stream(), pos.word_pos()), (3, 21));
+        let mut a = [0u64; 20];
+        a.iter_mut().for_each(|x| *x = rng.next_u64());
+
+        rng.set_stream(4);
+        rng.next_u32();
+        rng.restore_state(pos);
+        assert_eq!(rng.save_state(), pos);
+        let mut b = [0u64; 20];
+        b.iter_mut().for_each(|x| *x = rng.next_u64());
+        assert_eq!(a, b);
+
+        // Another generator with the same seed reproduces the output too
+        let mut rng2 = ChaChaRng::from_seed([7; 32]);
+        rng2.restore_state(pos);
+        assert_eq!(rng2.next_u64(), a[0]);
+    }
+
+    #[test]
+    fn test_chacha_fork() {
+        use rand_core::ParallelRng;
+
+        let mut rng = ChaChaRng::from_seed([7; 32]);
+        rng.set_stream(5);
+        let mut expected = rng.clone();
+        let mut child = rng.fork();
+        assert_eq!(child, ChaChaRng::from_rng(&mut expected).unwrap());
+        assert_eq!(rng, expected);
+
+        // Nested forks and jumps do not reach the streams of siblings
+        let mut sibling = rng.fork();
+        let mut grandchild = child.fork();
+        child.jump();
+        let mut outputs = [
+            rng.next_u64(),
+            child.next_u64(),
+            sibling.next_u64(),
+            grandchild.next_u64(),
+        ];
+        outputs.sort_unstable();
+        for i in 1..outputs.len() {
+            assert_ne!(outputs[i - 1], outputs[i]);
+        }
+        assert_ne!(child.get_seed(), sibling.get_seed());
+        assert_ne!(child.get_seed(), grandchild.get_seed());
+    }
+
     #[test]
     fn test_trait_objects() {
         use rand_core::CryptoRng;
@@ -0,0 +1,281 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A DRBG-style interface over ChaCha20.
+
+#[cfg(not(feature = "std"))] use core;
+#[cfg(feature = "std")] use std as core;
+
+use self::core::fmt;
+use crate::ChaCha20Rng;
+use rand_core::{impls, CryptoRng, Error, RngCore, SeedableRng};
+
+/// Domain separation tags for the input of each operation
+const TAG_INSTANTIATE: u64 = 1;
+const TAG_RESEED: u64 = 2;
+const TAG_ADDITIONAL_INPUT: u64 = 3;
+
+/// A deterministic random bit generator (DRBG) built on ChaCha20.
+///
+/// This offers the interface of the DRBG mechanisms of
+/// [NIST SP 800-90A](https://csrc.nist.gov/publications/detail/sp/800-90a/rev-1/final):
+///
+/// - [`instantiate`] from entropy input, a nonce and an optional
+///   personalization string
+/// - [`reseed`] with fresh entropy input and optional additional input
+/// - [`generate`] output with optional additional input
+///
+/// Note that ChaCha20 is *not* one of the mechanisms approved by SP 800-90A;
+/// this type provides similar semantics, not compliance.
+///
+/// # Construction
+///
+/// The state is a single 256-bit ChaCha20 key. Inputs are mixed into the key
+/// by a derivation function: a length-prefixed encoding of the inputs is
+/// absorbed 16 bytes at a time, each block being used as the counter and
+/// nonce of ChaCha20 under the current key, with the first 32 bytes of
+/// output becoming the next key. Each request to `generate` outputs the
+/// ChaCha20 keystream under the current key and then replaces the key with
+/// further keystream ("fast key erasure"), thus earlier output cannot be
+/// reconstructed from a later state (backtracking resistance).
+///
+/// Since each request to [`generate`] (including each call to an
+/// [`RngCore`] method) derives a new key, this generator is best suited to
+/// fewer, larger requests. Wrap it in a [`ChaCha20Rng`] seeded from its output
+/// for many small requests.
+///
+/// # Example
+///
+/// ```
+/// use rand_chacha::ChaCha20Drbg;
+///
+/// # let (entropy, nonce) = ([7u8; 32], [1u8; 16]);
+/// let mut drbg = ChaCha20Drbg::instantiate(&entropy, &nonce, b"my application v1");
+/// let mut key = [0u8; 32];
+/// drbg.generate(&mut key, b"session 42");
+///
+/// # let fresh_entropy = [9u8; 32];
+/// drbg.reseed(&fresh_entropy, b"");
+/// assert_eq!(drbg.reseed_counter(), 1);
+/// ```
+///
+/// [`instantiate`]: ChaCha20Drbg::instantiate
+/// [`reseed`]: ChaCha20Drbg::reseed
+/// [`generate`]: ChaCha20Drbg::generate
+#[derive(Clone)]
+pub struct ChaCha20Drbg {
+    key: [u8; 32],
+    reseed_counter: u64,
+}
+
+impl ChaCha20Drbg {
+    /// Instantiate from `entropy` input, a `nonce` and a `personalization`
+    /// string (which may be empty).
+    ///
+    /// The entropy input should contain at least 256 bits of entropy.
+    pub fn instantiate(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let key = absorb(&[0; 32], TAG_INSTANTIATE, &[entropy, nonce, personalization]);
+        ChaCha20Drbg {
+            key,
+            reseed_counter: 1,
+        }
+    }
+
+    /// Reseed with fresh `entropy` input and `additional_input` (which may
+    /// be empty).
+    ///
+    /// Unlike construction of a new generator, the new state depends on both
+    /// the old state and the inputs.
+    pub fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        self.key = absorb(&self.key, TAG_RESEED, &[entropy, additional_input]);
+        self.reseed_counter = 1;
+    }
+
+    /// Fill `dest` with output, after mixing in `additional_input` (which may
+    /// be empty).
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) {
+        if !additional_input.is_empty() {
+            self.key = absorb(&self.key, TAG_ADDITIONAL_INPUT, &[additional_input]);
+        }
+        let mut rng = ChaCha20Rng::from_seed(self.key);
+        rng.fill_bytes(dest);
+        rng.fill_bytes(&mut self.key);
+        self.reseed_counter = self.reseed_counter.saturating_add(1);
+    }
+
+    /// The number of requests to `generate` since instantiation or the last
+    /// reseed, plus one.
+    ///
+    /// Applications enforcing a reseed interval should compare this against
+    /// their limit before each request.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+}
+
+/// Mix the length-prefixed encoding of `tag` and `inputs` into `key`
+fn absorb(key: &[u8; 32], tag: u64, inputs: &[&[u8]]) -> [u8; 32] {
+    // The first block identifies the operation and the number of inputs,
+    // which makes the encoding of different operations distinct.
+    let mut key = prf(key, inputs.len() as u64, tag);
+    let mut block = [0u8; 16];
+    let mut len = 0;
+    let mut push = |key: &mut [u8; 32], bytes: &[u8]| {
+        for &b in bytes {
+            block[len] = b;
+            len += 1;
+            if len == block.len() {
+                *key = prf_block(key, &block);
+                len = 0;
+            }
+        }
+    };
+    for input in inputs {
+        push(&mut key, &(input.len() as u64).to_le_bytes());
+        push(&mut key, input);
+    }
+    if len > 0 {
+        for b in &mut block[len..] {
+            *b = 0;
+        }
+        key = prf_block(&key, &block);
+    }
+    key
+}
+
+fn prf_block(key: &[u8; 32], block: &[u8; 16]) -> [u8; 32] {
+    let mut counter = [0; 8];
+    let mut stream = [0; 8];
+    counter.copy_from_slice(&block[..8]);
+    stream.copy_from_slice(&block[8..]);
+    prf(key, u64::from_le_bytes(counter), u64::from_le_bytes(stream))
+}
+
+/// First 32 bytes of the ChaCha20 block at position `counter` of `stream`
+fn prf(key: &[u8; 32], counter: u64, stream: u64) -> [u8; 32] {
+    let mut rng = ChaCha20Rng::from_seed(*key);
+    rng.set_stream(stream);
+    rng.set_word_pos(u128::from(counter) * 16);
+    let mut out = [0; 32];
+    rng.fill_bytes(&mut out);
+    out
+}
+
+impl RngCore for ChaCha20Drbg {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.generate(dest, &[])
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ChaCha20Drbg {}
+
+impl fmt::Debug for ChaCha20Drbg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not print the key
+        f.debug_struct("ChaCha20Drbg")
+            .field("reseed_counter", &self.reseed_counter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn output(drbg: &mut ChaCha20Drbg, additional_input: &[u8]) -> [u8; 16] {
+        let mut buf = [0; 16];
+        drbg.generate(&mut buf, additional_input);
+        buf
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let a = output(&mut ChaCha20Drbg::instantiate(b"entropy", b"nonce", b""), b"");
+        assert_eq!(a, output(&mut ChaCha20Drbg::instantiate(b"entropy", b"nonce", b""), b""));
+
+        // Any change of input changes the output, including moving bytes
+        // between inputs
+        let others = [
+            ChaCha20Drbg::instantiate(b"entropy", b"nonce", b"x"),
+            ChaCha20Drbg::instantiate(b"entropy", b"nonc", b"e"),
+            ChaCha20Drbg::instantiate(b"entropx", b"nonce", b""),
+            ChaCha20Drbg::instantiate(b"entropy", b"nonce\0", b""),
+        ];
+        for mut drbg in others {
+            assert_ne!(a, output(&mut drbg, b""));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut drbg = ChaCha20Drbg::instantiate(&[1; 32], &[2; 16], b"test");
+        let mut copy = drbg.clone();
+        let a = output(&mut drbg, b"");
+        assert_eq!(a, output(&mut copy, b""));
+        assert_eq!(drbg.reseed_counter(), 2);
+
+        // Requests never repeat output, and additional input is mixed in
+        let b = output(&mut drbg, b"");
+        let c = output(&mut copy, b"extra");
+        assert!(a != b && a != c && b != c);
+
+        // The RngCore methods are equivalent to requests without additional
+        // input
+        let mut copy = drbg.clone();
+        let x = drbg.next_u64();
+        assert_eq!(x.to_le_bytes(), output(&mut copy, b"")[..8]);
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut drbg = ChaCha20Drbg::instantiate(&[1; 32], &[2; 16], b"");
+        let mut other = ChaCha20Drbg::instantiate(&[3; 32], &[2; 16], b"");
+        let _ = output(&mut drbg, b"");
+        drbg.reseed(&[4; 32], b"");
+        other.reseed(&[4; 32], b"");
+        assert_eq!(drbg.reseed_counter(), 1);
+        // The new state depends on the previous state
+        assert_ne!(output(&mut drbg, b""), output(&mut other, b""));
+    }
+
+    #[test]
+    fn test_value_stability() {
+        let mut drbg = ChaCha20Drbg::instantiate(&[0; 32], &[0; 16], b"");
+        assert_eq!(output(&mut drbg, b""), [
+            238, 223, 21, 125, 52, 69, 105, 212, 28, 163, 151, 167, 15, 159, 249, 43
+        ]);
+        drbg.reseed(&[1; 32], b"additional");
+        assert_eq!(output(&mut drbg, b"input"), [
+            127, 198, 98, 178, 138, 138, 30, 15, 115, 156, 168, 6, 21, 24, 200, 112
+        ]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_debug() {
+        let drbg = ChaCha20Drbg::instantiate(&[0; 32], &[0; 16], b"");
+        assert_eq!(format!("{:?}", drbg), "ChaCha20Drbg { reseed_counter: 1, .. }");
+    }
+}
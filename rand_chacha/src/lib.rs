@@ -22,11 +22,13 @@
 pub use rand_core;
 
 mod chacha;
+mod drbg;
 mod guts;
 
 pub use crate::chacha::{
-    ChaCha12Core, ChaCha12Rng, ChaCha20Core, ChaCha20Rng, ChaCha8Core, ChaCha8Rng,
+    ChaCha12Core, ChaCha12Rng, ChaCha20Core, ChaCha20Rng, ChaCha8Core, ChaCha8Rng, StreamPosition,
 };
+pub use crate::drbg::ChaCha20Drbg;
 
 /// ChaCha with 20 rounds
 pub type ChaChaRng = ChaCha20Rng;
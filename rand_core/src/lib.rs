@@ -39,7 +39,6 @@
 #![no_std]
 
 use core::convert::AsMut;
-use core::default::Default;
 
 #[cfg(feature = "alloc")] extern crate alloc;
 #[cfg(feature = "std")] extern crate std;
@@ -215,6 +214,23 @@ pub trait RngCore {
 /// [`BlockRngCore`]: block::BlockRngCore
 pub trait CryptoRng: RngCore {}
 
+/// A buffer of bytes usable as the seed of a [`SeedableRng`].
+///
+/// This is implemented for byte arrays `[u8; N]` of any length `N`. Other
+/// seed types (e.g. newtypes over arrays) may implement it by constructing an
+/// all-zero value.
+pub trait SeedBytes: Sized + AsMut<[u8]> {
+    /// Construct a seed with all bytes zero.
+    fn zeroed() -> Self;
+}
+
+impl<const N: usize> SeedBytes for [u8; N] {
+    #[inline]
+    fn zeroed() -> Self {
+        [0; N]
+    }
+}
+
 /// A random number generator that can be explicitly seeded.
 ///
 /// This trait encapsulates the low-level functionality common to all
@@ -222,8 +238,8 @@ pub trait CryptoRng: RngCore {}
 ///
 /// [`rand`]: https://docs.rs/rand
 pub trait SeedableRng: Sized {
-    /// Seed type, which is restricted to types mutably-dereferenceable as `u8`
-    /// arrays (we recommend `[u8; N]` for some `N`).
+    /// Seed type, which is restricted to byte buffers implementing
+    /// [`SeedBytes`] (we recommend `[u8; N]` for some `N`).
     ///
     /// It is recommended to seed PRNGs with a seed of at least circa 100 bits,
     /// which means an array of `[u8; 12]` or greater to avoid picking RNGs with
@@ -231,43 +247,26 @@ pub trait SeedableRng: Sized {
     ///
     /// For cryptographic RNG's a seed of 256 bits is recommended, `[u8; 32]`.
     ///
-    ///
-    /// # Implementing `SeedableRng` for RNGs with large seeds
-    ///
-    /// Note that the required traits `core::default::Default` and
-    /// `core::convert::AsMut<u8>` are not implemented for large arrays
-    /// `[u8; N]` with `N` > 32. To be able to implement the traits required by
-    /// `SeedableRng` for RNGs with such large seeds, the newtype pattern can be
-    /// used:
+    /// Arrays `[u8; N]` of any length implement [`SeedBytes`], including
+    /// large seeds:
     ///
     /// ```
     /// use rand_core::SeedableRng;
     ///
-    /// const N: usize = 64;
-    /// pub struct MyRngSeed(pub [u8; N]);
-    /// pub struct MyRng(MyRngSeed);
-    ///
-    /// impl Default for MyRngSeed {
-    ///     fn default() -> MyRngSeed {
-    ///         MyRngSeed([0; N])
-    ///     }
-    /// }
-    ///
-    /// impl AsMut<[u8]> for MyRngSeed {
-    ///     fn as_mut(&mut self) -> &mut [u8] {
-    ///         &mut self.0
-    ///     }
-    /// }
+    /// pub struct MyRng([u8; 64]);
     ///
     /// impl SeedableRng for MyRng {
-    ///     type Seed = MyRngSeed;
+    ///     type Seed = [u8; 64];
     ///
-    ///     fn from_seed(seed: MyRngSeed) -> MyRng {
+    ///     fn from_seed(seed: [u8; 64]) -> MyRng {
     ///         MyRng(seed)
     ///     }
     /// }
+    ///
+    /// let rng = MyRng::seed_from_u64(1);
+    /// assert_ne!(rng.0, [0; 64]);
     /// ```
-    type Seed: Sized + Default + AsMut<[u8]>;
+    type Seed: SeedBytes;
 
     /// Create a new PRNG using the given seed.
     ///
@@ -328,7 +327,7 @@ pub trait SeedableRng: Sized {
             x.to_le_bytes()
         }
 
-        let mut seed = Self::Seed::default();
+        let mut seed = Self::Seed::zeroed();
         let mut iter = seed.as_mut().chunks_exact_mut(4);
         for chunk in &mut iter {
             chunk.copy_from_slice(&pcg32(&mut state));
@@ -366,7 +365,7 @@ pub trait SeedableRng: Sized {
     ///
     /// [`rand`]: https://docs.rs/rand
     fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
-        let mut seed = Self::Seed::default();
+        let mut seed = Self::Seed::zeroed();
         rng.try_fill_bytes(seed.as_mut())?;
         Ok(Self::from_seed(seed))
     }
@@ -383,17 +382,116 @@ pub trait SeedableRng: Sized {
     /// # Panics
     ///
     /// If [`getrandom`] is unable to provide secure entropy this method will panic.
+    /// Use [`SeedableRng::try_from_entropy`] to handle this case instead.
     ///
     /// [`getrandom`]: https://docs.rs/getrandom
     #[cfg(feature = "getrandom")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
     fn from_entropy() -> Self {
-        let mut seed = Self::Seed::default();
+        let mut seed = Self::Seed::zeroed();
         if let Err(err) = getrandom::getrandom(seed.as_mut()) {
             panic!("from_entropy failed: {}", err);
         }
         Self::from_seed(seed)
     }
+
+    /// Creates a new instance of the RNG seeded via [`getrandom`], without
+    /// panicking on failure.
+    ///
+    /// This is identical to [`SeedableRng::from_entropy`] except that an
+    /// error is returned if [`getrandom`] is unable to provide secure
+    /// entropy, allowing the caller to retry or report the failure.
+    ///
+    /// [`getrandom`]: https://docs.rs/getrandom
+    #[cfg(feature = "getrandom")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
+    fn try_from_entropy() -> Result<Self, Error> {
+        let mut seed = Self::Seed::zeroed();
+        getrandom::getrandom(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
+    }
+}
+
+/// A generator able to provide independent streams for parallel use.
+///
+/// Worker threads (or jobs) of a parallel computation each need their own
+/// generator; seeding each from a common parent via
+/// [`SeedableRng::from_rng`] is possible but gives no guarantee that the
+/// sequences do not overlap. Generators implementing this trait can instead
+/// *jump* ahead by a fixed distance far beyond any practical amount of
+/// output, or switch to a distinct stream, such that the sequences before
+/// and after a jump do not overlap.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(dead_code)]
+/// use rand_core::ParallelRng;
+///
+/// fn spawn_jobs<R: ParallelRng>(rng: &mut R, jobs: usize) {
+///     for _ in 0..jobs {
+///         let mut child = rng.fork();
+///         // ... hand `child` to a worker thread
+///         child.next_u64();
+///     }
+/// }
+/// ```
+pub trait ParallelRng: RngCore + Clone {
+    /// Jump to a new, non-overlapping part of the output sequence.
+    ///
+    /// The distance jumped is implementation-defined but fixed, and large
+    /// enough that output from before the jump will not be repeated after
+    /// it in practice.
+    fn jump(&mut self);
+
+    /// Fork off a generator for an independent stream.
+    ///
+    /// By default, the returned generator continues from the current state of
+    /// `self`, while `self` jumps ahead. Repeated calls thus return generators
+    /// with mutually non-overlapping output. Forking a generator which was
+    /// itself forked off may however overlap with the output of its parent,
+    /// unless the implementation documents otherwise; see [`SplitRng`] for
+    /// nested splitting.
+    fn fork(&mut self) -> Self {
+        let child = self.clone();
+        self.jump();
+        child
+    }
+}
+
+/// A generator which may be split recursively into independent generators.
+///
+/// Unlike [`ParallelRng::fork`], which hands out consecutive parts of a
+/// single sequence, splitting with this trait is safe to nest: a generator
+/// split off from another may itself be split, to any depth, and no two
+/// generators in the resulting tree produce overlapping or correlated
+/// output. This suits divide-and-conquer and functional-style code, where
+/// each recursive call splits off the generators of its sub-problems without
+/// coordinating with any other part of the computation.
+///
+/// Splitting is deterministic: the result depends only on the state of
+/// `self`, thus a computation splitting in a fixed pattern is reproducible.
+/// Splitting updates `self` such that repeated calls return distinct
+/// generators.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(dead_code)]
+/// use rand_core::SplitRng;
+///
+/// fn simulate<R: SplitRng>(rng: &mut R, depth: u32) -> u64 {
+///     if depth == 0 {
+///         return rng.next_u64() % 2;
+///     }
+///     let mut left = rng.split();
+///     let mut right = rng.split();
+///     simulate(&mut left, depth - 1) + simulate(&mut right, depth - 1)
+/// }
+/// ```
+pub trait SplitRng: RngCore + Sized {
+    /// Split off a new, independent generator.
+    fn split(&mut self) -> Self;
 }
 
 // Implement `RngCore` for references to an `RngCore`.
@@ -449,6 +547,14 @@ impl<R: RngCore + ?Sized> RngCore for Box<R> {
 
 /// Adapter that enables reading through a [`io::Read`](std::io::Read) from a [`RngCore`].
 ///
+/// The stream of bytes never ends: each call to `read` fills the whole
+/// buffer via [`RngCore::try_fill_bytes`], whose errors are passed on. Use
+/// [`Read::take`](std::io::Read::take) to read a given number of bytes.
+///
+/// This is created by [`RngCore::read_adapter`], or by
+/// [`RngReadAdapter::new`] which also supports unsized RNGs such as
+/// `dyn RngCore`.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -463,6 +569,14 @@ pub struct RngReadAdapter<'a, R: RngCore + ?Sized> {
     inner: &'a mut R,
 }
 
+#[cfg(feature = "std")]
+impl<'a, R: RngCore + ?Sized> RngReadAdapter<'a, R> {
+    /// Construct, reading from `rng`.
+    pub fn new(rng: &'a mut R) -> Self {
+        RngReadAdapter { inner: rng }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<R: RngCore + ?Sized> std::io::Read for RngReadAdapter<'_, R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
@@ -529,4 +643,58 @@ mod test {
         // value-breakage test:
         assert_eq!(results[0], 5029875928683246316);
     }
+
+    #[test]
+    fn test_large_seed() {
+        struct LargeSeed([u8; 48]);
+        impl SeedableRng for LargeSeed {
+            type Seed = [u8; 48];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                LargeSeed(seed)
+            }
+        }
+
+        let LargeSeed(seed) = LargeSeed::seed_from_u64(0);
+        // The first bytes are as for shorter seeds
+        assert_eq!(seed[..8], 5029875928683246316u64.to_le_bytes());
+        assert_ne!(seed[40..], [0; 8]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_adapter() {
+        use std::io::{self, Read};
+
+        struct CountingBytes(u8);
+        impl RngCore for CountingBytes {
+            fn next_u32(&mut self) -> u32 {
+                impls::next_u32_via_fill(self)
+            }
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_fill(self)
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for x in dest {
+                    *x = self.0;
+                    self.0 = self.0.wrapping_add(1);
+                }
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let mut rng = CountingBytes(0);
+        let mut out = std::vec::Vec::new();
+        io::copy(&mut rng.read_adapter().take(5), &mut out).unwrap();
+        assert_eq!(out, [0, 1, 2, 3, 4]);
+
+        // Unsized RNGs are supported via `new`
+        let dyn_rng: &mut dyn RngCore = &mut rng;
+        let mut buf = [0u8; 3];
+        RngReadAdapter::new(dyn_rng).read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [5, 6, 7]);
+    }
 }
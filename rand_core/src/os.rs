@@ -34,6 +34,10 @@ use getrandom::getrandom;
 /// significant delays will occur (although performance should be expected to
 /// be much slower than a user-space PRNG).
 ///
+/// Where blocking is not acceptable (e.g. within an async runtime or an init
+/// system), use [`OsRng::is_ready`] or [`OsRng::try_fill_bytes_nonblocking`]
+/// (`std` only).
+///
 /// # Usage example
 /// ```
 /// use rand_core::{RngCore, OsRng};
@@ -50,6 +54,52 @@ pub struct OsRng;
 
 impl CryptoRng for OsRng {}
 
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl OsRng {
+    /// Returns `true` if the system's RNG is initialised, thus if `OsRng`
+    /// will not block.
+    ///
+    /// On Linux and Android, this queries the kernel via the `getrandom`
+    /// system call with the `GRND_NONBLOCK` flag (or, on kernels older than
+    /// 3.17, by polling `/dev/random`). Once initialised, the system's RNG
+    /// stays initialised. On other platforms, the system's RNG is assumed to
+    /// be ready and this always returns `true`.
+    ///
+    /// Errors in querying the system's RNG are reported as not ready.
+    pub fn is_ready() -> bool {
+        matches!(nonblocking::is_ready(), Ok(true))
+    }
+
+    /// Fill `dest` with random data, failing instead of blocking if the
+    /// system's RNG is not initialised yet.
+    ///
+    /// In that case the error has [`Error::raw_os_error`] `EAGAIN` and
+    /// converts to a [`std::io::Error`] of kind
+    /// [`WouldBlock`](std::io::ErrorKind::WouldBlock), thus the call may be
+    /// retried later. See [`OsRng::is_ready`] for details on the supported
+    /// platforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_core::OsRng;
+    ///
+    /// let mut key = [0u8; 16];
+    /// if let Err(e) = OsRng.try_fill_bytes_nonblocking(&mut key) {
+    ///     let e = std::io::Error::from(e);
+    ///     if e.kind() != std::io::ErrorKind::WouldBlock {
+    ///         panic!("Error: {}", e);
+    ///     }
+    ///     // Otherwise try again later
+    /// }
+    /// ```
+    pub fn try_fill_bytes_nonblocking(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        nonblocking::check_ready()?;
+        self.try_fill_bytes(dest)
+    }
+}
+
 impl RngCore for OsRng {
     fn next_u32(&mut self) -> u32 {
         impls::next_u32_via_fill(self)
@@ -71,6 +121,89 @@ impl RngCore for OsRng {
     }
 }
 
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+mod nonblocking {
+    use crate::Error;
+    use core::num::NonZeroU32;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    pub(super) fn check_ready() -> Result<(), Error> {
+        if is_ready()? {
+            Ok(())
+        } else {
+            Err(os_error(libc::EAGAIN))
+        }
+    }
+
+    fn os_error(errno: i32) -> Error {
+        match NonZeroU32::new(errno as u32) {
+            Some(code) => Error::from(code),
+            None => Error::new("unexpected error code from the OS"),
+        }
+    }
+
+    pub(super) fn is_ready() -> Result<bool, Error> {
+        let mut byte = 0u8;
+        loop {
+            // SAFETY: the buffer is valid for writes of one byte
+            let ret = unsafe {
+                libc::syscall(
+                    libc::SYS_getrandom,
+                    &mut byte as *mut u8,
+                    1usize,
+                    libc::GRND_NONBLOCK,
+                )
+            };
+            if ret >= 0 {
+                return Ok(true);
+            }
+            match io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                Some(libc::EAGAIN) => return Ok(false),
+                Some(libc::ENOSYS) => return poll_dev_random(),
+                Some(errno) => return Err(os_error(errno)),
+                None => return Err(os_error(0)),
+            }
+        }
+    }
+
+    // Fallback for kernels without getrandom: /dev/random becomes readable
+    // once the pool is initialised
+    fn poll_dev_random() -> Result<bool, Error> {
+        let file = std::fs::File::open("/dev/random").map_err(Error::new)?;
+        let mut pfd = libc::pollfd {
+            fd: file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        loop {
+            // SAFETY: `pfd` is a valid array of one `pollfd`
+            let ret = unsafe { libc::poll(&mut pfd, 1, 0) };
+            if ret >= 0 {
+                return Ok(ret == 1);
+            }
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::EINTR) {
+                return Err(Error::new(err));
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "std", not(any(target_os = "linux", target_os = "android"))))]
+mod nonblocking {
+    use crate::Error;
+
+    pub(super) fn check_ready() -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub(super) fn is_ready() -> Result<bool, Error> {
+        Ok(true)
+    }
+}
+
 #[test]
 fn test_os_rng() {
     let x = OsRng.next_u64();
@@ -84,3 +217,13 @@ fn test_construction() {
     let mut rng = OsRng::default();
     assert!(rng.next_u64() != 0);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_nonblocking() {
+    // The system's RNG is initialised long before tests run
+    assert!(OsRng::is_ready());
+    let mut buf = [0u8; 32];
+    OsRng.try_fill_bytes_nonblocking(&mut buf).unwrap();
+    assert!(buf.iter().any(|&b| b != 0));
+}
@@ -0,0 +1,127 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sparse Bernoulli trials via geometric skips.
+
+use crate::{Distribution, GeoError, Geometric};
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use rand::Rng;
+
+/// An iterator over the successes in a sequence of Bernoulli trials.
+///
+/// This yields, in increasing order, the indices of the trials in `0..n`
+/// which succeed, where each trial independently succeeds with probability
+/// `p`. Rather than testing every trial, the gap between successes is
+/// sampled from the [`Geometric`] distribution, so iterating takes
+/// `O(n p)` expected time instead of `O(n)`. This makes sparse random
+/// selection over huge index spaces cheap, e.g. selecting each of `2^40`
+/// candidates with probability `1e-9`.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::BernoulliSkip;
+///
+/// // Sample about 0.1% of a large index space
+/// let selected = BernoulliSkip::new(rand::thread_rng(), 0.001, 1 << 40).unwrap();
+/// for index in selected.take(10) {
+///     assert!(index < 1 << 40);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BernoulliSkip<R> {
+    rng: R,
+    gap: Geometric,
+    // The first trial not yet decided
+    next: u64,
+    end: u64,
+}
+
+impl<R: Rng> BernoulliSkip<R> {
+    /// Construct an iterator over the successes of `n` trials, each
+    /// succeeding with probability `p`.
+    ///
+    /// Fails if `p` is not in `[0, 1]`.
+    pub fn new(rng: R, p: f64, n: u64) -> Result<Self, GeoError> {
+        Ok(BernoulliSkip {
+            rng,
+            gap: Geometric::new(p)?,
+            next: 0,
+            end: n,
+        })
+    }
+}
+
+impl<R: Rng> Iterator for BernoulliSkip<R> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next >= self.end {
+            return None;
+        }
+        // The number of failures before the next success
+        let failures = self.gap.sample(&mut self.rng);
+        match self.next.checked_add(failures) {
+            Some(index) if index < self.end => {
+                self.next = index + 1;
+                Some(index)
+            }
+            _ => {
+                self.next = self.end;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, usize::try_from(self.end - self.next).ok())
+    }
+}
+
+impl<R: Rng> FusedIterator for BernoulliSkip<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bernoulli_skip() {
+        let mut rng = crate::test::rng(246);
+        assert!(BernoulliSkip::new(&mut rng, 1.5, 10).is_err());
+        assert_eq!(BernoulliSkip::new(&mut rng, 0.0, u64::MAX).unwrap().next(), None);
+        assert!(BernoulliSkip::new(&mut rng, 1.0, 10).unwrap().eq(0..10));
+        assert_eq!(BernoulliSkip::new(&mut rng, 1.0, 0).unwrap().next(), None);
+
+        let mut prev = None;
+        let mut count = 0;
+        for index in BernoulliSkip::new(&mut rng, 0.01, 1_000_000).unwrap() {
+            assert!(index < 1_000_000);
+            assert!(prev < Some(index));
+            prev = Some(index);
+            count += 1;
+        }
+        // Expect 10_000, with standard deviation about 100
+        assert!((9500..10500).contains(&count), "{}", count);
+
+        // Near the end of the index space
+        let mut iter = BernoulliSkip::new(&mut rng, 0.5, u64::MAX).unwrap();
+        iter.next = u64::MAX - 100;
+        assert!(iter.all(|i| i >= u64::MAX - 100));
+    }
+
+    #[test]
+    fn value_stability() {
+        let iter = BernoulliSkip::new(crate::test::rng(247), 0.1, 100).unwrap();
+        let mut buf = [0; 6];
+        for (x, i) in buf.iter_mut().zip(iter) {
+            *x = i;
+        }
+        assert_eq!(buf, [4, 5, 7, 28, 38, 54]);
+    }
+}
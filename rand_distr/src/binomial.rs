@@ -9,7 +9,7 @@
 
 //! The binomial distribution.
 
-use crate::{Distribution, Uniform};
+use crate::{Distribution, StandardNormal, Uniform};
 use rand::Rng;
 use core::fmt;
 use core::cmp::Ordering;
@@ -300,9 +300,136 @@ impl Distribution<u64> for Binomial {
     }
 }
 
+/// The proportion of successes `k / n` of the binomial distribution
+/// `Binomial(n, p)`.
+///
+/// This is useful when only the observed rate matters, e.g. when simulating
+/// the outcome of A/B tests. The result is the same as sampling `k` from
+/// [`Binomial`] and dividing by `n`, except that when the variance
+/// `n * p * (1 - p)` exceeds a threshold (by default
+/// [`BinomialProportion::DEFAULT_THRESHOLD`]) `k` is drawn from the normal
+/// approximation `N(n * p, n * p * (1 - p))`, rounded with continuity
+/// correction and clamped to `0..=n`. This is fast for any `n`, including
+/// `n` beyond the range supported by the exact algorithm.
+///
+/// At the default threshold the skewness of the binomial distribution is
+/// below `1e-3`, thus the approximation is visually indistinguishable from
+/// the exact distribution.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{BinomialProportion, Distribution};
+///
+/// // Conversion rate observed from a billion visitors
+/// let rate = BinomialProportion::new(1_000_000_000, 0.031).unwrap();
+/// let v = rate.sample(&mut rand::thread_rng());
+/// assert!((v - 0.031).abs() < 0.001);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialProportion {
+    /// Number of trials.
+    n: u64,
+    /// Probability of success.
+    p: f64,
+    /// Whether to use the normal approximation.
+    approximate: bool,
+}
+
+/// Error type returned from `BinomialProportion::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinomialProportionError {
+    /// `n == 0`.
+    NoTrials,
+    /// `p < 0` or `nan`.
+    ProbabilityTooSmall,
+    /// `p > 1`.
+    ProbabilityTooLarge,
+    /// `threshold < 0` or `nan`.
+    InvalidThreshold,
+}
+
+impl fmt::Display for BinomialProportionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BinomialProportionError::NoTrials => "n is zero in binomial proportion distribution",
+            BinomialProportionError::ProbabilityTooSmall => {
+                "p < 0 or is NaN in binomial proportion distribution"
+            }
+            BinomialProportionError::ProbabilityTooLarge => {
+                "p > 1 in binomial proportion distribution"
+            }
+            BinomialProportionError::InvalidThreshold => {
+                "threshold < 0 or is NaN in binomial proportion distribution"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for BinomialProportionError {}
+
+impl BinomialProportion {
+    /// Variance `n * p * (1 - p)` above which the normal approximation is
+    /// used by [`BinomialProportion::new`].
+    pub const DEFAULT_THRESHOLD: f64 = 1e6;
+
+    /// Construct a new `BinomialProportion` with the given number of trials
+    /// `n` and probability of success `p`.
+    pub fn new(n: u64, p: f64) -> Result<BinomialProportion, BinomialProportionError> {
+        Self::with_threshold(n, p, Self::DEFAULT_THRESHOLD)
+    }
+
+    /// Construct a new `BinomialProportion`, using the normal approximation
+    /// when the variance `n * p * (1 - p)` exceeds `threshold`.
+    ///
+    /// A `threshold` of infinity always uses exact sampling, which panics
+    /// at sampling time if `n * p` does not fit in an `i64`.
+    pub fn with_threshold(
+        n: u64, p: f64, threshold: f64,
+    ) -> Result<BinomialProportion, BinomialProportionError> {
+        if n == 0 {
+            return Err(BinomialProportionError::NoTrials);
+        }
+        if !(p >= 0.0) {
+            return Err(BinomialProportionError::ProbabilityTooSmall);
+        }
+        if !(p <= 1.0) {
+            return Err(BinomialProportionError::ProbabilityTooLarge);
+        }
+        if !(threshold >= 0.0) {
+            return Err(BinomialProportionError::InvalidThreshold);
+        }
+        let variance = n as f64 * p * (1.0 - p);
+        Ok(BinomialProportion {
+            n,
+            p,
+            approximate: variance > threshold,
+        })
+    }
+}
+
+impl Distribution<f64> for BinomialProportion {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let n = self.n as f64;
+        let k = if self.approximate {
+            let mean = n * self.p;
+            let std_dev = (mean * (1.0 - self.p)).sqrt();
+            let z: f64 = rng.sample(StandardNormal);
+            // Continuity correction: k is the integer nearest to the sample
+            (mean + std_dev * z + 0.5).floor().max(0.0).min(n)
+        } else {
+            Binomial { n: self.n, p: self.p }.sample(rng) as f64
+        };
+        k / n
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Binomial;
+    use super::{Binomial, BinomialProportion, BinomialProportionError};
     use crate::Distribution;
     use rand::Rng;
 
@@ -348,6 +475,60 @@ mod test {
         Binomial::new(20, -10.0).unwrap();
     }
 
+    #[test]
+    fn test_binomial_proportion() {
+        let mut rng = crate::test::rng(353);
+        assert_eq!(BinomialProportion::new(0, 0.5), Err(BinomialProportionError::NoTrials));
+        assert_eq!(
+            BinomialProportion::new(10, f64::NAN),
+            Err(BinomialProportionError::ProbabilityTooSmall)
+        );
+        assert_eq!(
+            BinomialProportion::new(10, 1.5),
+            Err(BinomialProportionError::ProbabilityTooLarge)
+        );
+        assert_eq!(
+            BinomialProportion::with_threshold(10, 0.5, -1.0),
+            Err(BinomialProportionError::InvalidThreshold)
+        );
+
+        // Exact sampling matches `Binomial`
+        let exact = BinomialProportion::new(40, 0.3).unwrap();
+        let bin = Binomial::new(40, 0.3).unwrap();
+        let mut rng2 = crate::test::rng(353);
+        for _ in 0..100 {
+            assert_eq!(exact.sample(&mut rng), bin.sample(&mut rng2) as f64 / 40.0);
+        }
+
+        // End points are exact for both methods
+        for &threshold in &[0.0, f64::INFINITY] {
+            let zero = BinomialProportion::with_threshold(1000, 0.0, threshold).unwrap();
+            let one = BinomialProportion::with_threshold(1000, 1.0, threshold).unwrap();
+            assert_eq!(zero.sample(&mut rng), 0.0);
+            assert_eq!(one.sample(&mut rng), 1.0);
+        }
+
+        // Approximation for huge n; mean and standard deviation
+        let (n, p) = (u64::MAX, 0.2);
+        let approx = BinomialProportion::new(n, p).unwrap();
+        let sd = (p * (1.0 - p) / n as f64).sqrt();
+        let mut sum = 0.0;
+        for _ in 0..1000 {
+            let x = approx.sample(&mut rng);
+            assert!((x - p).abs() < 10.0 * sd);
+            sum += x;
+        }
+        assert!((sum / 1000.0 - p).abs() < sd);
+
+        // The approximation preserves the granularity of k / n
+        let coarse = BinomialProportion::with_threshold(10, 0.5, 0.0).unwrap();
+        for _ in 0..100 {
+            let k = coarse.sample(&mut rng) * 10.0;
+            assert_eq!(k, k.round());
+            assert!((0.0..=10.0).contains(&k));
+        }
+    }
+
     #[test]
     fn binomial_distributions_can_be_compared() {
         assert_eq!(Binomial::new(1, 1.0), Binomial::new(1, 1.0));
@@ -0,0 +1,398 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Copulas: joint distributions with dependence between marginals.
+//!
+//! A copula is a joint distribution over `[0, 1]^d` with uniform marginals;
+//! it describes only the dependence between the components. Applying the
+//! inverse distribution function (quantile function) of any marginal
+//! distribution to each component then gives a joint distribution with
+//! those marginals and that dependence structure.
+//!
+//! This module provides:
+//!
+//! - the [`Copula`] trait, and its implementations [`Gaussian`] and
+//!   [`Clayton`], each also sampling `Vec<f64>` of dependent uniforms
+//! - [`Joint`], combining a copula with a [`Quantile`] function per
+//!   component, sampling `Vec<f64>`
+//! - [`Empirical`], approximating the quantile function of any
+//!   `Distribution<f64>` from samples
+//!
+//! # Example
+//!
+//! ```
+//! use rand_distr::copula::{Clayton, Empirical, Joint};
+//! use rand_distr::{Distribution, LogNormal};
+//!
+//! let mut rng = rand::thread_rng();
+//! // Two asset returns which tend to crash together
+//! let returns = LogNormal::new(0.0, 0.2).unwrap();
+//! let marginal = Empirical::new(&returns, &mut rng, 1000);
+//! let joint = Joint::new(Clayton::new(2, 3.0).unwrap(), vec![marginal.clone(), marginal]).unwrap();
+//! let x = joint.sample(&mut rng);
+//! assert_eq!(x.len(), 2);
+//! ```
+//!
+//! [`CorrelatedPairs`](crate::CorrelatedPairs) provides a bivariate Gaussian
+//! copula without allocation.
+
+use alloc::vec::Vec;
+use core::fmt;
+#[allow(unused_imports)]
+use num_traits::Float;
+use rand::Rng;
+
+use crate::utils::normal_cdf;
+use crate::{Distribution, Exp1, Gamma, StandardNormal};
+
+/// Error type returned from the constructors of this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The correlation matrix is not square, not symmetric, or has diagonal
+    /// entries other than 1.
+    InvalidCorrelation,
+    /// The correlation matrix is not positive definite.
+    NotPositiveDefinite,
+    /// The parameter `theta` is not finite and positive.
+    InvalidTheta,
+    /// The number of marginals differs from the dimension of the copula.
+    DimensionMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidCorrelation => "correlation matrix is not a symmetric matrix with unit diagonal",
+            Error::NotPositiveDefinite => "correlation matrix is not positive definite",
+            Error::InvalidTheta => "theta is not finite and positive in Clayton copula",
+            Error::DimensionMismatch => "number of marginals does not match the dimension of the copula",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+/// A copula: a joint distribution over `[0, 1]^d` with uniform marginals.
+pub trait Copula {
+    /// The dimension `d` of the copula.
+    fn dim(&self) -> usize;
+
+    /// Fill `u` with a sample of the copula.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u.len() != self.dim()`.
+    fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, u: &mut [f64]);
+}
+
+/// The Gaussian copula with a given correlation matrix.
+///
+/// Samples are `Φ(z)` componentwise, where `z` is a multivariate normal
+/// vector with zero means and the given correlation matrix, and `Φ` is the
+/// standard normal distribution function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gaussian {
+    dim: usize,
+    // Lower triangular Cholesky factor, row-major
+    chol: Vec<f64>,
+}
+
+impl Gaussian {
+    /// Construct, from the rows of a correlation matrix.
+    ///
+    /// The matrix must be symmetric, positive definite and have unit
+    /// diagonal.
+    pub fn new<M: AsRef<[f64]>>(correlation: &[M]) -> Result<Gaussian, Error> {
+        let dim = correlation.len();
+        let c = |i: usize, j: usize| correlation[i].as_ref()[j];
+        for (i, row) in correlation.iter().enumerate() {
+            if row.as_ref().len() != dim || c(i, i) != 1.0 {
+                return Err(Error::InvalidCorrelation);
+            }
+            for j in 0..i {
+                if c(i, j) != c(j, i) || !(c(i, j).abs() <= 1.0) {
+                    return Err(Error::InvalidCorrelation);
+                }
+            }
+        }
+
+        let mut chol = alloc::vec![0.0; dim * dim];
+        for i in 0..dim {
+            for j in 0..=i {
+                let dot: f64 = (0..j).map(|k| chol[i * dim + k] * chol[j * dim + k]).sum();
+                if i == j {
+                    let d = c(i, i) - dot;
+                    if !(d > 0.0) {
+                        return Err(Error::NotPositiveDefinite);
+                    }
+                    chol[i * dim + i] = d.sqrt();
+                } else {
+                    chol[i * dim + j] = (c(i, j) - dot) / chol[j * dim + j];
+                }
+            }
+        }
+        Ok(Gaussian { dim, chol })
+    }
+}
+
+impl Copula for Gaussian {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, u: &mut [f64]) {
+        assert_eq!(u.len(), self.dim, "length of u must equal the dimension");
+        let z: Vec<f64> = (0..self.dim).map(|_| rng.sample(StandardNormal)).collect();
+        for (i, ui) in u.iter_mut().enumerate() {
+            let row = &self.chol[i * self.dim..i * self.dim + i + 1];
+            *ui = normal_cdf(row.iter().zip(&z).map(|(l, z)| l * z).sum());
+        }
+    }
+}
+
+/// The Clayton copula with parameter `theta > 0`.
+///
+/// This copula has lower tail dependence: components tend to take small
+/// values together, but not large values. Its Kendall rank correlation is
+/// `theta / (theta + 2)`, approaching independence as `theta → 0`.
+///
+/// Samples are generated with the method of Marshall and Olkin, using one
+/// [`Gamma`] variate and `d` [`Exp1`] variates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clayton {
+    dim: usize,
+    theta: f64,
+    frailty: Gamma<f64>,
+}
+
+impl Clayton {
+    /// Construct, with dimension `dim` and parameter `theta`.
+    ///
+    /// Fails unless `theta` is finite and positive.
+    pub fn new(dim: usize, theta: f64) -> Result<Clayton, Error> {
+        if !(theta > 0.0 && theta.is_finite()) {
+            return Err(Error::InvalidTheta);
+        }
+        let frailty = Gamma::new(1.0 / theta, 1.0).map_err(|_| Error::InvalidTheta)?;
+        Ok(Clayton { dim, theta, frailty })
+    }
+
+    /// Returns the parameter `theta`.
+    pub fn theta(&self) -> f64 {
+        self.theta
+    }
+}
+
+impl Copula for Clayton {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, u: &mut [f64]) {
+        assert_eq!(u.len(), self.dim, "length of u must equal the dimension");
+        let v = self.frailty.sample(rng);
+        for ui in u.iter_mut() {
+            let e: f64 = rng.sample(Exp1);
+            *ui = (1.0 + e / v).powf(-1.0 / self.theta);
+        }
+    }
+}
+
+macro_rules! copula_distribution {
+    ($($ty:ty),*) => {$(
+        impl Distribution<Vec<f64>> for $ty {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+                let mut u = alloc::vec![0.0; self.dim()];
+                self.sample_into(rng, &mut u);
+                u
+            }
+        }
+    )*};
+}
+
+copula_distribution!(Gaussian, Clayton);
+
+/// The quantile function (inverse distribution function) of a marginal.
+///
+/// This is implemented for closures `Fn(f64) -> f64` and for [`Empirical`].
+pub trait Quantile {
+    /// Returns the value `x` with `P(X <= x) = p`, for `p` in `[0, 1]`.
+    fn quantile(&self, p: f64) -> f64;
+}
+
+impl<F: Fn(f64) -> f64> Quantile for F {
+    fn quantile(&self, p: f64) -> f64 {
+        self(p)
+    }
+}
+
+/// An empirical approximation of the quantile function of a distribution.
+///
+/// This allows any `Distribution<f64>` to be used as a marginal of a
+/// [`Joint`] distribution. It is constructed from `n` samples of the
+/// distribution; each quantile is one of these samples, so the marginal
+/// is approximated by a discrete distribution over them. Prefer an exact
+/// quantile function where one is known.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Empirical {
+    sorted: Vec<f64>,
+}
+
+impl Empirical {
+    /// Construct from `n` samples of `distr`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub fn new<D, R>(distr: &D, rng: &mut R, n: usize) -> Empirical
+    where
+        D: Distribution<f64> + ?Sized,
+        R: Rng + ?Sized,
+    {
+        assert!(n > 0, "n must be positive");
+        let mut sorted: Vec<f64> = (0..n).map(|_| distr.sample(rng)).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        Empirical { sorted }
+    }
+}
+
+impl Quantile for Empirical {
+    fn quantile(&self, p: f64) -> f64 {
+        let n = self.sorted.len();
+        // `p * n` is non-negative for valid `p`; the cast saturates
+        let i = ((p * n as f64) as usize).min(n - 1);
+        self.sorted[i]
+    }
+}
+
+/// A joint distribution given by a copula and the quantile functions of
+/// its marginals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Joint<C, Q> {
+    copula: C,
+    marginals: Vec<Q>,
+}
+
+impl<C: Copula, Q: Quantile> Joint<C, Q> {
+    /// Construct, from a copula and one marginal per dimension.
+    ///
+    /// Fails if the number of marginals is not `copula.dim()`.
+    pub fn new(copula: C, marginals: Vec<Q>) -> Result<Joint<C, Q>, Error> {
+        if marginals.len() != copula.dim() {
+            return Err(Error::DimensionMismatch);
+        }
+        Ok(Joint { copula, marginals })
+    }
+}
+
+impl<C: Copula, Q: Quantile> Distribution<Vec<f64>> for Joint<C, Q> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let mut x = alloc::vec![0.0; self.marginals.len()];
+        self.copula.sample_into(rng, &mut x);
+        for (xi, q) in x.iter_mut().zip(&self.marginals) {
+            *xi = q.quantile(*xi);
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Exp;
+
+    // Kendall's rank correlation between the first two components
+    fn kendall_tau<D: Distribution<Vec<f64>>>(distr: &D, seed: u64) -> f64 {
+        let mut rng = crate::test::rng(seed);
+        let samples: Vec<Vec<f64>> = (0..400).map(|_| distr.sample(&mut rng)).collect();
+        let mut sum = 0.0;
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[..i] {
+                sum += ((a[0] - b[0]) * (a[1] - b[1])).signum();
+            }
+        }
+        let n = samples.len() as f64;
+        sum / (n * (n - 1.0) / 2.0)
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let copula = Gaussian::new(&[[1.0, 0.5, 0.0], [0.5, 1.0, -0.3], [0.0, -0.3, 1.0]]).unwrap();
+        assert_eq!(copula.dim(), 3);
+        let mut rng = crate::test::rng(251);
+        for _ in 0..100 {
+            let u = copula.sample(&mut rng);
+            assert!(u.iter().all(|u| (0.0..=1.0).contains(u)));
+        }
+        // tau = 2 / pi * asin(0.5) = 1/3
+        let tau = kendall_tau(&copula, 252);
+        assert!((tau - 1.0 / 3.0).abs() < 0.08, "{}", tau);
+
+        let empty: &[[f64; 0]] = &[];
+        assert_eq!(Gaussian::new(empty).unwrap().sample(&mut rng), []);
+        assert_eq!(Gaussian::new(&[[1.0, 0.5]]), Err(Error::InvalidCorrelation));
+        assert_eq!(Gaussian::new(&[[1.0, 0.5], [0.4, 1.0]]), Err(Error::InvalidCorrelation));
+        assert_eq!(Gaussian::new(&[[2.0, 0.5], [0.5, 2.0]]), Err(Error::InvalidCorrelation));
+        assert_eq!(Gaussian::new(&[[1.0, 1.0], [1.0, 1.0]]), Err(Error::NotPositiveDefinite));
+        assert_eq!(
+            Gaussian::new(&[[1.0, 0.9, -0.9], [0.9, 1.0, 0.9], [-0.9, 0.9, 1.0]]),
+            Err(Error::NotPositiveDefinite)
+        );
+    }
+
+    #[test]
+    fn test_clayton() {
+        let copula = Clayton::new(2, 2.0).unwrap();
+        assert_eq!(copula.theta(), 2.0);
+        // tau = theta / (theta + 2) = 1/2
+        let tau = kendall_tau(&copula, 253);
+        assert!((tau - 0.5).abs() < 0.08, "{}", tau);
+
+        assert_eq!(Clayton::new(2, 0.0), Err(Error::InvalidTheta));
+        assert_eq!(Clayton::new(2, -1.0), Err(Error::InvalidTheta));
+        assert_eq!(Clayton::new(2, f64::INFINITY), Err(Error::InvalidTheta));
+    }
+
+    #[test]
+    fn test_joint() {
+        let mut rng = crate::test::rng(254);
+        let exp = Exp::new(2.0).unwrap();
+        let marginals = [Empirical::new(&exp, &mut rng, 1000), Empirical::new(&exp, &mut rng, 1000)];
+        let copula = Gaussian::new(&[[1.0, 0.5], [0.5, 1.0]]).unwrap();
+        assert_eq!(
+            Joint::new(copula.clone(), marginals[..1].to_vec()),
+            Err(Error::DimensionMismatch)
+        );
+        let joint = Joint::new(copula, marginals.to_vec()).unwrap();
+        let mut mean = 0.0;
+        for _ in 0..1000 {
+            let x = joint.sample(&mut rng);
+            assert!(x.iter().all(|&x| x >= 0.0));
+            mean += x[1] / 1000.0;
+        }
+        assert!((mean - 0.5).abs() < 0.05, "{}", mean);
+        // Transforming the marginals preserves rank correlation
+        let tau = kendall_tau(&joint, 252);
+        assert!((tau - 1.0 / 3.0).abs() < 0.08, "{}", tau);
+
+        let joint = Joint::new(Clayton::new(3, 1.0).unwrap(), alloc::vec![|p: f64| 10.0 * p; 3]).unwrap();
+        let x = joint.sample(&mut rng);
+        assert!(x.iter().all(|x| (0.0..=10.0).contains(x)));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(255);
+        let x = Gaussian::new(&[[1.0, 0.5], [0.5, 1.0]]).unwrap().sample(&mut rng);
+        assert_eq!(x, [0.011837316887602808, 0.13061121831775943]);
+        let x = Clayton::new(2, 2.0).unwrap().sample(&mut rng);
+        assert_eq!(x, [0.23980822343363392, 0.6454861596441424]);
+    }
+}
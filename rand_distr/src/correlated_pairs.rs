@@ -0,0 +1,240 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pairs of correlated random variables.
+
+use crate::utils::normal_cdf;
+use crate::{Distribution, StandardNormal};
+use core::fmt;
+use num_traits::Float;
+use rand::Rng;
+
+/// Pairs of standard normal variates with correlation `rho`.
+///
+/// Each sample is a pair `(x, y)` from the bivariate normal distribution
+/// with zero means, unit variances and correlation coefficient `rho`. It is
+/// computed from two independent [`StandardNormal`] variates `z1, z2` as
+/// `x = z1` and `y = rho * z1 + sqrt(1 - rho^2) * z2`.
+///
+/// Other marginal distributions may be used via
+/// [`CorrelatedPairs::with_marginals`], which applies a Gaussian copula.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{CorrelatedPairs, Distribution};
+///
+/// let pairs = CorrelatedPairs::new(0.8).unwrap();
+/// let (x, y): (f64, f64) = pairs.sample(&mut rand::thread_rng());
+/// println!("({}, {}) are correlated standard normal variates", x, y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrelatedPairs<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    rho: F,
+    // sqrt(1 - rho^2)
+    scale: F,
+}
+
+/// Error type returned from `CorrelatedPairs::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The correlation is not in `[-1, 1]` (or is NaN).
+    InvalidCorrelation,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidCorrelation => "correlation is not in [-1, 1] in correlated pairs distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> CorrelatedPairs<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    /// Construct, from the correlation coefficient `rho`.
+    ///
+    /// Fails if `rho` is not in `[-1, 1]`.
+    pub fn new(rho: F) -> Result<CorrelatedPairs<F>, Error> {
+        if !(rho >= -F::one() && rho <= F::one()) {
+            return Err(Error::InvalidCorrelation);
+        }
+        let scale = (F::one() - rho * rho).sqrt();
+        Ok(CorrelatedPairs { rho, scale })
+    }
+
+    /// Returns the correlation coefficient of the distribution.
+    pub fn rho(&self) -> F {
+        self.rho
+    }
+}
+
+impl CorrelatedPairs<f64> {
+    /// Transform the normal pairs to the given marginal distributions.
+    ///
+    /// Each component of a sampled pair is mapped to `[0, 1]` via the
+    /// standard normal distribution function, then to the marginal
+    /// distribution via `inv_cdf_x` or `inv_cdf_y`, which should be the
+    /// inverse distribution function (quantile function) of that marginal.
+    /// Note that the arguments may be exactly `0` or `1`.
+    ///
+    /// This is a Gaussian copula: the rank correlation of the pair is that
+    /// of the normal pair, but in general the (Pearson) correlation of the
+    /// transformed pair is not `rho`. See the [`copula`] module for more
+    /// dimensions and other dependence structures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_distr::{CorrelatedPairs, Distribution};
+    ///
+    /// // Correlated exponentially distributed waiting times
+    /// let exp_inv_cdf = |u: f64| -(1.0 - u).ln();
+    /// let pairs = CorrelatedPairs::new(0.5).unwrap().with_marginals(exp_inv_cdf, exp_inv_cdf);
+    /// let (s, t) = pairs.sample(&mut rand::thread_rng());
+    /// assert!(s >= 0.0 && t >= 0.0);
+    /// ```
+    ///
+    /// [`copula`]: crate::copula
+    pub fn with_marginals<G1, G2, X, Y>(self, inv_cdf_x: G1, inv_cdf_y: G2) -> CorrelatedMarginals<G1, G2>
+    where
+        G1: Fn(f64) -> X,
+        G2: Fn(f64) -> Y,
+    {
+        CorrelatedMarginals {
+            pairs: self,
+            inv_cdf_x,
+            inv_cdf_y,
+        }
+    }
+}
+
+impl<F> Distribution<(F, F)> for CorrelatedPairs<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (F, F) {
+        let z1: F = rng.sample(StandardNormal);
+        let z2: F = rng.sample(StandardNormal);
+        (z1, self.rho * z1 + self.scale * z2)
+    }
+}
+
+/// Pairs of correlated variates with given marginal distributions.
+///
+/// This struct is created by [`CorrelatedPairs::with_marginals`].
+#[derive(Clone, Copy)]
+pub struct CorrelatedMarginals<G1, G2> {
+    pairs: CorrelatedPairs<f64>,
+    inv_cdf_x: G1,
+    inv_cdf_y: G2,
+}
+
+impl<G1, G2> fmt::Debug for CorrelatedMarginals<G1, G2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CorrelatedMarginals {{ pairs: {:?}, .. }}", self.pairs)
+    }
+}
+
+impl<G1, G2, X, Y> Distribution<(X, Y)> for CorrelatedMarginals<G1, G2>
+where
+    G1: Fn(f64) -> X,
+    G2: Fn(f64) -> Y,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (X, Y) {
+        let (x, y) = self.pairs.sample(rng);
+        ((self.inv_cdf_x)(normal_cdf(x)), (self.inv_cdf_y)(normal_cdf(y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn correlation<I: Iterator<Item = (f64, f64)>>(pairs: I) -> f64 {
+        let (mut n, mut sx, mut sy, mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        for (x, y) in pairs {
+            n += 1.0;
+            sx += x;
+            sy += y;
+            sxy += x * y;
+            sxx += x * x;
+            syy += y * y;
+        }
+        (n * sxy - sx * sy) / ((n * sxx - sx * sx) * (n * syy - sy * sy)).sqrt()
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert_eq!(normal_cdf(0.0), 0.5);
+        assert_almost_eq!(normal_cdf(1.96), 0.9750021048517795, 1e-14);
+        assert_almost_eq!(normal_cdf(-1.0), 0.15865525393145707, 1e-14);
+        assert_eq!(normal_cdf(f64::INFINITY), 1.0);
+        assert_eq!(normal_cdf(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_correlated_pairs() {
+        let mut rng = crate::test::rng(250);
+        for &rho in &[-0.9, -0.3, 0.0, 0.5, 0.99] {
+            let distr = CorrelatedPairs::new(rho).unwrap();
+            assert_eq!(distr.rho(), rho);
+            let r = correlation(distr.sample_iter(&mut rng).take(10_000));
+            assert!((r - rho).abs() < 0.03, "rho = {}, r = {}", rho, r);
+        }
+
+        let (x, y): (f64, f64) = CorrelatedPairs::new(1.0).unwrap().sample(&mut rng);
+        assert_eq!(x, y);
+        let (x, y): (f32, f32) = CorrelatedPairs::new(-1.0).unwrap().sample(&mut rng);
+        assert_eq!(x, -y);
+    }
+
+    #[test]
+    fn test_invalid_correlation() {
+        assert_eq!(CorrelatedPairs::new(1.01).unwrap_err(), Error::InvalidCorrelation);
+        assert!(CorrelatedPairs::new(-2.0f32).is_err());
+        assert!(CorrelatedPairs::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_marginals() {
+        let mut rng = crate::test::rng(251);
+        let distr = CorrelatedPairs::new(0.7)
+            .unwrap()
+            .with_marginals(|u| u, |u| u < 0.5);
+        let mut agree = 0;
+        for _ in 0..1000 {
+            let (u, b) = distr.sample(&mut rng);
+            assert!((0.0..=1.0).contains(&u));
+            agree += ((u < 0.5) == b) as usize;
+        }
+        // The signs agree with probability 1/2 + asin(0.7) / pi, about 0.75
+        assert!((700..800).contains(&agree), "{}", agree);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(252);
+        let distr = CorrelatedPairs::new(0.5).unwrap();
+        let pair: (f64, f64) = distr.sample(&mut rng);
+        assert_eq!(pair, (-0.7682948332886052, -0.9245214115516185));
+    }
+}
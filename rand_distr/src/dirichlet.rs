@@ -22,6 +22,9 @@ use serde_with::serde_as;
 /// probability distributions parameterized by a vector alpha of positive reals.
 /// It is a multivariate generalization of the beta distribution.
 ///
+/// The `Gamma` sampler of each dimension is set up once, when the
+/// distribution is constructed.
+///
 /// # Example
 ///
 /// ```
@@ -45,6 +48,9 @@ where
     /// Concentration parameters (alpha)
     #[cfg_attr(feature = "serde_with", serde_as(as = "[_; N]"))]
     alpha: [F; N],
+    /// A `Gamma(alpha_i, 1)` sampler per dimension, set up once in `new`
+    #[cfg_attr(feature = "serde_with", serde_as(as = "[_; N]"))]
+    gamma: [Gamma<F>; N],
 }
 
 /// Error type returned from `Dirchlet::new`.
@@ -89,13 +95,16 @@ where
         if N < 2 {
             return Err(Error::AlphaTooShort);
         }
-        for &ai in alpha.iter() {
+        let mut gamma = [Gamma::new(F::one(), F::one()).unwrap(); N];
+        for (g, &ai) in gamma.iter_mut().zip(alpha.iter()) {
             if !(ai > F::zero()) {
                 return Err(Error::AlphaTooSmall);
             }
+            // Cannot fail since shape is positive and scale is one
+            *g = Gamma::new(ai, F::one()).unwrap();
         }
 
-        Ok(Dirichlet { alpha })
+        Ok(Dirichlet { alpha, gamma })
     }
 }
 
@@ -110,8 +119,7 @@ where
         let mut samples = [F::zero(); N];
         let mut sum = F::zero();
 
-        for (s, &a) in samples.iter_mut().zip(self.alpha.iter()) {
-            let g = Gamma::new(a, F::one()).unwrap();
+        for (s, g) in samples.iter_mut().zip(self.gamma.iter()) {
             *s = g.sample(rng);
             sum =  sum + (*s);
         }
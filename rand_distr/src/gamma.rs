@@ -40,6 +40,12 @@ use serde::{Serialize, Deserialize};
 /// == 1`, and using the boosting technique described in that paper for
 /// `shape < 1`.
 ///
+/// All set-up work which depends on the parameters is done once, in
+/// [`Gamma::new`]; sampling only reads this state. Hence a single `Gamma`
+/// (which is `Copy`, `Send` and `Sync`) may be constructed once and shared
+/// by reference between threads, rather than being rebuilt per sample. The
+/// same holds for the other distributions in this module.
+///
 /// # Example
 ///
 /// ```
@@ -739,6 +745,17 @@ where
 mod test {
     use super::*;
 
+    #[test]
+    fn test_shareable() {
+        // Set-up happens in `new`; the distributions may be freely shared
+        fn check<T: Copy + Send + Sync>(_: T) {}
+        check(Gamma::new(2.0, 1.0).unwrap());
+        check(ChiSquared::new(2.0).unwrap());
+        check(FisherF::new(2.0, 3.0).unwrap());
+        check(StudentT::new(2.0).unwrap());
+        check(Beta::new(2.0, 3.0).unwrap());
+    }
+
     #[test]
     fn test_chi_squared_one() {
         let chi = ChiSquared::new(1.0).unwrap();
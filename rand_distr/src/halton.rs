@@ -0,0 +1,333 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Halton quasi-random sequence.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use rand::Rng;
+
+/// The Halton low-discrepancy sequence in `D` dimensions.
+///
+/// Point `i` of the sequence is formed by taking the
+/// [radical inverse](https://en.wikipedia.org/wiki/Van_der_Corput_sequence)
+/// of `i` in a different base for each dimension. Points cover the unit
+/// hypercube `[0, 1)^D` much more evenly than independent uniform samples,
+/// which makes the sequence useful for quasi-Monte Carlo integration and for
+/// generating sample patterns (e.g. pixel or lens positions in graphics).
+///
+/// By default dimension `k` uses the `k`-th prime as its base. The sequence
+/// is cheap to compute but its quality degrades as the bases grow, so it is
+/// best suited to low dimensions (roughly `D <= 10`). Random digit scrambling
+/// (see [`Halton::new_scrambled`]) breaks up the correlation between
+/// dimensions with larger bases and allows randomised QMC error estimates.
+///
+/// Note that this is a deterministic sequence, not a random distribution:
+/// `Halton` implements [`Iterator`] rather than [`Distribution`]. The
+/// sequence starts at index 0, which is the origin for all variants.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::Halton;
+///
+/// let points: Vec<[f64; 2]> = Halton::<2>::new().unwrap().skip(1).take(3).collect();
+/// assert_eq!(points, [[0.5, 1.0 / 3.0], [0.25, 2.0 / 3.0], [0.75, 1.0 / 9.0]]);
+/// ```
+///
+/// [`Distribution`]: crate::Distribution
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Halton<const D: usize> {
+    /// Base of each dimension
+    bases: [u32; D],
+    /// Digit permutation `d -> d * m mod b` of each dimension; 1 if unscrambled
+    multipliers: [u32; D],
+    /// Index of the next point yielded by the iterator
+    index: u64,
+    /// Set once the index has overflowed
+    exhausted: bool,
+}
+
+/// Error type returned from `Halton` constructors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `D == 0`.
+    ZeroDimension,
+    /// A base is less than 2.
+    BaseTooSmall,
+    /// Two bases share a common factor.
+    BasesNotCoprime,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::ZeroDimension => "dimension is zero in Halton sequence",
+            Error::BaseTooSmall => "base is less than 2 in Halton sequence",
+            Error::BasesNotCoprime => "bases are not pairwise coprime in Halton sequence",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<const D: usize> Halton<D> {
+    /// Construct the standard Halton sequence, using the first `D` primes as
+    /// bases.
+    pub fn new() -> Result<Halton<D>, Error> {
+        if D == 0 {
+            return Err(Error::ZeroDimension);
+        }
+        let mut bases = [2; D];
+        for k in 1..D {
+            let mut candidate = bases[k - 1] + 1;
+            while !is_prime(candidate) {
+                candidate += 1;
+            }
+            bases[k] = candidate;
+        }
+        Ok(Halton {
+            bases,
+            multipliers: [1; D],
+            index: 0,
+            exhausted: false,
+        })
+    }
+
+    /// Construct a Halton sequence with the given per-dimension bases.
+    ///
+    /// Bases must be at least 2 and pairwise coprime (usually they are
+    /// distinct primes), otherwise the dimensions are correlated.
+    pub fn with_bases(bases: [u32; D]) -> Result<Halton<D>, Error> {
+        if D == 0 {
+            return Err(Error::ZeroDimension);
+        }
+        for (k, &b) in bases.iter().enumerate() {
+            if b < 2 {
+                return Err(Error::BaseTooSmall);
+            }
+            if bases[..k].iter().any(|&a| gcd(a, b) != 1) {
+                return Err(Error::BasesNotCoprime);
+            }
+        }
+        Ok(Halton {
+            bases,
+            multipliers: [1; D],
+            index: 0,
+            exhausted: false,
+        })
+    }
+
+    /// Construct a randomly scrambled Halton sequence with the first `D`
+    /// primes as bases.
+    ///
+    /// Each dimension applies a random permutation to the digits of the
+    /// radical inverse, of the form `d -> d * m mod b` with `m` drawn
+    /// uniformly from `1..b`. The permutation fixes 0, so every point is
+    /// still computed exactly from a finite number of digits.
+    pub fn new_scrambled<R: Rng + ?Sized>(rng: &mut R) -> Result<Halton<D>, Error> {
+        Ok(Halton::new()?.scrambled(rng))
+    }
+
+    /// Apply random digit scrambling to this sequence.
+    ///
+    /// See [`Halton::new_scrambled`]. Any previous scrambling is replaced.
+    pub fn scrambled<R: Rng + ?Sized>(mut self, rng: &mut R) -> Halton<D> {
+        for (m, &b) in self.multipliers.iter_mut().zip(self.bases.iter()) {
+            // Since b is prime every multiplier is a valid permutation. For
+            // other bases we only accept multipliers coprime with b.
+            *m = loop {
+                let m = rng.gen_range(1..b);
+                if gcd(m, b) == 1 {
+                    break m;
+                }
+            };
+        }
+        self
+    }
+
+    /// The base used for each dimension.
+    pub fn bases(&self) -> [u32; D] {
+        self.bases
+    }
+
+    /// The index of the next point yielded by the iterator.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Set the index of the next point yielded by the iterator.
+    pub fn set_index(&mut self, index: u64) {
+        self.index = index;
+        self.exhausted = false;
+    }
+
+    /// Compute point `index` of the sequence directly.
+    pub fn point(&self, index: u64) -> [f64; D] {
+        let mut p = [0.0; D];
+        for (k, x) in p.iter_mut().enumerate() {
+            *x = radical_inverse(index, self.bases[k], self.multipliers[k]);
+        }
+        p
+    }
+}
+
+impl<const D: usize> Iterator for Halton<D> {
+    type Item = [f64; D];
+
+    fn next(&mut self) -> Option<[f64; D]> {
+        if self.exhausted {
+            return None;
+        }
+        let p = self.point(self.index);
+        match self.index.checked_add(1) {
+            Some(i) => self.index = i,
+            None => self.exhausted = true,
+        }
+        Some(p)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<[f64; D]> {
+        match self.index.checked_add(n as u64) {
+            Some(i) if !self.exhausted => {
+                self.index = i;
+                self.next()
+            }
+            _ => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+}
+
+impl<const D: usize> FusedIterator for Halton<D> {}
+
+/// Radical inverse of `i` in base `b`, with digits permuted by `d -> d * m mod b`
+fn radical_inverse(mut i: u64, b: u32, m: u32) -> f64 {
+    let base = u64::from(b);
+    let inv_base = 1.0 / f64::from(b);
+    let mut scale = inv_base;
+    let mut result = 0.0;
+    while i > 0 {
+        let digit = (i % base) * u64::from(m) % base;
+        result += digit as f64 * scale;
+        scale *= inv_base;
+        i /= base;
+    }
+    // Rounding may give 1.0 for large i; return the largest value below 1
+    result.min(1.0 - f64::EPSILON / 2.0)
+}
+
+fn is_prime(n: u32) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bases() {
+        assert_eq!(Halton::<6>::new().unwrap().bases(), [2, 3, 5, 7, 11, 13]);
+        assert_eq!(Halton::<0>::new(), Err(Error::ZeroDimension));
+        assert!(Halton::with_bases([2, 9]).is_ok());
+        assert_eq!(Halton::with_bases([2, 1]), Err(Error::BaseTooSmall));
+        assert_eq!(Halton::with_bases([3, 6]), Err(Error::BasesNotCoprime));
+    }
+
+    #[test]
+    fn test_sequence() {
+        let mut h = Halton::<2>::new().unwrap();
+        let expected = [
+            [0.0, 0.0],
+            [0.5, 1.0 / 3.0],
+            [0.25, 2.0 / 3.0],
+            [0.75, 1.0 / 9.0],
+            [0.125, 4.0 / 9.0],
+            [0.625, 7.0 / 9.0],
+        ];
+        for (i, e) in expected.iter().enumerate() {
+            let (p, q) = (h.point(i as u64), h.next().unwrap());
+            assert_eq!(p, q);
+            assert_almost_eq!(p[0], e[0], 1e-15);
+            assert_almost_eq!(p[1], e[1], 1e-15);
+        }
+        assert_eq!(h.index(), 6);
+
+        h.set_index(1);
+        assert_eq!(h.nth(2), Some(h.point(3)));
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let mut h = Halton::<1>::new().unwrap();
+        h.set_index(u64::MAX);
+        assert!(h.next().is_some());
+        assert!(h.next().is_none());
+        h.set_index(u64::MAX - 1);
+        assert!(h.nth(5).is_none());
+
+        // Large indices round to just below 1 in base 2
+        let h = Halton::<3>::new().unwrap();
+        for &i in &[(1 << 54) - 1, u64::MAX] {
+            let p = h.point(i);
+            assert!(p.iter().all(|&x| (0.0..1.0).contains(&x)), "{:?}", p);
+            assert_eq!(p[0], 1.0 - f64::EPSILON / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_scrambled() {
+        let mut rng = crate::test::rng(204);
+        let h = Halton::<4>::new_scrambled(&mut rng).unwrap();
+        assert_eq!(h.point(0), [0.0; 4]);
+
+        // Scrambling permutes digits, so the first b - 1 points of each
+        // dimension are still exactly the multiples of 1/b.
+        for (k, &b) in h.bases().iter().enumerate() {
+            let mut seen = [false; 7];
+            for i in 1..u64::from(b) {
+                let x = h.point(i)[k] * f64::from(b);
+                assert_almost_eq!(x, x.round(), 1e-12);
+                let digit = x.round() as usize;
+                assert!(digit >= 1 && !seen[digit - 1]);
+                seen[digit - 1] = true;
+            }
+        }
+
+        for p in h.take(1000) {
+            assert!(p.iter().all(|&x| (0.0..1.0).contains(&x)));
+        }
+    }
+
+    #[test]
+    fn test_uniformity() {
+        // Each of the first 8 * 27 points lands in its own cell of an 8x27 grid
+        let h = Halton::<2>::new().unwrap();
+        let mut seen = [[false; 27]; 8];
+        for p in h.take(8 * 27) {
+            // Points may sit on a cell boundary, up to rounding error
+            let (x, y) = (p[0] * 8.0 + 1e-9, p[1] * 27.0 + 1e-9);
+            let cell = &mut seen[x as usize][y as usize];
+            assert!(!*cell);
+            *cell = true;
+        }
+    }
+}
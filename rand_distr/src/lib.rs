@@ -47,9 +47,12 @@
 //!   - [`SkewNormal`] distribution
 //!   - [`Cauchy`] distribution
 //! - Related to Bernoulli trials (yes/no events, with a given probability):
-//!   - [`Binomial`] distribution
-//!   - [`Geometric`] distribution
+//!   - [`Binomial`] distribution, and [`BinomialProportion`] for `k / n`
+//!   - [`Geometric`] distribution, and [`BernoulliSkip`] to iterate over the
+//!     successes of many trials
 //!   - [`Hypergeometric`] distribution
+//!   - [`SparseSign`] distribution of sparse random projections, and
+//!     [`Sparse`] vectors and matrices with random non-zero entries
 //! - Related to positive real-valued quantities that grow exponentially
 //!   (e.g. prices, incomes, populations):
 //!   - [`LogNormal`] distribution
@@ -76,8 +79,31 @@
 //!   - [`UnitBall`] distribution
 //!   - [`UnitCircle`] distribution
 //!   - [`UnitDisc`] distribution
+//!   - [`UnitSimplex`] distribution
+//!   - [`Triangle`] and [`ConvexPolygon`] distributions of points in a
+//!     region, and [`Polyline`] of points on a path (the latter two
+//!     require the `alloc` feature)
+//!   - [`Permutation`] matrices and [`DoublyStochastic`] matrices (requires
+//!     the `alloc` feature)
+//!   - [`CorrelatedPairs`] of normal variates, or of other marginals via a
+//!     Gaussian copula
+//!   - The [`copula`] module, joining marginal distributions with a given
+//!     dependence structure (requires the `alloc` feature)
+//!   - [`PoissonDisk`] point sets
+//!   - [`UnitRotation`] distribution (requires the `nalgebra` feature)
+//! - Quasi-random sequences
+//!   - [`Halton`] sequence
+//!   - [`Stratified`] samples of the unit interval (requires the `alloc`
+//!     feature)
 //! - Alternative implementation for weighted index sampling
 //!   - [`WeightedAliasIndex`] distribution
+//!   - [`SampleCounts`] to count the outcomes of many draws from a
+//!     [`WeightedIndex`]
+//!   - [`MarkovChain`] to walk a Markov chain with given transition
+//!     probabilities
+//! - Markov chain Monte Carlo
+//!   - The [`mcmc`] module, sampling from a distribution given by its
+//!     (unnormalized) log-density
 //! - Misc. distributions
 //!   - [`InverseGaussian`] distribution
 //!   - [`NormalInverseGaussian`] distribution
@@ -97,8 +123,12 @@ pub use rand::distributions::{
     Standard, Uniform,
 };
 
-pub use self::binomial::{Binomial, Error as BinomialError};
+pub use self::bernoulli_skip::BernoulliSkip;
+pub use self::binomial::{
+    Binomial, BinomialProportion, BinomialProportionError, Error as BinomialError,
+};
 pub use self::cauchy::{Cauchy, Error as CauchyError};
+pub use self::correlated_pairs::{CorrelatedMarginals, CorrelatedPairs, Error as CorrelatedPairsError};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub use self::dirichlet::{Dirichlet, Error as DirichletError};
@@ -110,20 +140,43 @@ pub use self::gamma::{
 };
 pub use self::geometric::{Error as GeoError, Geometric, StandardGeometric};
 pub use self::gumbel::{Error as GumbelError, Gumbel};
+pub use self::halton::{Error as HaltonError, Halton};
 pub use self::hypergeometric::{Error as HyperGeoError, Hypergeometric};
 pub use self::inverse_gaussian::{Error as InverseGaussianError, InverseGaussian};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::markov_chain::{Error as MarkovChainError, MarkovChain, Walk as MarkovWalk};
 pub use self::normal::{Error as NormalError, LogNormal, Normal, StandardNormal};
 pub use self::normal_inverse_gaussian::{
     Error as NormalInverseGaussianError, NormalInverseGaussian,
 };
 pub use self::pareto::{Error as ParetoError, Pareto};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::permutation::{DoublyStochastic, Error as DoublyStochasticError, Permutation};
 pub use self::pert::{Pert, PertError};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::polygon::{ConvexPolygon, Polyline};
+pub use self::polygon::{Error as PolygonError, Triangle};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::poisson_disk::{Error as PoissonDiskError, PoissonDisk};
 pub use self::poisson::{Error as PoissonError, Poisson};
 pub use self::skew_normal::{Error as SkewNormalError, SkewNormal};
+pub use self::sparse::{Sparse, SparseIter};
+pub use self::sparse_sign::{Error as SparseSignError, SparseSign};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::stratified::Stratified;
 pub use self::triangular::{Triangular, TriangularError};
 pub use self::unit_ball::UnitBall;
 pub use self::unit_circle::UnitCircle;
 pub use self::unit_disc::UnitDisc;
+pub use self::unit_simplex::UnitSimplex;
+#[cfg(feature = "nalgebra")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "nalgebra")))]
+pub use self::unit_rotation::UnitRotation;
 pub use self::unit_sphere::UnitSphere;
 pub use self::weibull::{Error as WeibullError, Weibull};
 pub use self::zipf::{Zeta, ZetaError, Zipf, ZipfError};
@@ -133,6 +186,8 @@ pub use rand::distributions::{WeightedError, WeightedIndex};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub use weighted_alias::WeightedAliasIndex;
+#[cfg(feature = "alloc")]
+pub use sample_counts::SampleCounts;
 
 pub use num_traits;
 
@@ -186,27 +241,46 @@ mod test {
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod weighted_alias;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod copula;
+pub mod mcmc;
 
+mod bernoulli_skip;
 mod binomial;
 mod cauchy;
+mod correlated_pairs;
 mod dirichlet;
 mod exponential;
 mod frechet;
 mod gamma;
 mod geometric;
 mod gumbel;
+mod halton;
 mod hypergeometric;
 mod inverse_gaussian;
+mod markov_chain;
 mod normal;
 mod normal_inverse_gaussian;
 mod pareto;
+mod permutation;
 mod pert;
+mod polygon;
 mod poisson;
+mod poisson_disk;
+#[cfg(feature = "alloc")]
+mod sample_counts;
 mod skew_normal;
+mod sparse;
+mod sparse_sign;
+#[cfg(feature = "alloc")]
+mod stratified;
 mod triangular;
 mod unit_ball;
 mod unit_circle;
 mod unit_disc;
+mod unit_rotation;
+mod unit_simplex;
 mod unit_sphere;
 mod utils;
 mod weibull;
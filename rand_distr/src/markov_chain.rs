@@ -0,0 +1,236 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Markov chains over a finite set of states.
+#![cfg(feature = "alloc")]
+use crate::{Distribution, WeightedAliasIndex, WeightedError};
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use rand::Rng;
+
+// Tolerance of the sum of each row of the transition matrix
+const SUM_TOLERANCE: f64 = 1e-9;
+
+/// A discrete-time Markov chain over the states `0..n`.
+///
+/// The chain is defined by its `n × n` transition matrix, where entry
+/// `matrix[i][j]` is the probability of a step from state `i` to state `j`.
+/// Each row is compiled to a [`WeightedAliasIndex`], thus each step takes
+/// `O(1)` time regardless of the number of states.
+///
+/// Use [`MarkovChain::step`] for single steps, or [`MarkovChain::walk`] to
+/// iterate over the states visited from a given start state.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::MarkovChain;
+///
+/// // Weather: 0 = sunny, 1 = rainy
+/// let weather = MarkovChain::new(vec![
+///     vec![0.9, 0.1],
+///     vec![0.5, 0.5],
+/// ]).unwrap();
+///
+/// let forecast: Vec<usize> = weather.walk(rand::thread_rng(), 0).take(7).collect();
+/// assert!(forecast.iter().all(|&s| s < 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarkovChain {
+    rows: Vec<WeightedAliasIndex<f64>>,
+}
+
+/// Error type returned from [`MarkovChain::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The matrix has no rows.
+    Empty,
+    /// The matrix is not square.
+    NotSquare,
+    /// An entry is negative or NaN.
+    InvalidProbability,
+    /// The entries of a row do not sum to 1.
+    RowSumNotOne,
+    /// The number of states exceeds `u32::MAX`.
+    TooManyStates,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::Empty => "transition matrix is empty in Markov chain",
+            Error::NotSquare => "transition matrix is not square in Markov chain",
+            Error::InvalidProbability => "transition probability is negative or NaN in Markov chain",
+            Error::RowSumNotOne => "transition probabilities do not sum to 1 in Markov chain",
+            Error::TooManyStates => "more than u32::MAX states in Markov chain",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl MarkovChain {
+    /// Construct from the rows of a transition matrix.
+    ///
+    /// The matrix must be square and non-empty, with non-negative entries
+    /// each of whose rows sums to 1 (with a tolerance of `1e-9` for
+    /// rounding errors).
+    pub fn new(matrix: Vec<Vec<f64>>) -> Result<MarkovChain, Error> {
+        let n = matrix.len();
+        if n == 0 {
+            return Err(Error::Empty);
+        }
+        if n > u32::MAX as usize {
+            return Err(Error::TooManyStates);
+        }
+        let mut rows = Vec::with_capacity(n);
+        for row in matrix {
+            if row.len() != n {
+                return Err(Error::NotSquare);
+            }
+            if !row.iter().all(|&p| p >= 0.0) {
+                return Err(Error::InvalidProbability);
+            }
+            let sum: f64 = row.iter().sum();
+            if !((sum - 1.0).abs() <= SUM_TOLERANCE) {
+                return Err(Error::RowSumNotOne);
+            }
+            rows.push(WeightedAliasIndex::new(row).map_err(|e| match e {
+                WeightedError::TooMany => Error::TooManyStates,
+                _ => Error::InvalidProbability,
+            })?);
+        }
+        Ok(MarkovChain { rows })
+    }
+
+    /// Returns the number of states `n`.
+    pub fn n_states(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Take one step from `state`, returning the next state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `state >= n`.
+    pub fn step<R: Rng + ?Sized>(&self, state: usize, rng: &mut R) -> usize {
+        self.rows[state].sample(rng)
+    }
+
+    /// Iterate over the states visited by a walk from `start`.
+    ///
+    /// The iterator yields the state after each step (excluding `start`
+    /// itself) and never ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start >= n`.
+    pub fn walk<R: Rng>(&self, rng: R, start: usize) -> Walk<'_, R> {
+        assert!(start < self.n_states(), "start state out of range");
+        Walk {
+            chain: self,
+            rng,
+            state: start,
+        }
+    }
+}
+
+/// An iterator over the states of a walk on a [`MarkovChain`].
+///
+/// This struct is created by [`MarkovChain::walk`].
+#[derive(Debug)]
+pub struct Walk<'a, R> {
+    chain: &'a MarkovChain,
+    rng: R,
+    state: usize,
+}
+
+impl<'a, R> Walk<'a, R> {
+    /// Returns the current state, i.e. the state last yielded (or the start
+    /// state).
+    pub fn state(&self) -> usize {
+        self.state
+    }
+}
+
+impl<'a, R: Rng> Iterator for Walk<'a, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.state = self.chain.step(self.state, &mut self.rng);
+        Some(self.state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<'a, R: Rng> FusedIterator for Walk<'a, R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_markov_chain_invalid() {
+        assert_eq!(MarkovChain::new(Vec::new()).unwrap_err(), Error::Empty);
+        assert_eq!(
+            MarkovChain::new(vec![vec![0.5, 0.5], vec![1.0]]).unwrap_err(),
+            Error::NotSquare
+        );
+        assert_eq!(
+            MarkovChain::new(vec![vec![1.5, -0.5], vec![0.5, 0.5]]).unwrap_err(),
+            Error::InvalidProbability
+        );
+        assert_eq!(
+            MarkovChain::new(vec![vec![f64::NAN]]).unwrap_err(),
+            Error::InvalidProbability
+        );
+        assert_eq!(
+            MarkovChain::new(vec![vec![0.5, 0.4], vec![0.5, 0.5]]).unwrap_err(),
+            Error::RowSumNotOne
+        );
+        assert!(MarkovChain::new(vec![vec![0.1, 0.2, 0.7]; 3]).is_ok());
+    }
+
+    #[test]
+    fn test_markov_chain() {
+        // A deterministic cycle
+        let cycle = MarkovChain::new(vec![
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![1.0, 0.0, 0.0],
+        ])
+        .unwrap();
+        assert_eq!(cycle.n_states(), 3);
+        let mut rng = crate::test::rng(267);
+        assert_eq!(cycle.step(2, &mut rng), 0);
+        let mut walk = cycle.walk(&mut rng, 1);
+        assert_eq!(walk.state(), 1);
+        assert_eq!(walk.by_ref().take(4).collect::<Vec<_>>(), [2, 0, 1, 2]);
+        assert_eq!(walk.state(), 2);
+
+        // The stationary distribution of this chain is (5/6, 1/6)
+        let weather = MarkovChain::new(vec![vec![0.9, 0.1], vec![0.5, 0.5]]).unwrap();
+        let n = 10000;
+        let rainy = weather.walk(&mut rng, 0).take(n).filter(|&s| s == 1).count();
+        assert!((rainy as f64 / n as f64 - 1.0 / 6.0).abs() < 0.02, "{}", rainy);
+    }
+
+    #[test]
+    fn value_stability() {
+        let chain = MarkovChain::new(vec![vec![0.5, 0.25, 0.25]; 3]).unwrap();
+        let walk: Vec<usize> = chain.walk(crate::test::rng(268), 0).take(8).collect();
+        assert_eq!(walk, [0, 0, 1, 1, 0, 1, 1, 0]);
+    }
+}
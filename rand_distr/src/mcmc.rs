@@ -0,0 +1,716 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Markov chain Monte Carlo (MCMC) samplers.
+//!
+//! MCMC methods sample from a distribution known only up to a normalizing
+//! constant, e.g. the posterior distribution of Bayesian inference, by
+//! simulating a Markov chain whose stationary distribution is the target.
+//! Successive states are correlated, and the first states depend on the
+//! initial state; it is common to discard an initial "burn-in" period (via
+//! [`Iterator::skip`]) and to keep only every `k`-th state (via
+//! [`Iterator::step_by`]).
+//!
+//! This module provides:
+//!
+//! - [`MetropolisHastings`], a random-walk Metropolis sampler over a
+//!   log-density function and a symmetric proposal distribution
+//! - [`Gibbs`], sampling each coordinate of a parameter vector in turn from
+//!   its conditional distribution given the others (requires the `alloc`
+//!   feature)
+//! - [`Acceptance`], the Metropolis acceptance rule with a [`Cooling`]
+//!   schedule, for simulated annealing
+//!
+//! # Example
+//!
+//! ```
+//! use rand_distr::mcmc::MetropolisHastings;
+//! use rand_distr::Normal;
+//!
+//! // Sample from the standard normal distribution, up to a constant
+//! let log_density = |x: &f64| -x * x / 2.0;
+//! let proposal = Normal::new(0.0, 1.0).unwrap();
+//! let mut sampler = MetropolisHastings::new(log_density, proposal, 0.0).unwrap();
+//!
+//! let n = 10000;
+//! let mean = sampler.iter(rand::thread_rng()).skip(1000).take(n).sum::<f64>() / n as f64;
+//! assert!(mean.abs() < 0.2);
+//! ```
+
+use crate::Distribution;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+use core::ops::Add;
+#[allow(unused_imports)]
+use num_traits::Float;
+use rand::Rng;
+#[cfg(feature = "alloc")]
+use rand::RngCore;
+
+/// Error type returned from [`MetropolisHastings::new`],
+/// [`GibbsBuilder::build`] and [`Acceptance::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The log-density of the initial state is NaN or `-inf`, i.e. the
+    /// initial state is impossible.
+    InitialStateImpossible,
+    /// The number of conditional distributions differs from the number of
+    /// coordinates of the initial state, or is zero.
+    DimensionMismatch,
+    /// The initial temperature is not finite and positive.
+    InvalidTemperature,
+    /// A parameter of the cooling schedule is out of range.
+    InvalidCooling,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InitialStateImpossible => "log-density of initial state is NaN or -inf in MCMC sampler",
+            Error::DimensionMismatch => "number of conditionals does not match dimension in Gibbs sampler",
+            Error::InvalidTemperature => "initial temperature is not finite and positive in annealing",
+            Error::InvalidCooling => "cooling schedule parameter is out of range in annealing",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+/// A random-walk Metropolis–Hastings sampler.
+///
+/// The target distribution is given by a function `log_density` returning
+/// the natural logarithm of its density (or probability mass) at a state,
+/// up to an additive constant; states outside its support should map to
+/// `-inf`. Each step proposes the state `x + delta` where `x` is the
+/// current state and `delta` is sampled from `proposal`. The proposed state
+/// `y` is accepted with probability `min(1, p(y) / p(x))`; otherwise the
+/// chain stays at `x`.
+///
+/// This is correct only for *symmetric* proposals, where `delta` and
+/// `-delta` are equally likely, e.g. [`Normal`] with mean zero or a
+/// [`Uniform`] over a symmetric interval. The scale of the proposal
+/// controls the trade-off between the acceptance rate and the distance
+/// moved per step; a rate of roughly 0.2 to 0.5 is usually efficient.
+///
+/// States may be of any type `S: Clone + Add<Output = S>`, e.g. `f64` or
+/// the vector types of `nalgebra`.
+///
+/// [`Normal`]: crate::Normal
+/// [`Uniform`]: crate::Uniform
+#[derive(Clone)]
+pub struct MetropolisHastings<S, F, D> {
+    log_density: F,
+    proposal: D,
+    state: S,
+    log_p: f64,
+    steps: u64,
+    accepted: u64,
+}
+
+impl<S, F, D> MetropolisHastings<S, F, D>
+where
+    S: Clone + Add<Output = S>,
+    F: FnMut(&S) -> f64,
+    D: Distribution<S>,
+{
+    /// Construct, starting at state `initial`.
+    ///
+    /// Fails if the log-density of `initial` is NaN or `-inf`.
+    pub fn new(mut log_density: F, proposal: D, initial: S) -> Result<Self, Error> {
+        let log_p = log_density(&initial);
+        if log_p.is_nan() || log_p == f64::NEG_INFINITY {
+            return Err(Error::InitialStateImpossible);
+        }
+        Ok(MetropolisHastings {
+            log_density,
+            proposal,
+            state: initial,
+            log_p,
+            steps: 0,
+            accepted: 0,
+        })
+    }
+
+    /// Take one step, returning the new state.
+    pub fn step<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &S {
+        let candidate = self.state.clone() + self.proposal.sample(rng);
+        let log_p = (self.log_density)(&candidate);
+        let log_ratio = log_p - self.log_p;
+        // NaN compares false and is rejected
+        let accept = log_ratio >= 0.0 || rng.gen::<f64>().ln() < log_ratio;
+        self.steps += 1;
+        if accept {
+            self.accepted += 1;
+            self.state = candidate;
+            self.log_p = log_p;
+        }
+        &self.state
+    }
+
+    /// Iterate over the states after each step.
+    ///
+    /// The iterator never ends; the sampler may be used again after the
+    /// iterator is dropped.
+    pub fn iter<R: Rng>(&mut self, rng: R) -> MetropolisHastingsIter<'_, S, F, D, R> {
+        MetropolisHastingsIter { sampler: self, rng }
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the log-density of the current state.
+    pub fn log_density(&self) -> f64 {
+        self.log_p
+    }
+
+    /// Returns the fraction of steps accepted so far, or `None` before the
+    /// first step.
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        if self.steps == 0 {
+            None
+        } else {
+            Some(self.accepted as f64 / self.steps as f64)
+        }
+    }
+}
+
+impl<S: fmt::Debug, F, D: fmt::Debug> fmt::Debug for MetropolisHastings<S, F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetropolisHastings")
+            .field("proposal", &self.proposal)
+            .field("state", &self.state)
+            .field("log_p", &self.log_p)
+            .field("steps", &self.steps)
+            .field("accepted", &self.accepted)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the states of a [`MetropolisHastings`] sampler.
+///
+/// This struct is created by [`MetropolisHastings::iter`].
+pub struct MetropolisHastingsIter<'a, S, F, D, R> {
+    sampler: &'a mut MetropolisHastings<S, F, D>,
+    rng: R,
+}
+
+impl<'a, S: fmt::Debug, F, D: fmt::Debug, R: fmt::Debug> fmt::Debug for MetropolisHastingsIter<'a, S, F, D, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetropolisHastingsIter")
+            .field("sampler", &self.sampler)
+            .field("rng", &self.rng)
+            .finish()
+    }
+}
+
+impl<'a, S, F, D, R> Iterator for MetropolisHastingsIter<'a, S, F, D, R>
+where
+    S: Clone + Add<Output = S>,
+    F: FnMut(&S) -> f64,
+    D: Distribution<S>,
+    R: Rng,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        Some(self.sampler.step(&mut self.rng).clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// The order in which a [`Gibbs`] sampler updates coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scan {
+    /// Update the coordinates in order `0..n` in each sweep.
+    Systematic,
+    /// Update `n` coordinates chosen uniformly at random (with replacement)
+    /// in each sweep.
+    Random,
+}
+
+#[cfg(feature = "alloc")]
+type Conditional<'a, T> = Box<dyn FnMut(&[T], &mut dyn RngCore) -> T + 'a>;
+
+/// A Gibbs sampler over parameter vectors.
+///
+/// A Gibbs sampler updates one coordinate `i` of the state `x` at a time,
+/// sampling it from its conditional distribution given all other
+/// coordinates. Each conditional is a function `f(x, rng)` registered via
+/// [`GibbsBuilder::conditional`], which is passed the whole current state
+/// (the value of `x[i]` itself should be ignored) and a shared RNG, and
+/// typically constructs and samples a distribution with parameters
+/// depending on `x`.
+///
+/// A *sweep* updates `n` coordinates, where `n` is the dimension of the
+/// state, in the order given by the [`Scan`]. [`Gibbs::sweep`] performs one
+/// sweep, while [`Gibbs::iter`] yields the state after each sweep.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::mcmc::Gibbs;
+/// use rand_distr::{Distribution, Normal};
+///
+/// // A bivariate normal distribution with correlation `rho`
+/// let rho = 0.8;
+/// let sd = (1.0f64 - rho * rho).sqrt();
+/// let mut gibbs = Gibbs::builder()
+///     .conditional(|x: &[f64], rng| Normal::new(rho * x[1], sd).unwrap().sample(rng))
+///     .conditional(|x: &[f64], rng| Normal::new(rho * x[0], sd).unwrap().sample(rng))
+///     .build(vec![0.0, 0.0])
+///     .unwrap();
+///
+/// let n = 10000;
+/// let cov = gibbs.iter(rand::thread_rng())
+///     .skip(100)
+///     .take(n)
+///     .map(|x| x[0] * x[1])
+///     .sum::<f64>() / n as f64;
+/// assert!((cov - rho).abs() < 0.1);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct Gibbs<'a, T> {
+    state: Vec<T>,
+    conditionals: Vec<Conditional<'a, T>>,
+    scan: Scan,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Clone> Gibbs<'a, T> {
+    /// Start building a sampler
+    pub fn builder() -> GibbsBuilder<'a, T> {
+        GibbsBuilder {
+            conditionals: Vec::new(),
+            scan: Scan::Systematic,
+        }
+    }
+
+    /// Perform one sweep, returning the new state.
+    pub fn sweep<R: Rng + ?Sized>(&mut self, mut rng: &mut R) -> &[T] {
+        let n = self.state.len();
+        for k in 0..n {
+            let i = match self.scan {
+                Scan::Systematic => k,
+                Scan::Random => rng.gen_range(0..n),
+            };
+            let x = (self.conditionals[i])(&self.state, &mut rng);
+            self.state[i] = x;
+        }
+        &self.state
+    }
+
+    /// Iterate over the states after each sweep.
+    ///
+    /// The iterator never ends; the sampler may be used again after the
+    /// iterator is dropped.
+    pub fn iter<R: Rng>(&mut self, rng: R) -> GibbsIter<'_, 'a, T, R> {
+        GibbsIter { sampler: self, rng }
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> &[T] {
+        &self.state
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: fmt::Debug> fmt::Debug for Gibbs<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gibbs")
+            .field("state", &self.state)
+            .field("scan", &self.scan)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for [`Gibbs`]
+///
+/// This struct is created by [`Gibbs::builder`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct GibbsBuilder<'a, T> {
+    conditionals: Vec<Conditional<'a, T>>,
+    scan: Scan,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Clone> GibbsBuilder<'a, T> {
+    /// Register the conditional distribution of the next coordinate
+    ///
+    /// Conditionals are registered in the order of the coordinates.
+    pub fn conditional<F>(mut self, f: F) -> Self
+    where F: FnMut(&[T], &mut dyn RngCore) -> T + 'a {
+        self.conditionals.push(Box::new(f));
+        self
+    }
+
+    /// Set the order of updates (by default, [`Scan::Systematic`])
+    pub fn scan(self, scan: Scan) -> Self {
+        GibbsBuilder { scan, ..self }
+    }
+
+    /// Build the sampler, starting at state `initial`.
+    ///
+    /// Fails with [`Error::DimensionMismatch`] unless a conditional was
+    /// registered for each of the (at least one) coordinates of `initial`.
+    pub fn build(self, initial: Vec<T>) -> Result<Gibbs<'a, T>, Error> {
+        if initial.is_empty() || initial.len() != self.conditionals.len() {
+            return Err(Error::DimensionMismatch);
+        }
+        Ok(Gibbs {
+            state: initial,
+            conditionals: self.conditionals,
+            scan: self.scan,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> fmt::Debug for GibbsBuilder<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GibbsBuilder")
+            .field("scan", &self.scan)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the states of a [`Gibbs`] sampler.
+///
+/// This struct is created by [`Gibbs::iter`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct GibbsIter<'s, 'a, T, R> {
+    sampler: &'s mut Gibbs<'a, T>,
+    rng: R,
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, 'a, T: Clone, R: Rng> Iterator for GibbsIter<'s, 'a, T, R> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        Some(self.sampler.sweep(&mut self.rng).to_vec())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// A cooling schedule of an [`Acceptance`] rule.
+///
+/// The temperature after `k` calls to [`Acceptance::cool`], given the
+/// initial temperature `T0`, is:
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cooling {
+    /// `T0 * factor^k`, where `0 < factor <= 1`
+    Exponential(f64),
+    /// `max(T0 - rate * k, 0)`, where `rate >= 0`
+    Linear(f64),
+    /// `T0 / ln(k + e)`
+    Logarithmic,
+}
+
+/// The Metropolis acceptance rule of simulated annealing.
+///
+/// A move which changes the cost (energy) of the current solution by `delta`
+/// is accepted with probability `min(1, exp(-delta / T))` at temperature
+/// `T`: improvements (`delta <= 0`) are always accepted, while deteriorations
+/// are accepted less often as the temperature decreases according to the
+/// [`Cooling`] schedule.
+///
+/// Edge cases are handled consistently: a `delta` of NaN is always rejected,
+/// `+inf` is always rejected and `-inf` always accepted, and at temperature
+/// zero only improvements are accepted. Overflow of `-delta / T` to `-inf`
+/// (for a tiny temperature) gives probability zero. Decisions are sampled
+/// via [`Rng::gen_bool`], thus no randomness is used for certain decisions.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand_distr::mcmc::{Acceptance, Cooling};
+///
+/// // Minimize (x - 3)^2 over the integers
+/// let cost = |x: i32| ((x - 3) * (x - 3)) as f64;
+/// let mut rng = rand::thread_rng();
+/// let mut acceptance = Acceptance::new(10.0, Cooling::Exponential(0.99)).unwrap();
+/// let mut x = -20;
+/// for _ in 0..2000 {
+///     let candidate = x + if rng.gen() { 1 } else { -1 };
+///     if acceptance.accept(cost(candidate) - cost(x), &mut rng) {
+///         x = candidate;
+///     }
+///     acceptance.cool();
+/// }
+/// assert!((x - 3).abs() <= 2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Acceptance {
+    initial: f64,
+    cooling: Cooling,
+    steps: u64,
+    temperature: f64,
+}
+
+impl Acceptance {
+    /// Construct with the given initial temperature and cooling schedule.
+    ///
+    /// Fails if the initial temperature is not finite and positive, or if
+    /// the parameter of the schedule is out of range.
+    pub fn new(initial_temperature: f64, cooling: Cooling) -> Result<Acceptance, Error> {
+        if !(initial_temperature > 0.0 && initial_temperature.is_finite()) {
+            return Err(Error::InvalidTemperature);
+        }
+        let valid = match cooling {
+            Cooling::Exponential(factor) => factor > 0.0 && factor <= 1.0,
+            Cooling::Linear(rate) => rate >= 0.0 && rate.is_finite(),
+            Cooling::Logarithmic => true,
+        };
+        if !valid {
+            return Err(Error::InvalidCooling);
+        }
+        Ok(Acceptance {
+            initial: initial_temperature,
+            cooling,
+            steps: 0,
+            temperature: initial_temperature,
+        })
+    }
+
+    /// Returns the current temperature.
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Returns the number of calls to [`Acceptance::cool`] so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Returns the probability of accepting a change of cost by `delta` at
+    /// the current temperature.
+    pub fn probability(&self, delta: f64) -> f64 {
+        if delta <= 0.0 {
+            1.0
+        } else if delta > 0.0 && self.temperature > 0.0 {
+            (-delta / self.temperature).exp()
+        } else {
+            // NaN, or a deterioration at temperature zero
+            0.0
+        }
+    }
+
+    /// Decide whether to accept a change of cost by `delta` at the current
+    /// temperature.
+    pub fn accept<R: Rng + ?Sized>(&self, delta: f64, rng: &mut R) -> bool {
+        rng.gen_bool(self.probability(delta))
+    }
+
+    /// Lower the temperature by one step of the cooling schedule.
+    pub fn cool(&mut self) {
+        self.steps = self.steps.saturating_add(1);
+        // Computed from the initial temperature to avoid accumulating errors
+        let k = self.steps as f64;
+        self.temperature = match self.cooling {
+            Cooling::Exponential(factor) => self.initial * factor.powf(k),
+            Cooling::Linear(rate) => (self.initial - rate * k).max(0.0),
+            Cooling::Logarithmic => self.initial / (k + core::f64::consts::E).ln(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Normal, Uniform};
+
+    #[test]
+    fn test_metropolis_hastings_invalid() {
+        let proposal = Uniform::new(-1.0, 1.0).unwrap();
+        let log_density = |x: &f64| if *x > 0.0 { 0.0 } else { f64::NEG_INFINITY };
+        assert_eq!(
+            MetropolisHastings::new(log_density, proposal, -1.0).unwrap_err(),
+            Error::InitialStateImpossible
+        );
+        assert_eq!(
+            MetropolisHastings::new(|_: &f64| f64::NAN, proposal, 0.0).unwrap_err(),
+            Error::InitialStateImpossible
+        );
+    }
+
+    #[test]
+    fn test_metropolis_hastings() {
+        // Exponential(1) target, which has support [0, inf)
+        let log_density = |x: &f64| if *x >= 0.0 { -x } else { f64::NEG_INFINITY };
+        let proposal = Normal::new(0.0, 2.0).unwrap();
+        let mut sampler = MetropolisHastings::new(log_density, proposal, 1.0).unwrap();
+        assert_eq!(sampler.acceptance_rate(), None);
+        assert_eq!(sampler.log_density(), -1.0);
+
+        let mut rng = crate::test::rng(268);
+        let n = 20000;
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for x in sampler.iter(&mut rng).skip(1000).take(n) {
+            assert!(x >= 0.0);
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / n as f64;
+        let var = sum_sq / n as f64 - mean * mean;
+        assert!((mean - 1.0).abs() < 0.1, "{}", mean);
+        assert!((var - 1.0).abs() < 0.2, "{}", var);
+
+        let rate = sampler.acceptance_rate().unwrap();
+        assert!(rate > 0.2 && rate < 0.8, "{}", rate);
+        assert_eq!(sampler.log_density(), -sampler.state());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gibbs_invalid() {
+        let builder = Gibbs::builder().conditional(|_: &[f64], _: &mut dyn RngCore| 0.0);
+        assert_eq!(
+            builder.build(alloc::vec![0.0, 0.0]).unwrap_err(),
+            Error::DimensionMismatch
+        );
+        assert_eq!(
+            Gibbs::<f64>::builder().build(Vec::new()).unwrap_err(),
+            Error::DimensionMismatch
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gibbs() {
+        // Coordinates are updated in order, each seeing the latest state
+        let mut gibbs = Gibbs::builder()
+            .conditional(|x: &[u32], _: &mut dyn RngCore| x[1] + 1)
+            .conditional(|x: &[u32], _: &mut dyn RngCore| x[0] * 10)
+            .build(alloc::vec![0, 0])
+            .unwrap();
+        let mut rng = crate::test::rng(269);
+        assert_eq!(gibbs.sweep(&mut rng), [1, 10]);
+        assert_eq!(gibbs.iter(&mut rng).next().unwrap(), [11, 110]);
+        assert_eq!(gibbs.state(), [11, 110]);
+
+        // The bivariate standard normal with correlation 0.5
+        for &scan in &[Scan::Systematic, Scan::Random] {
+            let rho = 0.5;
+            let sd = (1.0f64 - rho * rho).sqrt();
+            let mut gibbs = Gibbs::builder()
+                .conditional(|x: &[f64], rng: &mut dyn RngCore| Normal::new(rho * x[1], sd).unwrap().sample(rng))
+                .conditional(|x: &[f64], rng: &mut dyn RngCore| Normal::new(rho * x[0], sd).unwrap().sample(rng))
+                .scan(scan)
+                .build(alloc::vec![3.0, -3.0])
+                .unwrap();
+            let n = 20000;
+            let (mut mean, mut cov) = (0.0, 0.0);
+            for x in gibbs.iter(&mut rng).skip(100).take(n) {
+                mean += x[0] / n as f64;
+                cov += x[0] * x[1] / n as f64;
+            }
+            assert!(mean.abs() < 0.05, "{:?}: {}", scan, mean);
+            assert!((cov - rho).abs() < 0.05, "{:?}: {}", scan, cov);
+        }
+    }
+
+    #[test]
+    fn test_acceptance_invalid() {
+        for &t in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                Acceptance::new(t, Cooling::Logarithmic).unwrap_err(),
+                Error::InvalidTemperature
+            );
+        }
+        for &cooling in &[
+            Cooling::Exponential(0.0),
+            Cooling::Exponential(1.5),
+            Cooling::Exponential(f64::NAN),
+            Cooling::Linear(-1.0),
+            Cooling::Linear(f64::INFINITY),
+        ] {
+            assert_eq!(Acceptance::new(1.0, cooling).unwrap_err(), Error::InvalidCooling);
+        }
+    }
+
+    #[test]
+    fn test_acceptance() {
+        let mut acceptance = Acceptance::new(2.0, Cooling::Linear(1.0)).unwrap();
+        assert_eq!(acceptance.probability(-1.0), 1.0);
+        assert_eq!(acceptance.probability(0.0), 1.0);
+        assert_eq!(acceptance.probability(2.0), (-1.0f64).exp());
+        assert_eq!(acceptance.probability(f64::NAN), 0.0);
+        assert_eq!(acceptance.probability(f64::INFINITY), 0.0);
+        assert_eq!(acceptance.probability(f64::NEG_INFINITY), 1.0);
+        acceptance.cool();
+        assert_eq!(acceptance.temperature(), 1.0);
+        acceptance.cool();
+        acceptance.cool();
+        assert_eq!(acceptance.steps(), 3);
+        assert_eq!(acceptance.temperature(), 0.0);
+        assert_eq!(acceptance.probability(1e-300), 0.0);
+        assert_eq!(acceptance.probability(0.0), 1.0);
+
+        let mut acceptance = Acceptance::new(1.0, Cooling::Exponential(0.5)).unwrap();
+        acceptance.cool();
+        acceptance.cool();
+        assert_eq!(acceptance.temperature(), 0.25);
+        let mut acceptance = Acceptance::new(1.0, Cooling::Logarithmic).unwrap();
+        acceptance.cool();
+        assert_eq!(acceptance.temperature(), 1.0 / (1.0 + core::f64::consts::E).ln());
+
+        // Tiny temperatures underflow to probability zero
+        let acceptance = Acceptance::new(f64::MIN_POSITIVE, Cooling::Logarithmic).unwrap();
+        assert_eq!(acceptance.probability(f64::MAX), 0.0);
+
+        // The acceptance frequency matches the probability
+        let acceptance = Acceptance::new(1.0, Cooling::Logarithmic).unwrap();
+        let mut rng = crate::test::rng(270);
+        let n = 10000;
+        let count = (0..n).filter(|_| acceptance.accept(1.0, &mut rng)).count();
+        let p = (-1.0f64).exp();
+        assert!((count as f64 / n as f64 - p).abs() < 0.02, "{}", count);
+    }
+
+    #[test]
+    fn value_stability() {
+        let proposal = Uniform::new(-1.0, 1.0).unwrap();
+        let mut sampler = MetropolisHastings::new(|x: &f64| -x * x / 2.0, proposal, 0.0).unwrap();
+        let mut rng = crate::test::rng(269);
+        let mut buf = [0.0; 4];
+        for x in buf.iter_mut() {
+            *x = *sampler.step(&mut rng);
+        }
+        assert_eq!(buf, [0.503068507843583, 0.6527717425852475, 0.21625232262275995, 0.4455802526077277]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn value_stability_gibbs() {
+        let uniform = Uniform::new(0.0, 1.0).unwrap();
+        let mut gibbs = Gibbs::builder()
+            .conditional(|x: &[f64], rng: &mut dyn RngCore| x[1] + uniform.sample(rng))
+            .conditional(|x: &[f64], rng: &mut dyn RngCore| x[0] * uniform.sample(rng))
+            .scan(Scan::Random)
+            .build(alloc::vec![0.0, 0.0])
+            .unwrap();
+        let mut rng = crate::test::rng(270);
+        assert_eq!(gibbs.sweep(&mut rng), [0.3097052700819274, 0.0]);
+    }
+}
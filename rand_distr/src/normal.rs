@@ -92,6 +92,24 @@ impl Distribution<f64> for StandardNormal {
     }
 }
 
+/// Samples statically sized [`nalgebra`](https://docs.rs/nalgebra) vectors and
+/// matrices with independent standard normal elements.
+#[cfg(feature = "nalgebra")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "nalgebra")))]
+impl<T, R, C> Distribution<nalgebra::OMatrix<T, R, C>> for StandardNormal
+where
+    T: nalgebra::Scalar,
+    R: nalgebra::DimName,
+    C: nalgebra::DimName,
+    nalgebra::DefaultAllocator: nalgebra::allocator::Allocator<T, R, C>,
+    StandardNormal: Distribution<T>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> nalgebra::OMatrix<T, R, C> {
+        nalgebra::OMatrix::from_fn(|_, _| self.sample(rng))
+    }
+}
+
 /// The normal distribution `N(mean, std_dev**2)`.
 ///
 /// This uses the ZIGNOR variant of the Ziggurat method, see [`StandardNormal`]
@@ -319,6 +337,18 @@ where F: Float, StandardNormal: Distribution<F>
 mod tests {
     use super::*;
 
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_standard_normal_nalgebra() {
+        let mut rng = crate::test::rng(208);
+        let m: nalgebra::Matrix3<f64> = StandardNormal.sample(&mut rng);
+        let mut rng = crate::test::rng(208);
+        for &x in m.iter() {
+            let y: f64 = StandardNormal.sample(&mut rng);
+            assert_eq!(x, y);
+        }
+    }
+
     #[test]
     fn test_normal() {
         let norm = Normal::new(10.0, 10.0).unwrap();
@@ -0,0 +1,203 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random permutation and doubly stochastic matrices.
+#![cfg(feature = "alloc")]
+use crate::{Distribution, Exp1};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The uniform distribution over the permutations of `0..n`.
+///
+/// A sample `p` represents the `n × n` permutation matrix `P` whose only
+/// non-zero entries are `P[i][p[i]] = 1`. All `n!` permutations are equally
+/// likely (a Fisher–Yates shuffle is used).
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Permutation};
+///
+/// let p: Vec<usize> = Permutation::new(4).sample(&mut rand::thread_rng());
+/// // Relabel the vertices of a graph given by its edges
+/// let edges = [(0, 1), (1, 2), (2, 3)];
+/// let relabelled: Vec<(usize, usize)> = edges.iter().map(|&(a, b)| (p[a], p[b])).collect();
+/// # let _ = relabelled;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Permutation {
+    n: usize,
+}
+
+impl Permutation {
+    /// Construct the distribution over permutations of `0..n`.
+    pub fn new(n: usize) -> Permutation {
+        Permutation { n }
+    }
+
+    /// Returns the size `n`.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+}
+
+impl Distribution<Vec<usize>> for Permutation {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<usize> {
+        let mut p: Vec<usize> = (0..self.n).collect();
+        p.shuffle(rng);
+        p
+    }
+}
+
+/// Random doubly stochastic matrices, as convex combinations of random
+/// permutation matrices.
+///
+/// By the Birkhoff–von Neumann theorem, every doubly stochastic matrix (with
+/// non-negative entries and all rows and columns summing to 1) is a convex
+/// combination of permutation matrices. Each sample of this distribution is
+/// the combination of `k` independent uniformly random permutation matrices
+/// (see [`Permutation`]) with weights sampled uniformly from the unit simplex.
+/// Small `k` give sparse matrices, while the matrices become denser and
+/// closer to the uniform matrix with entries `1/n` as `k` grows.
+///
+/// Samples are `n × n` matrices given as vectors of rows. Rows and columns
+/// sum to 1 up to rounding errors.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, DoublyStochastic};
+///
+/// let distr = DoublyStochastic::new(5, 3).unwrap();
+/// let m: Vec<Vec<f64>> = distr.sample(&mut rand::thread_rng());
+/// for row in &m {
+///     assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoublyStochastic {
+    n: usize,
+    k: usize,
+}
+
+/// Error type returned from [`DoublyStochastic::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The number of permutations `k` is zero.
+    NoPermutations,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::NoPermutations => "number of permutations is zero in doubly stochastic distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl DoublyStochastic {
+    /// Construct the distribution of `n × n` matrices combining `k`
+    /// permutation matrices.
+    ///
+    /// Fails if `k == 0`.
+    pub fn new(n: usize, k: usize) -> Result<DoublyStochastic, Error> {
+        if k == 0 {
+            return Err(Error::NoPermutations);
+        }
+        Ok(DoublyStochastic { n, k })
+    }
+}
+
+impl Distribution<Vec<Vec<f64>>> for DoublyStochastic {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Vec<f64>> {
+        // Uniform weights on the simplex, as normalized exponential variates
+        let mut weights: Vec<f64> = (0..self.k).map(|_| Exp1.sample(rng)).collect();
+        let sum: f64 = weights.iter().sum();
+        for w in weights.iter_mut() {
+            *w /= sum;
+        }
+
+        let permutation = Permutation::new(self.n);
+        let mut m = vec![vec![0.0; self.n]; self.n];
+        for w in weights {
+            let p: Vec<usize> = permutation.sample(rng);
+            for (row, &j) in m.iter_mut().zip(p.iter()) {
+                row[j] += w;
+            }
+        }
+        m
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_permutation() {
+        let mut rng = crate::test::rng(275);
+        let distr = Permutation::new(4);
+        assert_eq!(distr.n(), 4);
+        let mut counts = [[0u32; 4]; 4];
+        for _ in 0..4000 {
+            let p: Vec<usize> = distr.sample(&mut rng);
+            let mut sorted = p.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, [0, 1, 2, 3]);
+            for (i, &j) in p.iter().enumerate() {
+                counts[i][j] += 1;
+            }
+        }
+        // Each entry of the matrix is 1 with probability 1/4
+        for &c in counts.iter().flatten() {
+            assert!((c as f64 - 1000.0).abs() < 120.0, "{:?}", counts);
+        }
+        assert!(Permutation::new(0).sample(&mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_doubly_stochastic() {
+        assert_eq!(DoublyStochastic::new(3, 0).unwrap_err(), Error::NoPermutations);
+        let mut rng = crate::test::rng(276);
+        for &(n, k) in &[(1, 1), (5, 1), (5, 3), (10, 50)] {
+            let m = DoublyStochastic::new(n, k).unwrap().sample(&mut rng);
+            assert_eq!(m.len(), n);
+            for i in 0..n {
+                assert_eq!(m[i].len(), n);
+                assert!(m[i].iter().all(|&x| x >= 0.0));
+                assert_almost_eq!(m[i].iter().sum::<f64>(), 1.0, 1e-12);
+                assert_almost_eq!(m.iter().map(|row| row[i]).sum::<f64>(), 1.0, 1e-12);
+            }
+            if k == 1 {
+                // A permutation matrix
+                assert!(m.iter().flatten().all(|&x| x == 0.0 || x == 1.0));
+            }
+        }
+        assert!(DoublyStochastic::new(0, 1).unwrap().sample(&mut rng).is_empty());
+    }
+
+    #[test]
+    fn value_stability() {
+        let p = Permutation::new(6).sample(&mut crate::test::rng(277));
+        assert_eq!(p, [4, 1, 0, 3, 2, 5]);
+        let m = DoublyStochastic::new(2, 2).unwrap().sample(&mut crate::test::rng(278));
+        assert_eq!(m, [
+            [0.4093284587247738, 0.5906715412752263],
+            [0.5906715412752263, 0.4093284587247738]
+        ]);
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Poisson-disk sampling.
+#![cfg(feature = "alloc")]
+use crate::{Distribution, Uniform};
+use alloc::vec::Vec;
+use core::fmt;
+use num_traits::Float;
+use rand::Rng;
+
+/// Samples a set of points with a minimum-distance constraint from an
+/// axis-aligned box `[0, extent[0]) × ... × [0, extent[N-1])`.
+///
+/// Each sample is a `Vec` of points, no two of which are closer than `radius`
+/// to each other, and which are "maximal" in the sense that (with high
+/// probability) no further point could be added. Such point sets are also
+/// known as blue noise and are widely used in graphics and procedural
+/// generation, where they look natural without clumping.
+///
+/// Implemented via Bridson's algorithm
+/// ["Fast Poisson Disk Sampling in Arbitrary Dimensions"](https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf),
+/// which runs in time linear in the number of points. A background grid with
+/// one point per cell accelerates the distance checks; its memory use is
+/// proportional to the volume of the box divided by `radius^N`.
+///
+/// The algorithm works in any dimension, but is most useful for `N = 2` and
+/// `N = 3`.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{PoissonDisk, Distribution};
+///
+/// let disk = PoissonDisk::new([10.0, 5.0], 1.0).unwrap();
+/// let points: Vec<[f64; 2]> = disk.sample(&mut rand::thread_rng());
+/// println!("{} points with spacing at least 1.0", points.len());
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoissonDisk<const N: usize> {
+    extent: [f64; N],
+    radius: f64,
+    attempts: u32,
+    cell_size: f64,
+    grid_dims: [usize; N],
+}
+
+/// Error type returned from `PoissonDisk::new`.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `N == 0`.
+    ZeroDimension,
+    /// `extent` has a component which is not finite positive.
+    ExtentNotPositive,
+    /// `radius <= 0` or `nan` or infinite.
+    RadiusNotPositive,
+    /// `attempts == 0`.
+    ZeroAttempts,
+    /// The background grid would have more than `usize::MAX` cells.
+    TooManyCells,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::ZeroDimension => "dimension is zero in Poisson-disk sampler",
+            Error::ExtentNotPositive => "extent is not positive and finite in Poisson-disk sampler",
+            Error::RadiusNotPositive => "radius is not positive and finite in Poisson-disk sampler",
+            Error::ZeroAttempts => "number of attempts is zero in Poisson-disk sampler",
+            Error::TooManyCells => "radius too small relative to extent in Poisson-disk sampler",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<const N: usize> PoissonDisk<N> {
+    /// Default number of candidates tried around each active point, as
+    /// suggested by Bridson.
+    pub const DEFAULT_ATTEMPTS: u32 = 30;
+
+    /// Construct a new `PoissonDisk` sampler over the box `[0, extent)` with
+    /// minimum distance `radius` between points.
+    pub fn new(extent: [f64; N], radius: f64) -> Result<PoissonDisk<N>, Error> {
+        Self::with_attempts(extent, radius, Self::DEFAULT_ATTEMPTS)
+    }
+
+    /// Construct a new `PoissonDisk` sampler, trying `attempts` candidates
+    /// around each point before it is retired.
+    ///
+    /// Larger values give denser point sets at the cost of speed.
+    pub fn with_attempts(
+        extent: [f64; N], radius: f64, attempts: u32,
+    ) -> Result<PoissonDisk<N>, Error> {
+        if N == 0 {
+            return Err(Error::ZeroDimension);
+        }
+        if !(radius > 0.0) || radius.is_infinite() {
+            return Err(Error::RadiusNotPositive);
+        }
+        if attempts == 0 {
+            return Err(Error::ZeroAttempts);
+        }
+        // Each cell has diagonal `radius`, so holds at most one point.
+        let cell_size = radius / Float::sqrt(N as f64);
+        let mut grid_dims = [0; N];
+        let mut cells: usize = 1;
+        for (dim, &e) in grid_dims.iter_mut().zip(extent.iter()) {
+            if !(e > 0.0) || e.is_infinite() {
+                return Err(Error::ExtentNotPositive);
+            }
+            let n = Float::ceil(e / cell_size);
+            if n >= usize::MAX as f64 {
+                return Err(Error::TooManyCells);
+            }
+            *dim = n as usize;
+            cells = cells.checked_mul(*dim).ok_or(Error::TooManyCells)?;
+        }
+        Ok(PoissonDisk {
+            extent,
+            radius,
+            attempts,
+            cell_size,
+            grid_dims,
+        })
+    }
+
+    /// Returns the minimum distance between points.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the extent of the sampled box.
+    pub fn extent(&self) -> [f64; N] {
+        self.extent
+    }
+
+    fn cell_of(&self, p: &[f64; N]) -> [usize; N] {
+        let mut cell = [0; N];
+        for ((c, &x), &dim) in cell.iter_mut().zip(p.iter()).zip(self.grid_dims.iter()) {
+            // Clamp for safety against rounding at the upper boundary
+            *c = ((x / self.cell_size) as usize).min(dim - 1);
+        }
+        cell
+    }
+
+    fn grid_index(&self, cell: &[usize; N]) -> usize {
+        cell.iter()
+            .zip(self.grid_dims.iter())
+            .fold(0, |index, (&c, &dim)| index * dim + c)
+    }
+
+    /// Checks whether any accepted point lies within `radius` of `p`.
+    fn is_far(&self, p: &[f64; N], grid: &[usize], points: &[[f64; N]]) -> bool {
+        // A point within `radius` may be up to `sqrt(N)` cells away on each axis
+        let reach = Float::ceil(Float::sqrt(N as f64)) as usize;
+        let centre = self.cell_of(p);
+        let mut lo = [0; N];
+        let mut hi = [0; N];
+        for (k, &c) in centre.iter().enumerate() {
+            lo[k] = c.saturating_sub(reach);
+            hi[k] = (c + reach).min(self.grid_dims[k] - 1);
+        }
+
+        // Visit all cells in the neighbourhood, odometer-style
+        let mut cell = lo;
+        loop {
+            let i = grid[self.grid_index(&cell)];
+            if i != EMPTY {
+                let q = &points[i];
+                let dist2: f64 = p.iter().zip(q.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+                if dist2 < self.radius * self.radius {
+                    return false;
+                }
+            }
+
+            let mut k = 0;
+            loop {
+                if k == N {
+                    return true;
+                }
+                if cell[k] < hi[k] {
+                    cell[k] += 1;
+                    break;
+                }
+                cell[k] = lo[k];
+                k += 1;
+            }
+        }
+    }
+}
+
+const EMPTY: usize = usize::MAX;
+
+impl<const N: usize> Distribution<Vec<[f64; N]>> for PoissonDisk<N> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<[f64; N]> {
+        let cells = self.grid_dims.iter().product();
+        let mut grid = Vec::with_capacity(cells);
+        grid.resize(cells, EMPTY);
+        let mut points = Vec::new();
+        let mut active = Vec::new();
+
+        let mut first = [0.0; N];
+        for (x, &e) in first.iter_mut().zip(self.extent.iter()) {
+            *x = rng.gen_range(0.0..e);
+        }
+        grid[self.grid_index(&self.cell_of(&first))] = 0;
+        points.push(first);
+        active.push(0);
+
+        // Candidates are drawn uniformly from the annulus [r, 2r) around an
+        // active point, by rejection from the enclosing cube.
+        let offset = Uniform::new(-2.0 * self.radius, 2.0 * self.radius).unwrap();
+        let (r2, r2_max) = (self.radius * self.radius, 4.0 * self.radius * self.radius);
+
+        while !active.is_empty() {
+            let a = rng.gen_range(0..active.len());
+            let centre = points[active[a]];
+            let mut found = false;
+
+            for _ in 0..self.attempts {
+                let mut p = [0.0; N];
+                loop {
+                    let mut dist2 = 0.0;
+                    for (x, &c) in p.iter_mut().zip(centre.iter()) {
+                        let d = offset.sample(rng);
+                        *x = c + d;
+                        dist2 += d * d;
+                    }
+                    if dist2 >= r2 && dist2 < r2_max {
+                        break;
+                    }
+                }
+
+                let inside = p.iter().zip(self.extent.iter()).all(|(&x, &e)| x >= 0.0 && x < e);
+                if inside && self.is_far(&p, &grid, &points) {
+                    grid[self.grid_index(&self.cell_of(&p))] = points.len();
+                    active.push(points.len());
+                    points.push(p);
+                    found = true;
+                    break;
+                }
+            }
+
+            if !found {
+                active.swap_remove(a);
+            }
+        }
+
+        points
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_spacing<const N: usize>(disk: &PoissonDisk<N>, points: &[[f64; N]]) {
+        for (i, p) in points.iter().enumerate() {
+            for (&x, &e) in p.iter().zip(disk.extent.iter()) {
+                assert!(x >= 0.0 && x < e);
+            }
+            for q in &points[..i] {
+                let dist2: f64 = p.iter().zip(q.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+                assert!(dist2 >= disk.radius * disk.radius);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(PoissonDisk::new([], 1.0), Err(Error::ZeroDimension));
+        assert_eq!(PoissonDisk::new([1.0, 0.0], 1.0), Err(Error::ExtentNotPositive));
+        assert_eq!(PoissonDisk::new([1.0, f64::NAN], 1.0), Err(Error::ExtentNotPositive));
+        assert_eq!(PoissonDisk::new([1.0, 1.0], 0.0), Err(Error::RadiusNotPositive));
+        assert_eq!(PoissonDisk::new([1.0, 1.0], f64::INFINITY), Err(Error::RadiusNotPositive));
+        assert_eq!(PoissonDisk::with_attempts([1.0, 1.0], 1.0, 0), Err(Error::ZeroAttempts));
+        assert_eq!(PoissonDisk::new([1e300, 1e300], 1e-300), Err(Error::TooManyCells));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_poisson_disk_2d() {
+        let disk = PoissonDisk::new([20.0, 10.0], 1.0).unwrap();
+        let mut rng = crate::test::rng(205);
+        let points = disk.sample(&mut rng);
+        check_spacing(&disk, &points);
+        // Maximal Poisson-disk sets have density roughly 0.7 / r^2
+        assert!(points.len() > 100, "{}", points.len());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_poisson_disk_3d() {
+        let disk = PoissonDisk::new([5.0, 5.0, 5.0], 1.0).unwrap();
+        let mut rng = crate::test::rng(206);
+        let points = disk.sample(&mut rng);
+        check_spacing(&disk, &points);
+        assert!(points.len() > 60, "{}", points.len());
+    }
+
+    #[test]
+    fn test_small_box() {
+        // Box smaller than the radius fits exactly one point
+        let disk = PoissonDisk::new([0.5, 0.5], 1.0).unwrap();
+        let mut rng = crate::test::rng(207);
+        assert_eq!(disk.sample(&mut rng).len(), 1);
+    }
+}
@@ -0,0 +1,423 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform points in triangles and convex polygons, and on polylines.
+
+use crate::Distribution;
+#[cfg(feature = "alloc")]
+use crate::WeightedIndex;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+#[allow(unused_imports)]
+use num_traits::Float;
+use rand::Rng;
+
+/// Error type returned from the constructors of [`Triangle`],
+/// [`ConvexPolygon`] and [`Polyline`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A coordinate is infinite or NaN.
+    NotFinite,
+    /// Too few vertices: a polygon needs at least three, a polyline two.
+    TooFewVertices,
+    /// The polygon is not convex (or not simple).
+    NotConvex,
+    /// The polygon has zero area, or the polyline zero length.
+    Degenerate,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::NotFinite => "coordinate is infinite or NaN in polygon distribution",
+            Error::TooFewVertices => "too few vertices in polygon distribution",
+            Error::NotConvex => "polygon is not convex in polygon distribution",
+            Error::Degenerate => "zero area or length in polygon distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+// Twice the signed area of the triangle (a, b, c); positive if
+// counter-clockwise
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+// The sum of the exterior angles of a closed polygon, ignoring repeated
+// vertices
+#[cfg(feature = "alloc")]
+fn turning_angle(vertices: &[[f64; 2]]) -> f64 {
+    let n = vertices.len();
+    let edges: Vec<[f64; 2]> = (0..n)
+        .map(|i| {
+            let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+            [b[0] - a[0], b[1] - a[1]]
+        })
+        .filter(|e| *e != [0.0, 0.0])
+        .collect();
+    let m = edges.len();
+    (0..m)
+        .map(|i| {
+            let (e, f) = (edges[i], edges[(i + 1) % m]);
+            let cross = e[0] * f[1] - e[1] * f[0];
+            let dot = e[0] * f[0] + e[1] * f[1];
+            cross.atan2(dot)
+        })
+        .sum()
+}
+
+fn check_finite(points: &[[f64; 2]]) -> Result<(), Error> {
+    if points.iter().flatten().all(|x| x.is_finite()) {
+        Ok(())
+    } else {
+        Err(Error::NotFinite)
+    }
+}
+
+/// The uniform distribution over a triangle.
+///
+/// Samples are points `[x, y]` in the triangle with vertices `a`, `b` and
+/// `c` (including its boundary). With two uniform variates `u` and `v`, a
+/// sample is `(1 - √u) a + √u (1 - v) b + √u v c`, which is uniform by area.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Triangle};
+///
+/// let triangle = Triangle::new([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]).unwrap();
+/// let [x, y] = triangle.sample(&mut rand::thread_rng());
+/// assert!(x >= 0.0 && y >= 0.0 && x + y <= 1.0 + 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle {
+    a: [f64; 2],
+    b: [f64; 2],
+    c: [f64; 2],
+}
+
+impl Triangle {
+    /// Construct from the three vertices, in any order.
+    ///
+    /// Degenerate triangles (with collinear vertices) are allowed; samples
+    /// then lie on a segment.
+    pub fn new(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Result<Triangle, Error> {
+        check_finite(&[a, b, c])?;
+        Ok(Triangle { a, b, c })
+    }
+
+    /// Returns the area of the triangle.
+    pub fn area(&self) -> f64 {
+        cross(self.a, self.b, self.c).abs() / 2.0
+    }
+}
+
+impl Distribution<[f64; 2]> for Triangle {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let r = rng.gen::<f64>().sqrt();
+        let v: f64 = rng.gen();
+        let (wa, wb, wc) = (1.0 - r, r * (1.0 - v), r * v);
+        [
+            wa * self.a[0] + wb * self.b[0] + wc * self.c[0],
+            wa * self.a[1] + wb * self.b[1] + wc * self.c[1],
+        ]
+    }
+}
+
+/// The uniform distribution over a convex polygon.
+///
+/// The polygon is split into a fan of triangles from its first vertex; each
+/// sample chooses a triangle with probability proportional to its area (via
+/// a [`WeightedIndex`]), then a uniform point within it as by [`Triangle`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{ConvexPolygon, Distribution};
+///
+/// let hexagon: Vec<[f64; 2]> = (0..6)
+///     .map(|i| {
+///         let angle = i as f64 * std::f64::consts::PI / 3.0;
+///         [angle.cos(), angle.sin()]
+///     })
+///     .collect();
+/// let polygon = ConvexPolygon::new(&hexagon).unwrap();
+/// let [x, y] = polygon.sample(&mut rand::thread_rng());
+/// assert!(x * x + y * y <= 1.0 + 1e-12);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexPolygon {
+    triangles: Vec<Triangle>,
+    index: WeightedIndex<f64>,
+}
+
+#[cfg(feature = "alloc")]
+impl ConvexPolygon {
+    /// Construct from the vertices of a convex polygon, in clockwise or
+    /// counter-clockwise order.
+    ///
+    /// Collinear consecutive vertices are allowed. Fails if there are fewer
+    /// than three vertices, if the polygon is not convex (including polygons
+    /// winding more than once, such as a pentagram), or if its area is zero.
+    pub fn new(vertices: &[[f64; 2]]) -> Result<ConvexPolygon, Error> {
+        check_finite(vertices)?;
+        let n = vertices.len();
+        if n < 3 {
+            return Err(Error::TooFewVertices);
+        }
+        let v0 = vertices[0];
+        let fan: Vec<f64> = vertices[1..]
+            .windows(2)
+            .map(|w| cross(v0, w[0], w[1]))
+            .collect();
+        let total: f64 = fan.iter().sum();
+        if total == 0.0 {
+            return Err(Error::Degenerate);
+        }
+        // All turns at the vertices, and all triangles of the fan, must have
+        // the orientation of the polygon
+        let turns =
+            (0..n).map(|i| cross(vertices[i], vertices[(i + 1) % n], vertices[(i + 2) % n]));
+        if turns.chain(fan.iter().cloned()).any(|x| x * total < 0.0) {
+            return Err(Error::NotConvex);
+        }
+        // The exterior angles of a polygon winding k times sum to 2πk
+        if turning_angle(vertices).abs() > 3.0 * core::f64::consts::PI {
+            return Err(Error::NotConvex);
+        }
+
+        let triangles = vertices[1..]
+            .windows(2)
+            .map(|w| Triangle {
+                a: v0,
+                b: w[0],
+                c: w[1],
+            })
+            .collect();
+        let index =
+            WeightedIndex::new(fan.iter().map(|x| x.abs())).map_err(|_| Error::Degenerate)?;
+        Ok(ConvexPolygon { triangles, index })
+    }
+
+    /// Returns the area of the polygon.
+    pub fn area(&self) -> f64 {
+        self.index.total_weight() / 2.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<[f64; 2]> for ConvexPolygon {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        self.triangles[self.index.sample(rng)].sample(rng)
+    }
+}
+
+/// The uniform distribution over a polyline, by length.
+///
+/// Each sample chooses a segment with probability proportional to its length
+/// (via a [`WeightedIndex`]), then a uniform point on it.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Polyline};
+///
+/// // An L-shaped path; the horizontal part is twice as long
+/// let path = Polyline::new(&[[0.0, 1.0], [0.0, 0.0], [2.0, 0.0]]).unwrap();
+/// assert_eq!(path.length(), 3.0);
+/// let [x, y] = path.sample(&mut rand::thread_rng());
+/// assert!(x == 0.0 || y == 0.0);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+    points: Vec<[f64; 2]>,
+    index: WeightedIndex<f64>,
+}
+
+#[cfg(feature = "alloc")]
+impl Polyline {
+    /// Construct from the points of the polyline, in order.
+    ///
+    /// Fails if there are fewer than two points, or if the total length is
+    /// zero.
+    pub fn new(points: &[[f64; 2]]) -> Result<Polyline, Error> {
+        check_finite(points)?;
+        if points.len() < 2 {
+            return Err(Error::TooFewVertices);
+        }
+        let lengths = points
+            .windows(2)
+            .map(|w| (w[1][0] - w[0][0]).hypot(w[1][1] - w[0][1]));
+        let index = WeightedIndex::new(lengths).map_err(|_| Error::Degenerate)?;
+        Ok(Polyline {
+            points: points.to_vec(),
+            index,
+        })
+    }
+
+    /// Returns the total length of the polyline.
+    pub fn length(&self) -> f64 {
+        self.index.total_weight()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<[f64; 2]> for Polyline {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let i = self.index.sample(rng);
+        let (a, b) = (self.points[i], self.points[i + 1]);
+        let t: f64 = rng.gen();
+        [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_triangle() {
+        assert_eq!(
+            Triangle::new([0.0, f64::NAN], [1.0, 0.0], [0.0, 1.0]).unwrap_err(),
+            Error::NotFinite
+        );
+        let triangle = Triangle::new([0.0, 0.0], [2.0, 0.0], [0.0, 2.0]).unwrap();
+        assert_eq!(triangle.area(), 2.0);
+
+        // The region x + y < 1 has a quarter of the area
+        let mut rng = crate::test::rng(276);
+        let n = 10000;
+        let mut inner = 0;
+        for _ in 0..n {
+            let [x, y] = triangle.sample(&mut rng);
+            assert!(x >= 0.0 && y >= 0.0 && x + y <= 2.0 + 1e-12);
+            if x + y < 1.0 {
+                inner += 1;
+            }
+        }
+        assert!((inner as f64 - 2500.0).abs() < 150.0, "{}", inner);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_convex_polygon() {
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.5, 1.0], [0.0, 1.0]];
+        assert_eq!(
+            ConvexPolygon::new(&square[..2]).unwrap_err(),
+            Error::TooFewVertices
+        );
+        assert_eq!(
+            ConvexPolygon::new(&[[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]]).unwrap_err(),
+            Error::Degenerate
+        );
+        let dart = [[0.0, 0.0], [2.0, 1.0], [0.0, 2.0], [1.0, 1.0]];
+        assert_eq!(ConvexPolygon::new(&dart).unwrap_err(), Error::NotConvex);
+        let pentagram: alloc::vec::Vec<[f64; 2]> = (0..5)
+            .map(|i| {
+                let angle = (i * 2 % 5) as f64 * 2.0 * core::f64::consts::PI / 5.0;
+                [angle.cos(), angle.sin()]
+            })
+            .collect();
+        assert_eq!(
+            ConvexPolygon::new(&pentagram).unwrap_err(),
+            Error::NotConvex
+        );
+        // Locally convex, but winding twice around the origin
+        let spiral: alloc::vec::Vec<[f64; 2]> = (0..40)
+            .map(|k| {
+                let theta = 4.0 * core::f64::consts::PI * k as f64 / 40.0;
+                let r = 1.0 + 0.05 * (1.0 - (theta / 2.0).cos());
+                [r * theta.cos(), r * theta.sin()]
+            })
+            .collect();
+        assert_eq!(ConvexPolygon::new(&spiral).unwrap_err(), Error::NotConvex);
+
+        // Either orientation, with a collinear vertex
+        let mut rng = crate::test::rng(277);
+        let mut reversed = square;
+        reversed.reverse();
+        for vertices in [square, reversed].iter() {
+            let polygon = ConvexPolygon::new(vertices).unwrap();
+            assert_eq!(polygon.area(), 1.0);
+            let n = 10000;
+            let mut quadrants = [0u32; 4];
+            for _ in 0..n {
+                let [x, y] = polygon.sample(&mut rng);
+                assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+                quadrants[(x >= 0.5) as usize * 2 + (y >= 0.5) as usize] += 1;
+            }
+            for &q in &quadrants {
+                assert!((q as f64 - 2500.0).abs() < 150.0, "{:?}", quadrants);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_polyline() {
+        assert_eq!(
+            Polyline::new(&[[0.0, 0.0]]).unwrap_err(),
+            Error::TooFewVertices
+        );
+        assert_eq!(
+            Polyline::new(&[[1.0, 1.0], [1.0, 1.0]]).unwrap_err(),
+            Error::Degenerate
+        );
+        assert_eq!(
+            Polyline::new(&[[0.0, 0.0], [f64::INFINITY, 0.0]]).unwrap_err(),
+            Error::NotFinite
+        );
+
+        let path = Polyline::new(&[[0.0, 1.0], [0.0, 0.0], [0.0, 0.0], [2.0, 0.0]]).unwrap();
+        assert_eq!(path.length(), 3.0);
+        let mut rng = crate::test::rng(278);
+        let n = 9000;
+        let mut horizontal = 0;
+        for _ in 0..n {
+            let [x, y] = path.sample(&mut rng);
+            assert!(
+                (x == 0.0 && (0.0..=1.0).contains(&y)) || (y == 0.0 && (0.0..=2.0).contains(&x))
+            );
+            if y == 0.0 {
+                horizontal += 1;
+            }
+        }
+        assert!((horizontal as f64 - 6000.0).abs() < 200.0, "{}", horizontal);
+    }
+
+    #[test]
+    fn value_stability() {
+        let triangle = Triangle::new([0.0, 0.0], [4.0, 0.0], [0.0, 2.0]).unwrap();
+        let mut rng = crate::test::rng(279);
+        assert_eq!(
+            triangle.sample(&mut rng),
+            [2.048897802579482, 0.5908393018370083]
+        );
+        #[cfg(feature = "alloc")]
+        {
+            let polygon =
+                ConvexPolygon::new(&[[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]]).unwrap();
+            assert_eq!(
+                polygon.sample(&mut rng),
+                [0.12326092608862474, 0.5558111868884751]
+            );
+            let path = Polyline::new(&[[0.0, 0.0], [1.0, 0.0], [1.0, 3.0]]).unwrap();
+            assert_eq!(path.sample(&mut rng), [1.0, 2.7428281064047715]);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Counting the outcomes of many draws from a weighted index.
+
+use crate::{Binomial, Distribution, WeightedIndex};
+use alloc::vec::Vec;
+use core::ops::SubAssign;
+use rand::distributions::uniform::SampleUniform;
+use rand::Rng;
+
+/// Sample how many times each index is drawn in many independent draws.
+///
+/// This is an extension trait for [`WeightedIndex`], since sampling
+/// efficiently requires the [`Binomial`] distribution.
+///
+/// The result of `sample_counts(rng, n_draws)` is distributed as if
+/// `n_draws` samples were taken from the distribution and the occurrences of
+/// each index counted, i.e. it follows a multinomial distribution. Instead
+/// of `n_draws` binary searches, the count of each index is sampled from a
+/// binomial distribution conditional on the counts of the preceding
+/// indices. The cost is thus `O(N)` for `N` weights, independently of
+/// `n_draws`, making this far faster when `n_draws` is large.
+///
+/// Note that the result is not the same as counting the values of
+/// individual samples with the same RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{SampleCounts, WeightedIndex};
+///
+/// let dist = WeightedIndex::new([1.0, 2.0, 7.0]).unwrap();
+/// let counts = dist.sample_counts(&mut rand::thread_rng(), 1_000_000);
+/// assert_eq!(counts.iter().sum::<u64>(), 1_000_000);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub trait SampleCounts {
+    /// Sample the number of times each index is drawn in `n_draws` draws.
+    ///
+    /// Returns a vector with one count per index, summing to `n_draws`.
+    fn sample_counts<R: Rng + ?Sized>(&self, rng: &mut R, n_draws: u64) -> Vec<u64>;
+}
+
+impl<X> SampleCounts for WeightedIndex<X>
+where X: SampleUniform + PartialOrd + for<'a> SubAssign<&'a X> + Clone + Into<f64>
+{
+    fn sample_counts<R: Rng + ?Sized>(&self, rng: &mut R, n_draws: u64) -> Vec<u64> {
+        let weights: Vec<f64> = self.weights().map(Into::into).collect();
+
+        // remaining_weight[i] is the total weight of indices `i..`
+        let mut remaining_weight = Vec::with_capacity(weights.len() + 1);
+        remaining_weight.push(0.0);
+        for w in weights.iter().rev() {
+            remaining_weight.push(w + remaining_weight.last().unwrap());
+        }
+        remaining_weight.reverse();
+
+        let mut counts = Vec::with_capacity(weights.len());
+        let mut remaining = n_draws;
+        for (i, &w) in weights.iter().enumerate() {
+            let count = if remaining == 0 || w == 0.0 {
+                0
+            } else if remaining_weight[i + 1] == 0.0 {
+                // All further weights are zero
+                remaining
+            } else {
+                let p = (w / remaining_weight[i]).min(1.0);
+                Binomial::new(remaining, p).unwrap().sample(rng)
+            };
+            counts.push(count);
+            remaining -= count;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sample_counts() {
+        let mut rng = crate::test::rng(236);
+        let dist = WeightedIndex::new([1u32, 0, 3, 6, 0]).unwrap();
+        for &n in &[0, 1, 10, 1_000_000, u64::MAX] {
+            let counts = dist.sample_counts(&mut rng, n);
+            assert_eq!(counts.len(), 5);
+            assert_eq!(counts.iter().sum::<u64>(), n);
+            assert_eq!((counts[1], counts[4]), (0, 0));
+        }
+
+        let counts = dist.sample_counts(&mut rng, 1_000_000);
+        for (&c, expected) in counts.iter().zip([100_000i64, 0, 300_000, 600_000, 0]) {
+            assert!((c as i64 - expected).abs() < 3000, "{:?}", counts);
+        }
+
+        let dist = WeightedIndex::new([0.0, 2.5]).unwrap();
+        assert_eq!(dist.sample_counts(&mut rng, 7), [0, 7]);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(236);
+        let dist = WeightedIndex::new([1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(dist.sample_counts(&mut rng, 100), [18, 28, 54]);
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sparse random vectors and matrices.
+
+use crate::{Distribution, GeoError, Geometric};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// A sampler of sparse random vectors and matrices.
+///
+/// Each entry of a vector (or matrix) is independently non-zero with
+/// probability `density`, in which case its value is sampled from the
+/// distribution `D`. Only the non-zero entries are generated, as pairs of
+/// their index and value: the gaps between them are sampled from the
+/// [`Geometric`] distribution (as by [`BernoulliSkip`]), thus sampling takes
+/// `O(n density)` expected time for `n` entries, however large `n` is.
+///
+/// Use [`Sparse::iter`] to iterate over the non-zero entries of a vector of
+/// any length, or (with the `alloc` feature) [`Sparse::sample_vector`] and
+/// [`Sparse::sample_matrix`] to collect them.
+///
+/// Note that values sampled from `D` are kept even if they happen to be
+/// zero.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Normal, Sparse};
+///
+/// // A vector of length 10^9 with about 100 non-zero entries
+/// let sparse = Sparse::new(1e-7, Normal::new(0.0, 1.0).unwrap()).unwrap();
+/// let mut norm2 = 0.0;
+/// for (i, x) in sparse.iter(rand::thread_rng(), 1_000_000_000) {
+///     assert!(i < 1_000_000_000);
+///     norm2 += x * x;
+/// }
+/// # let _ = norm2;
+/// ```
+///
+/// [`BernoulliSkip`]: crate::BernoulliSkip
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sparse<D> {
+    gap: Geometric,
+    values: D,
+}
+
+impl<D> Sparse<D> {
+    /// Construct, with entries non-zero with probability `density` and values
+    /// sampled from `values`.
+    ///
+    /// Fails if `density` is not in `[0, 1]`.
+    pub fn new(density: f64, values: D) -> Result<Self, GeoError> {
+        Ok(Sparse {
+            gap: Geometric::new(density)?,
+            values,
+        })
+    }
+
+    /// Iterate over the non-zero entries of a vector of length `len`.
+    ///
+    /// The iterator yields pairs `(index, value)`, in increasing order of
+    /// `index`.
+    pub fn iter<T, R: Rng>(&self, rng: R, len: u64) -> SparseIter<'_, D, R, T>
+    where D: Distribution<T> {
+        SparseIter {
+            sparse: self,
+            rng,
+            next: 0,
+            end: len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sample the non-zero entries of a vector of length `len`.
+    ///
+    /// Entries are returned as pairs `(index, value)`, in increasing order of
+    /// `index`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn sample_vector<T, R>(&self, rng: &mut R, len: usize) -> Vec<(usize, T)>
+    where
+        D: Distribution<T>,
+        R: Rng + ?Sized,
+    {
+        self.iter(rng, len as u64)
+            .map(|(i, x)| (i as usize, x))
+            .collect()
+    }
+
+    /// Sample the non-zero entries of a `rows × cols` matrix.
+    ///
+    /// Entries are returned as triples `(row, col, value)`, in row-major
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_distr::{Normal, Sparse};
+    ///
+    /// // A 1000 × 10^6 matrix with about 10 non-zero entries per row
+    /// let sparse = Sparse::new(1e-5, Normal::new(0.0, 1.0).unwrap()).unwrap();
+    /// let mut rng = rand::thread_rng();
+    /// let entries: Vec<(usize, usize, f64)> = sparse.sample_matrix(&mut rng, 1000, 1_000_000);
+    /// assert!(entries.iter().all(|&(i, j, _)| i < 1000 && j < 1_000_000));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of entries `rows * cols` exceeds `u64::MAX`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn sample_matrix<T, R>(
+        &self, rng: &mut R, rows: usize, cols: usize,
+    ) -> Vec<(usize, usize, T)>
+    where
+        D: Distribution<T>,
+        R: Rng + ?Sized,
+    {
+        let len = (rows as u64)
+            .checked_mul(cols as u64)
+            .expect("Sparse::sample_matrix: too many entries");
+        self.iter(rng, len)
+            .map(|(i, x)| ((i / cols as u64) as usize, (i % cols as u64) as usize, x))
+            .collect()
+    }
+}
+
+/// An iterator over the non-zero entries of a sparse random vector.
+///
+/// This struct is created by [`Sparse::iter`].
+#[derive(Debug)]
+pub struct SparseIter<'a, D, R, T> {
+    sparse: &'a Sparse<D>,
+    rng: R,
+    // The first entry not yet decided
+    next: u64,
+    end: u64,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, D: Distribution<T>, R: Rng, T> Iterator for SparseIter<'a, D, R, T> {
+    type Item = (u64, T);
+
+    fn next(&mut self) -> Option<(u64, T)> {
+        if self.next >= self.end {
+            return None;
+        }
+        // The number of zeros before the next non-zero entry
+        let zeros = self.sparse.gap.sample(&mut self.rng);
+        match self.next.checked_add(zeros) {
+            Some(index) if index < self.end => {
+                self.next = index + 1;
+                Some((index, self.sparse.values.sample(&mut self.rng)))
+            }
+            _ => {
+                self.next = self.end;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, usize::try_from(self.end - self.next).ok())
+    }
+}
+
+impl<'a, D: Distribution<T>, R: Rng, T> FusedIterator for SparseIter<'a, D, R, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Uniform;
+
+    #[test]
+    fn test_sparse() {
+        let mut rng = crate::test::rng(274);
+        let values = Uniform::new(1u32, 10).unwrap();
+        assert!(Sparse::new(-0.5, values).is_err());
+        assert_eq!(Sparse::new(0.0, values).unwrap().iter(&mut rng, u64::MAX).next(), None);
+        let dense = Sparse::new(1.0, values).unwrap();
+        assert!(dense.iter(&mut rng, 5).map(|(i, _)| i).eq(0..5));
+
+        let sparse = Sparse::new(0.01, values).unwrap();
+        let mut count = 0;
+        let mut prev = None;
+        for (i, x) in sparse.iter(&mut rng, 1_000_000) {
+            assert!(i < 1_000_000 && prev < Some(i));
+            assert!((1..10).contains(&x));
+            prev = Some(i);
+            count += 1;
+        }
+        // Expect 10_000, with standard deviation about 100
+        assert!((9500..10500).contains(&count), "{}", count);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sparse_matrix() {
+        let mut rng = crate::test::rng(275);
+        let sparse = Sparse::new(0.5, Uniform::new(0.0, 1.0).unwrap()).unwrap();
+        let v: Vec<(usize, f64)> = sparse.sample_vector(&mut rng, 1000);
+        assert!(v.windows(2).all(|w| w[0].0 < w[1].0) && v.last().unwrap().0 < 1000);
+
+        let dense = Sparse::new(1.0, Uniform::new(0u8, 1).unwrap()).unwrap();
+        let m = dense.sample_matrix(&mut rng, 2, 3);
+        assert_eq!(m, [(0, 0, 0), (0, 1, 0), (0, 2, 0), (1, 0, 0), (1, 1, 0), (1, 2, 0)]);
+        assert!(dense.sample_matrix(&mut rng, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn value_stability() {
+        let sparse = Sparse::new(0.2, Uniform::new(0u32, 100).unwrap()).unwrap();
+        let mut buf = [(0, 0); 4];
+        for (x, entry) in buf.iter_mut().zip(sparse.iter(crate::test::rng(276), 100)) {
+            *x = entry;
+        }
+        assert_eq!(buf, [(7, 34), (12, 76), (15, 67), (33, 76)]);
+    }
+}
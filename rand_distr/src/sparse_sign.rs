@@ -0,0 +1,195 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The sparse sign distribution of random projections.
+
+use crate::{Distribution, Geometric};
+use core::convert::TryFrom;
+use core::fmt;
+#[allow(unused_imports)]
+use num_traits::Float;
+use rand::Rng;
+
+/// The sparse sign distribution, as used by sparse random projections.
+///
+/// Each sample is `+1` or `-1` with probability `density / 2` each, and `0`
+/// with probability `1 - density`. With `density = 1/3` this is the
+/// distribution of Achlioptas (see [`SparseSign::achlioptas`]); smaller
+/// densities such as `1 / sqrt(d)` for `d`-dimensional inputs give the
+/// "very sparse" projections of Li, Hastie and Church. The entries of a
+/// projection matrix are usually multiplied by [`SparseSign::scale`], such
+/// that they have unit variance.
+///
+/// To generate many entries, e.g. a row of a projection matrix, use
+/// [`SparseSign::fill`]: rather than sampling each entry, it samples the
+/// gaps between non-zero entries from the [`Geometric`] distribution and
+/// takes the signs from the bits of a single `u64` per 64 non-zero entries,
+/// thus using `O(n density)` random numbers to fill `n` entries.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::SparseSign;
+///
+/// let distr = SparseSign::achlioptas();
+/// let mut row = [0i8; 1000];
+/// distr.fill(&mut row, &mut rand::thread_rng());
+/// assert!(row.iter().all(|&x| x == -1 || x == 0 || x == 1));
+/// let scale = distr.scale(); // sqrt(3)
+/// let projected: f64 = row.iter().map(|&x| f64::from(x) * scale).sum();
+/// # let _ = projected;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseSign {
+    density: f64,
+    gap: Geometric,
+}
+
+/// Error type returned from [`SparseSign::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `density <= 0` or `density > 1` or `nan`.
+    DensityInvalid,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::DensityInvalid => "density is not in (0, 1] in sparse sign distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl SparseSign {
+    /// Construct with the given probability of a non-zero sample.
+    pub fn new(density: f64) -> Result<SparseSign, Error> {
+        if !(density > 0.0 && density <= 1.0) {
+            return Err(Error::DensityInvalid);
+        }
+        let gap = Geometric::new(density).map_err(|_| Error::DensityInvalid)?;
+        Ok(SparseSign { density, gap })
+    }
+
+    /// Construct the distribution of Achlioptas, with density `1/3`.
+    pub fn achlioptas() -> SparseSign {
+        SparseSign::new(1.0 / 3.0).unwrap()
+    }
+
+    /// Returns the probability of a non-zero sample.
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    /// Returns `1 / sqrt(density)`, the factor scaling samples to unit
+    /// variance.
+    pub fn scale(&self) -> f64 {
+        1.0 / self.density.sqrt()
+    }
+
+    /// Fill `dest` with independent samples.
+    ///
+    /// This takes `O(dest.len() * density)` random numbers, plus `O(1)`
+    /// time per entry to zero `dest`.
+    pub fn fill<R: Rng + ?Sized>(&self, dest: &mut [i8], rng: &mut R) {
+        dest.fill(0);
+        let mut signs = 0u64;
+        let mut n_signs = 0;
+        let mut next = 0;
+        loop {
+            // The number of zeros before the next non-zero entry
+            let zeros = self.gap.sample(rng);
+            let index = match usize::try_from(zeros).ok().and_then(|z| z.checked_add(next)) {
+                Some(index) if index < dest.len() => index,
+                _ => return,
+            };
+            if n_signs == 0 {
+                signs = rng.next_u64();
+                n_signs = 64;
+            }
+            dest[index] = if signs & 1 == 0 { 1 } else { -1 };
+            signs >>= 1;
+            n_signs -= 1;
+            next = index + 1;
+        }
+    }
+}
+
+impl Distribution<i8> for SparseSign {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i8 {
+        let x: f64 = rng.gen();
+        if x < self.density / 2.0 {
+            1
+        } else if x < self.density {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sparse_sign_invalid() {
+        for &d in &[0.0, -0.5, 1.5, f64::NAN] {
+            assert_eq!(SparseSign::new(d).unwrap_err(), Error::DensityInvalid);
+        }
+        assert!(SparseSign::new(1.0).is_ok());
+    }
+
+    #[test]
+    fn test_sparse_sign() {
+        let distr = SparseSign::achlioptas();
+        assert!((distr.scale() - 3.0f64.sqrt()).abs() < 1e-15);
+        let mut rng = crate::test::rng(273);
+
+        let n = 30000;
+        let mut counts = [0u32; 3];
+        for _ in 0..n {
+            counts[(distr.sample(&mut rng) + 1) as usize] += 1;
+        }
+        let mut buf = [7i8; 30000];
+        distr.fill(&mut buf, &mut rng);
+        let mut fill_counts = [0u32; 3];
+        for &x in buf.iter() {
+            fill_counts[(x + 1) as usize] += 1;
+        }
+        for c in [counts, fill_counts].iter() {
+            // Expect 5000 each of +-1, with standard deviation about 65
+            assert!((c[0] as f64 - 5000.0).abs() < 300.0, "{:?}", c);
+            assert!((c[2] as f64 - 5000.0).abs() < 300.0, "{:?}", c);
+        }
+
+        // With density 1 all entries are non-zero
+        let mut buf = [0i8; 200];
+        SparseSign::new(1.0).unwrap().fill(&mut buf, &mut rng);
+        assert!(buf.iter().all(|&x| x != 0));
+        SparseSign::new(1.0).unwrap().fill(&mut [], &mut rng);
+    }
+
+    #[test]
+    fn value_stability() {
+        let distr = SparseSign::new(0.25).unwrap();
+        let mut rng = crate::test::rng(274);
+        let mut buf = [0i8; 12];
+        distr.fill(&mut buf, &mut rng);
+        assert_eq!(buf, [-1, 0, 0, 0, -1, 0, 0, 0, 0, 0, 0, 1]);
+        let mut samples = [0i8; 6];
+        for x in samples.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(samples, [0, 0, 0, 0, -1, 0]);
+    }
+}
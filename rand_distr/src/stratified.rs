@@ -0,0 +1,115 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Stratified sampling of the unit interval.
+
+use crate::{Distribution, Standard};
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// The largest `f64` less than 1
+const BELOW_ONE: f64 = 1.0 - f64::EPSILON / 2.0;
+
+/// Stratified samples of the unit interval `[0, 1)`.
+///
+/// Each sample is a `Vec` of `n` points, one in each of the strata
+/// `[i / n, (i + 1) / n)`, jittered uniformly within its stratum. Such
+/// points cover the interval more evenly than `n` independent uniform
+/// samples while remaining random, which reduces the variance of Monte Carlo
+/// integration and avoids clumping in sample patterns (e.g. of a renderer).
+///
+/// By default the points are in increasing order. Use
+/// [`Stratified::shuffled`] to yield them in random order instead, e.g.
+/// when pairing the points with those of another dimension.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Stratified};
+///
+/// // Estimate the integral of x^2 over [0, 1)
+/// let points = Stratified::new(100).sample(&mut rand::thread_rng());
+/// let estimate = points.iter().map(|x| x * x).sum::<f64>() / 100.0;
+/// assert!((estimate - 1.0 / 3.0).abs() < 0.01);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stratified {
+    n: usize,
+    shuffled: bool,
+}
+
+impl Stratified {
+    /// Construct, sampling `n` points in increasing order.
+    pub fn new(n: usize) -> Stratified {
+        Stratified { n, shuffled: false }
+    }
+
+    /// Yield the points in random order.
+    pub fn shuffled(self) -> Stratified {
+        Stratified {
+            shuffled: true,
+            ..self
+        }
+    }
+
+    /// Returns the number of points (and strata).
+    pub fn n(&self) -> usize {
+        self.n
+    }
+}
+
+impl Distribution<Vec<f64>> for Stratified {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let scale = 1.0 / self.n as f64;
+        let mut points: Vec<f64> = (0..self.n)
+            .map(|i| {
+                let u: f64 = rng.sample(Standard);
+                // Rounding could otherwise yield 1 in the last stratum
+                ((i as f64 + u) * scale).min(BELOW_ONE)
+            })
+            .collect();
+        if self.shuffled {
+            points.shuffle(rng);
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stratified() {
+        let mut rng = crate::test::rng(254);
+        for &n in &[0, 1, 2, 7, 1000] {
+            let points = Stratified::new(n).sample(&mut rng);
+            assert_eq!(points.len(), n);
+            for (i, x) in points.iter().enumerate() {
+                assert_eq!((x * n as f64) as usize, i);
+            }
+        }
+
+        let distr = Stratified::new(50).shuffled();
+        assert_eq!(distr.n(), 50);
+        let points = distr.sample(&mut rng);
+        let mut strata: Vec<usize> = points.iter().map(|x| (x * 50.0) as usize).collect();
+        assert!(strata.windows(2).any(|w| w[0] > w[1]));
+        strata.sort_unstable();
+        assert!(strata.into_iter().eq(0..50));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(255);
+        assert_eq!(Stratified::new(3).sample(&mut rng), [0.2806308823521887, 0.43059630718408626, 0.6683029955892641]);
+        assert_eq!(Stratified::new(3).shuffled().sample(&mut rng), [0.691895744473897, 0.6666627317417096, 0.1677470608869758]);
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniformly distributed rotations.
+#![cfg(feature = "nalgebra")]
+use crate::{Distribution, StandardNormal};
+use nalgebra::{Quaternion, RealField, Rotation3, UnitQuaternion};
+use rand::Rng;
+
+/// Samples rotations in three dimensions uniformly, i.e. according to the
+/// Haar measure on SO(3).
+///
+/// Implemented by normalising a vector of four independent standard normal
+/// samples, which yields a unit quaternion uniformly distributed on the
+/// 3-sphere. Samples may be produced as [`UnitQuaternion`] or [`Rotation3`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{UnitRotation, Distribution};
+/// use nalgebra::{Rotation3, UnitQuaternion, Vector3};
+///
+/// let mut rng = rand::thread_rng();
+/// let q: UnitQuaternion<f64> = UnitRotation.sample(&mut rng);
+/// let r: Rotation3<f64> = UnitRotation.sample(&mut rng);
+/// println!("{}", q * r * Vector3::x());
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "nalgebra")))]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitRotation;
+
+impl<F> Distribution<UnitQuaternion<F>> for UnitRotation
+where
+    F: RealField + Copy,
+    StandardNormal: Distribution<F>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UnitQuaternion<F> {
+        loop {
+            let q = Quaternion::new(
+                StandardNormal.sample(rng),
+                StandardNormal.sample(rng),
+                StandardNormal.sample(rng),
+                StandardNormal.sample(rng),
+            );
+            // Reject the (astronomically unlikely) near-zero vector, whose
+            // direction is dominated by rounding error
+            if let Some(u) = UnitQuaternion::try_new(q, F::default_epsilon()) {
+                return u;
+            }
+        }
+    }
+}
+
+impl<F> Distribution<Rotation3<F>> for UnitRotation
+where
+    F: RealField + Copy,
+    StandardNormal: Distribution<F>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rotation3<F> {
+        let q: UnitQuaternion<F> = self.sample(rng);
+        q.to_rotation_matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn test_unit_rotation() {
+        let mut rng = crate::test::rng(208);
+        // The rotated x axis is uniformly distributed on the sphere; check
+        // that its mean is close to zero
+        let mut sum = Vector3::zeros();
+        for _ in 0..1000 {
+            let q: UnitQuaternion<f64> = UnitRotation.sample(&mut rng);
+            assert!((q.norm() - 1.0).abs() < 1e-12);
+            sum += q * Vector3::x();
+        }
+        assert!(sum.norm() / 1000.0 < 0.1);
+
+        let r: Rotation3<f32> = UnitRotation.sample(&mut rng);
+        let v = r * Vector3::new(1.0f32, 2.0, 3.0);
+        assert!((v.norm() - 14f32.sqrt()).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use num_traits::Float;
+use crate::{Distribution, Exp1};
+use rand::Rng;
+
+/// Samples uniformly from the standard simplex in `N` dimensions.
+///
+/// The standard `(N - 1)`-simplex is the set of points with `N`
+/// non-negative coordinates summing to 1, thus each sample is a random
+/// probability vector. This is equivalent to the [`Dirichlet`] distribution
+/// with all parameters equal to 1, but faster: samples are normalized
+/// [`Exp1`] variates rather than [`Gamma`] variates.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{UnitSimplex, Distribution};
+///
+/// let p: [f64; 4] = UnitSimplex.sample(&mut rand::thread_rng());
+/// assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// ```
+///
+/// [`Dirichlet`]: crate::Dirichlet
+/// [`Gamma`]: crate::Gamma
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitSimplex;
+
+impl<F, const N: usize> Distribution<[F; N]> for UnitSimplex
+where
+    F: Float,
+    Exp1: Distribution<F>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [F; N] {
+        let mut x = [F::zero(); N];
+        let mut sum = F::zero();
+        for xi in x.iter_mut() {
+            *xi = Exp1.sample(rng);
+            sum = sum + *xi;
+        }
+        for xi in x.iter_mut() {
+            *xi = *xi / sum;
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnitSimplex;
+    use crate::Distribution;
+
+    #[test]
+    fn sum() {
+        let mut rng = crate::test::rng(249);
+        for _ in 0..1000 {
+            let x: [f64; 5] = UnitSimplex.sample(&mut rng);
+            assert!(x.iter().all(|&xi| (0.0..=1.0).contains(&xi)));
+            assert_almost_eq!(x.iter().sum::<f64>(), 1.0, 1e-14);
+        }
+        let x: [f32; 1] = UnitSimplex.sample(&mut rng);
+        assert_eq!(x, [1.0]);
+        let x: [f64; 0] = UnitSimplex.sample(&mut rng);
+        assert_eq!(x, []);
+    }
+
+    #[test]
+    fn uniformity() {
+        // The first coordinate of a uniform point on the 2-simplex has
+        // density 2 (1 - x), thus P(x < 1/2) = 3/4
+        let mut rng = crate::test::rng(250);
+        let n = 10_000;
+        let below = (0..n)
+            .filter(|_| {
+                let x: [f64; 3] = UnitSimplex.sample(&mut rng);
+                x[0] < 0.5
+            })
+            .count();
+        assert!((7300..7700).contains(&below), "{}", below);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(251);
+        let x: [f64; 3] = UnitSimplex.sample(&mut rng);
+        assert_eq!(x, [0.02696764920659196, 0.21639870475449458, 0.7566336460389135]);
+    }
+}
@@ -55,6 +55,46 @@ pub(crate) fn log_gamma<F: Float>(x: F) -> F {
     log + (F::from(2.5066282746310005).unwrap() * a / x).ln()
 }
 
+/// Calculates the cumulative distribution function of the standard normal
+/// distribution, `Φ(x)`.
+///
+/// This uses the algorithm of Hart (1968) as presented by West (2005),
+/// *Better approximations to cumulative normal functions*, which is accurate
+/// to double precision.
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    let x_abs = x.abs();
+    let c = if x_abs > 37.0 {
+        0.0
+    } else {
+        let e = (-x_abs * x_abs / 2.0).exp();
+        if x_abs < 7.07106781186547 {
+            let mut b = 3.52624965998911e-02 * x_abs + 0.700383064443688;
+            b = b * x_abs + 6.37396220353165;
+            b = b * x_abs + 33.912866078383;
+            b = b * x_abs + 112.079291497871;
+            b = b * x_abs + 221.213596169931;
+            b = b * x_abs + 220.206867912376;
+            let numerator = e * b;
+            let mut b = 8.83883476483184e-02 * x_abs + 1.75566716318264;
+            b = b * x_abs + 16.064177579207;
+            b = b * x_abs + 86.7807322029461;
+            b = b * x_abs + 296.564248779674;
+            b = b * x_abs + 637.333633378831;
+            b = b * x_abs + 793.826512519948;
+            b = b * x_abs + 440.413735824752;
+            numerator / b
+        } else {
+            let mut b = x_abs + 0.65;
+            b = x_abs + 4.0 / b;
+            b = x_abs + 3.0 / b;
+            b = x_abs + 2.0 / b;
+            b = x_abs + 1.0 / b;
+            e / b / 2.506628274631
+        }
+    };
+    if x > 0.0 { 1.0 - c } else { c }
+}
+
 /// Sample a random number using the Ziggurat method (specifically the
 /// ZIGNOR variant from Doornik 2005). Most of the arguments are
 /// directly from the paper:
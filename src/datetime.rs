@@ -0,0 +1,350 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random calendar dates and times of day
+//!
+//! This module provides:
+//!
+//! *   [`DayOfYear`] to sample a day uniformly within a given year, as a
+//!     [`Date`], with correct handling of leap years
+//! *   [`Weekday`], sampled uniformly via [`Standard`]
+//! *   [`TimeOfDay`] to sample a time of day following a given distribution
+//!     of hours
+//!
+//! Dates use the proleptic Gregorian calendar. Time zones and leap seconds
+//! are not supported; use a dedicated date and time library for these.
+//!
+//! ```
+//! use rand::datetime::{DayOfYear, TimeOfDay, Weekday};
+//! use rand::Rng;
+//!
+//! let mut rng = rand::thread_rng();
+//! let date = rng.sample(DayOfYear::new(2024));
+//! let time = rng.sample(TimeOfDay::uniform());
+//! let weekday: Weekday = rng.gen();
+//! println!("{} at {}s, a {:?}", date, time.as_secs(), weekday);
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::distributions::{Distribution, Standard, Uniform};
+use crate::Rng;
+
+// Number of days before the first of each month in a non-leap year
+const CUMULATIVE_DAYS: [u16; 13] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365];
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_year(year: i32) -> u16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+// Number of days before the first of `month` (in `1..=12`)
+fn days_before_month(year: i32, month: u8) -> u16 {
+    let leap_day = (month > 2 && is_leap_year(year)) as u16;
+    CUMULATIVE_DAYS[month as usize - 1] + leap_day
+}
+
+/// A day of the week
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    /// Monday
+    Monday,
+    /// Tuesday
+    Tuesday,
+    /// Wednesday
+    Wednesday,
+    /// Thursday
+    Thursday,
+    /// Friday
+    Friday,
+    /// Saturday
+    Saturday,
+    /// Sunday
+    Sunday,
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Sample a day of the week uniformly.
+impl Distribution<Weekday> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Weekday {
+        WEEKDAYS[rng.gen_range(0..7u32) as usize]
+    }
+}
+
+/// A date in the proleptic Gregorian calendar
+///
+/// This is a minimal type for sampled dates. Convert to the type of a date
+/// and time library for further use, e.g. via the [`Date::year`],
+/// [`Date::month`] and [`Date::day`] accessors. The [`fmt::Display`]
+/// implementation uses the ISO 8601 format, `YYYY-MM-DD`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Construct a date, or return `None` if the `month` (in `1..=12`) or
+    /// `day` (starting from 1) is invalid.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 {
+            return None;
+        }
+        let days_in_month = if month == 12 {
+            31
+        } else {
+            days_before_month(year, month + 1) - days_before_month(year, month)
+        };
+        if day as u16 > days_in_month {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    /// Construct the date of day number `ordinal` (starting from 1) of
+    /// `year`, or return `None` if out of range.
+    pub fn from_ordinal(year: i32, ordinal: u16) -> Option<Date> {
+        if ordinal == 0 || ordinal > days_in_year(year) {
+            return None;
+        }
+        // There are at most 12 months, so a linear search suffices
+        let month = (1..=12u8)
+            .rev()
+            .find(|&m| days_before_month(year, m) < ordinal)
+            .unwrap();
+        let day = (ordinal - days_before_month(year, month)) as u8;
+        Some(Date { year, month, day })
+    }
+
+    /// Get the year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the month, in `1..=12`
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month, starting from 1
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Get the day of the year, starting from 1
+    pub fn ordinal(&self) -> u16 {
+        days_before_month(self.year, self.month) + self.day as u16
+    }
+
+    /// Get the day of the week
+    pub fn weekday(&self) -> Weekday {
+        // Days since 1970-01-01 (a Thursday), after Howard Hinnant's
+        // `days_from_civil` algorithm, with years starting in March
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+        WEEKDAYS[(days + 3).rem_euclid(7) as usize]
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Sample a day uniformly within a year.
+///
+/// Each of the 365 or 366 days of the year is equally likely; in leap years
+/// this includes the 29th of February.
+///
+/// # Example
+///
+/// ```
+/// use rand::datetime::DayOfYear;
+/// use rand::Rng;
+///
+/// let date = rand::thread_rng().sample(DayOfYear::new(2024));
+/// assert_eq!(date.year(), 2024);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DayOfYear {
+    year: i32,
+    ordinal: Uniform<u16>,
+}
+
+impl DayOfYear {
+    /// Construct a sampler over the days of `year`.
+    pub fn new(year: i32) -> DayOfYear {
+        DayOfYear {
+            year,
+            ordinal: Uniform::new_inclusive(1, days_in_year(year)).unwrap(),
+        }
+    }
+
+    /// Get the year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+}
+
+impl Distribution<Date> for DayOfYear {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Date {
+        Date::from_ordinal(self.year, self.ordinal.sample(rng)).unwrap()
+    }
+}
+
+/// Sample a time of day, as the [`Duration`] since midnight.
+///
+/// The time of day follows the distribution `hours` of the number of hours
+/// since midnight, wrapped into `[0, 24)`. For example, a
+/// `Normal::new(14.0, 2.0)` distribution (from the `rand_distr` crate) models
+/// events peaking in the afternoon, with events near midnight wrapping
+/// around to the previous or next day. Use [`TimeOfDay::uniform`] for a
+/// uniformly distributed time.
+///
+/// # Panics
+///
+/// Sampling panics if `hours` yields a non-finite value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeOfDay<D> {
+    hours: D,
+}
+
+impl<D: Distribution<f64>> TimeOfDay<D> {
+    /// Construct, with `hours` the distribution of hours since midnight.
+    pub fn new(hours: D) -> TimeOfDay<D> {
+        TimeOfDay { hours }
+    }
+}
+
+impl TimeOfDay<Uniform<f64>> {
+    /// Construct a sampler of uniformly distributed times of day.
+    pub fn uniform() -> TimeOfDay<Uniform<f64>> {
+        TimeOfDay::new(Uniform::new(0.0, 24.0).unwrap())
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<Duration> for TimeOfDay<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let hours = self.hours.sample(rng);
+        assert!(hours.is_finite(), "TimeOfDay: hours must be finite");
+        let mut hours = hours.rem_euclid(24.0);
+        // Tiny negative values may round to 24
+        if hours >= 24.0 {
+            hours = 0.0;
+        }
+        Duration::from_secs_f64(hours * 3600.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_date() {
+        assert!(is_leap_year(2000) && is_leap_year(2024) && is_leap_year(-4));
+        assert!(!is_leap_year(1900) && !is_leap_year(2023));
+
+        assert_eq!(Date::new(2024, 2, 29).map(|d| d.ordinal()), Some(60));
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(Date::new(2023, 12, 31).map(|d| d.ordinal()), Some(365));
+        assert_eq!(Date::new(2023, 12, 32), None);
+        assert_eq!(Date::new(2023, 13, 1), None);
+        assert_eq!(Date::new(2023, 4, 0), None);
+        assert_eq!(Date::from_ordinal(2024, 366), Date::new(2024, 12, 31));
+        assert_eq!(Date::from_ordinal(2023, 366), None);
+        assert_eq!(Date::from_ordinal(2023, 32), Date::new(2023, 2, 1));
+
+        for &year in &[1900, 2000, 2023, 2024] {
+            for ordinal in 1..=days_in_year(year) {
+                let date = Date::from_ordinal(year, ordinal).unwrap();
+                assert_eq!(Date::new(year, date.month(), date.day()), Some(date));
+                assert_eq!(date.ordinal(), ordinal);
+            }
+        }
+
+        let weekday = |y, m, d| Date::new(y, m, d).unwrap().weekday();
+        assert_eq!(weekday(1970, 1, 1), Weekday::Thursday);
+        assert_eq!(weekday(2000, 1, 1), Weekday::Saturday);
+        assert_eq!(weekday(2000, 2, 29), Weekday::Tuesday);
+        assert_eq!(weekday(2023, 1, 1), Weekday::Sunday);
+        assert_eq!(weekday(1600, 3, 1), Weekday::Wednesday);
+        assert_eq!(Date::new(-1, 3, 7).unwrap().to_string(), "-001-03-07");
+        assert_eq!(Date::new(2024, 3, 7).unwrap().to_string(), "2024-03-07");
+    }
+
+    #[test]
+    fn test_day_of_year() {
+        let mut rng = crate::test::rng(247);
+        let distr = DayOfYear::new(2024);
+        let mut leap_days = 0;
+        for _ in 0..3660 {
+            let date = distr.sample(&mut rng);
+            assert_eq!(date.year(), 2024);
+            leap_days += (date.month() == 2 && date.day() == 29) as u32;
+        }
+        // Expect 10
+        assert!((1..30).contains(&leap_days), "{}", leap_days);
+    }
+
+    #[test]
+    fn test_weekday() {
+        let mut rng = crate::test::rng(248);
+        let mut counts = [0; 7];
+        for _ in 0..7000 {
+            counts[rng.gen::<Weekday>() as usize] += 1;
+        }
+        // Expect 1000 of each
+        assert!(counts.iter().all(|c| (900..1100).contains(c)), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let mut rng = crate::test::rng(249);
+        let day = Duration::from_secs(24 * 3600);
+        let distr = TimeOfDay::new(Uniform::new(-48.0, 48.0).unwrap());
+        assert!((0..100).all(|_| distr.sample(&mut rng) < day));
+        let distr = TimeOfDay::new(Uniform::new_inclusive(-1e-20, -1e-20).unwrap());
+        assert_eq!(distr.sample(&mut rng), Duration::ZERO);
+        let distr = TimeOfDay::new(Uniform::new_inclusive(25.5, 25.5).unwrap());
+        assert_eq!(distr.sample(&mut rng), Duration::from_secs(5400));
+        assert!(TimeOfDay::uniform().sample(&mut rng) < day);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(250);
+        let date = DayOfYear::new(2023).sample(&mut rng);
+        let weekday: Weekday = rng.gen();
+        let time = TimeOfDay::uniform().sample(&mut rng);
+        assert_eq!(date.to_string(), "2023-08-11");
+        assert_eq!(weekday, Weekday::Thursday);
+        assert_eq!(time.as_secs(), 27946);
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Antithetic variates: the [`Antithetic`] combinator.
+
+use alloc::vec::Vec;
+
+use crate::distributions::Distribution;
+use crate::{Error, Rng, RngCore};
+
+/// Sample antithetic pairs from a distribution, for variance reduction.
+///
+/// Each sample is a pair `(x, y)` where `x` is sampled from `distr` as usual
+/// and `y` is sampled from `distr` using the complements of the random
+/// numbers used for `x`: where `x` was generated from a uniform variate `u`,
+/// `y` is generated from `1 - u`. Thus `x` and `y` have the same
+/// distribution, but are negatively correlated for distributions which are
+/// monotone in their random input (e.g. those sampled by inversion). The
+/// mean of `f(x)` and `f(y)` is then an estimator of `E[f(X)]` with lower
+/// variance than the mean of two independent samples, for any monotone `f`.
+///
+/// Complements are taken bitwise on the output of the RNG, i.e. each `u32`
+/// or `u64` word `w` is replaced by `!w`. For [`Standard`] floats this maps
+/// `u` to `1 - ε - u`, where `ε` is the precision of the float. If sampling
+/// `y` uses more random numbers than sampling `x` did (e.g. due to
+/// rejection sampling), or uses them differently, further random numbers are
+/// taken directly from the RNG.
+///
+/// Use [`Distribution::sample_iter`] to iterate over pairs.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Antithetic, Distribution, Standard};
+///
+/// // Estimate E[exp(U)] = e - 1 for U uniform in [0, 1)
+/// let n = 1000;
+/// let pairs = Antithetic::new(Standard).sample_iter(rand::thread_rng());
+/// let estimate = pairs
+///     .take(n)
+///     .map(|(u, v): (f64, f64)| (u.exp() + v.exp()) / 2.0)
+///     .sum::<f64>() / n as f64;
+/// assert!((estimate - (1f64.exp() - 1.0)).abs() < 0.01);
+/// ```
+///
+/// [`Standard`]: crate::distributions::Standard
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Antithetic<D> {
+    distr: D,
+}
+
+impl<D> Antithetic<D> {
+    /// Construct, sampling antithetic pairs from `distr`.
+    pub fn new(distr: D) -> Self {
+        Antithetic { distr }
+    }
+
+    /// Get the underlying distribution
+    pub fn inner(&self) -> &D {
+        &self.distr
+    }
+}
+
+impl<D: Distribution<T>, T> Distribution<(T, T)> for Antithetic<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (T, T) {
+        let mut recorder = Recorder {
+            rng: &mut *rng,
+            draws: Vec::new(),
+        };
+        let x = self.distr.sample(&mut recorder);
+        let mut replay = Replay {
+            draws: recorder.draws.into_iter(),
+            rng,
+            diverged: false,
+        };
+        let y = self.distr.sample(&mut replay);
+        (x, y)
+    }
+}
+
+// A random number taken from the RNG while sampling the first of a pair
+#[derive(Debug)]
+enum Draw {
+    U32(u32),
+    U64(u64),
+    Bytes(Vec<u8>),
+}
+
+// Passes through and records all random numbers
+struct Recorder<'a, R: ?Sized> {
+    rng: &'a mut R,
+    draws: Vec<Draw>,
+}
+
+impl<'a, R: RngCore + ?Sized> RngCore for Recorder<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        let x = self.rng.next_u32();
+        self.draws.push(Draw::U32(x));
+        x
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let x = self.rng.next_u64();
+        self.draws.push(Draw::U64(x));
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        self.draws.push(Draw::Bytes(dest.to_vec()));
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)?;
+        self.draws.push(Draw::Bytes(dest.to_vec()));
+        Ok(())
+    }
+}
+
+// Yields the complements of recorded random numbers while they are used in
+// the same way, then falls back to the RNG
+struct Replay<'a, R: ?Sized> {
+    draws: alloc::vec::IntoIter<Draw>,
+    rng: &'a mut R,
+    diverged: bool,
+}
+
+impl<'a, R: ?Sized> Replay<'a, R> {
+    fn next_draw(&mut self) -> Option<Draw> {
+        if self.diverged {
+            return None;
+        }
+        let draw = self.draws.next();
+        self.diverged = draw.is_none();
+        draw
+    }
+}
+
+impl<'a, R: RngCore + ?Sized> RngCore for Replay<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        match self.next_draw() {
+            Some(Draw::U32(x)) => !x,
+            Some(_) => {
+                self.diverged = true;
+                self.rng.next_u32()
+            }
+            None => self.rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.next_draw() {
+            Some(Draw::U64(x)) => !x,
+            Some(_) => {
+                self.diverged = true;
+                self.rng.next_u64()
+            }
+            None => self.rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match self.next_draw() {
+            Some(Draw::Bytes(ref bytes)) if bytes.len() == dest.len() => {
+                for (d, b) in dest.iter_mut().zip(bytes) {
+                    *d = !b;
+                }
+                Ok(())
+            }
+            Some(_) => {
+                self.diverged = true;
+                self.rng.try_fill_bytes(dest)
+            }
+            None => self.rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::{Standard, Uniform};
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_antithetic() {
+        let mut rng = crate::test::rng(252);
+        let (a, b): (u64, u64) = Antithetic::new(Standard).sample(&mut rng);
+        assert_eq!(b, !a);
+        let (a, b): ([u8; 5], [u8; 5]) = Antithetic::new(Standard).sample(&mut rng);
+        for (a, b) in a.iter().zip(&b) {
+            assert_eq!(*b, !a);
+        }
+
+        let distr = Antithetic::new(Uniform::new(0.0f64, 10.0).unwrap());
+        for (x, y) in distr.sample_iter(&mut rng).take(100) {
+            assert!((x + y - 10.0).abs() < 1e-12, "{} {}", x, y);
+        }
+
+        // Antithetic pairs reduce the variance of the estimate of E[exp(U)]
+        let distr = Antithetic::new(Standard);
+        let mut var_indep = 0.0;
+        let mut var_anti = 0.0;
+        for _ in 0..1000 {
+            let (u, v): (f64, f64) = distr.sample(&mut rng);
+            let (s, t): (f64, f64) = (rng.gen(), rng.gen());
+            let mean = 1f64.exp() - 1.0;
+            var_anti += ((u.exp() + v.exp()) / 2.0 - mean).powi(2);
+            var_indep += ((s.exp() + t.exp()) / 2.0 - mean).powi(2);
+        }
+        // The variance ratio is about 0.03
+        assert!(var_anti < 0.1 * var_indep, "{} {}", var_anti, var_indep);
+    }
+
+    #[test]
+    fn test_diverged_replay() {
+        let pair: (u32, u32) = Antithetic::new(Standard).sample(&mut StepRng::new(7, 1));
+        assert_eq!(pair, (7, !7));
+
+        // Once a different kind of random number is requested than was
+        // recorded, all further numbers are taken directly from the RNG
+        let mut rng = StepRng::new(5, 1);
+        let mut recorder = Recorder {
+            rng: &mut rng,
+            draws: Vec::new(),
+        };
+        assert_eq!(recorder.next_u32(), 5);
+        let mut replay = Replay {
+            draws: recorder.draws.into_iter(),
+            rng: &mut rng,
+            diverged: false,
+        };
+        assert_eq!(replay.next_u64(), 6);
+        assert_eq!(replay.next_u32(), 7);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(253);
+        let pair: (f64, f64) = Antithetic::new(Standard).sample(&mut rng);
+        assert_eq!(pair, (0.47198493590152124, 0.5280150640984786));
+    }
+}
@@ -116,15 +116,18 @@ impl Bernoulli {
     /// For `numerator > denominator` and `denominator == 0`, this returns an
     /// error. Otherwise, for `numerator == denominator`, samples are always
     /// true; for `numerator == 0` samples are always false.
+    ///
+    /// This function may be used in a `const` context.
     #[inline]
-    pub fn from_ratio(numerator: u32, denominator: u32) -> Result<Bernoulli, BernoulliError> {
+    pub const fn from_ratio(numerator: u32, denominator: u32) -> Result<Bernoulli, BernoulliError> {
         if numerator > denominator || denominator == 0 {
             return Err(BernoulliError::InvalidProbability);
         }
         if numerator == denominator {
             return Ok(Bernoulli { p_int: ALWAYS_TRUE });
         }
-        let p_int = ((f64::from(numerator) / f64::from(denominator)) * SCALE) as u64;
+        // Exact since numerator < denominator, thus the result is < 2^64
+        let p_int = (((numerator as u128) << 64) / denominator as u128) as u64;
         Ok(Bernoulli { p_int })
     }
 }
@@ -211,6 +214,30 @@ mod test {
         assert_eq!(buf, [
             true, false, false, true, false, false, true, true, true, true
         ]);
+
+        let distr = Bernoulli::from_ratio(1, 3).unwrap();
+        for x in &mut buf {
+            *x = rng.sample(distr);
+        }
+        assert_eq!(buf, [
+            false, false, true, true, false, false, false, false, false, false
+        ]);
+        for x in &mut buf {
+            *x = rng.gen_ratio(2, 7);
+        }
+        assert_eq!(buf, [
+            false, false, false, false, true, false, false, false, false, false
+        ]);
+    }
+
+    #[test]
+    fn test_from_ratio_const() {
+        const COIN: Result<Bernoulli, super::BernoulliError> = Bernoulli::from_ratio(1, 2);
+        assert_eq!(COIN, Bernoulli::new(0.5));
+        assert_eq!(Bernoulli::from_ratio(3, 3), Bernoulli::new(1.0));
+        assert_eq!(Bernoulli::from_ratio(0, 3), Bernoulli::new(0.0));
+        assert_eq!(Bernoulli::from_ratio(1, 3).unwrap().p_int, u64::MAX / 3);
+        assert!(Bernoulli::from_ratio(4, 3).is_err());
     }
 
     #[test]
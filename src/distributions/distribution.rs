@@ -82,6 +82,39 @@ pub trait Distribution<T> {
         }
     }
 
+    /// Create an iterator that generates random values of `T`, borrowing
+    /// both `self` and `rng`.
+    ///
+    /// This is equivalent to `(&distr).sample_iter(&mut *rng)`. Neither the
+    /// distribution nor the RNG is consumed, so once the iterator is dropped
+    /// both may be used again, e.g. to interleave iterator-based and direct
+    /// sampling from the same RNG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    ///
+    /// let first_three: Vec<u32> = die.sample_iter_ref(&mut rng).take(3).collect();
+    /// let next = die.sample(&mut rng);
+    /// let total: u32 = die.sample_iter_ref(&mut rng).take(2).sum();
+    /// # let _ = (first_three, next, total);
+    /// ```
+    fn sample_iter_ref<'a, R>(&'a self, rng: &'a mut R) -> DistIter<&'a Self, &'a mut R, T>
+    where
+        R: Rng + ?Sized,
+    {
+        DistIter {
+            distr: self,
+            rng,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+
     /// Create a distribution of values of 'S' by mapping the output of `Self`
     /// through the closure `F`
     ///
@@ -255,6 +288,23 @@ mod tests {
         assert_eq!(count, 10);
     }
 
+    #[test]
+    fn test_sample_iter_ref() {
+        let distr = Uniform::new(0u32, 1000).unwrap();
+        let mut rng = crate::test::rng(217);
+        let mut expected = crate::test::rng(217);
+
+        let mut iter = distr.sample_iter_ref(&mut rng);
+        assert_eq!(iter.next(), Some(distr.sample(&mut expected)));
+        assert_eq!(iter.next(), Some(distr.sample(&mut expected)));
+        // Interleave direct sampling
+        assert_eq!(distr.sample(&mut rng), distr.sample(&mut expected));
+        let rng: &mut dyn crate::RngCore = &mut rng;
+        for x in distr.sample_iter_ref(rng).take(3) {
+            assert_eq!(x, distr.sample(&mut expected));
+        }
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_dist_string() {
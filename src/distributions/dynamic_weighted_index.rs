@@ -0,0 +1,359 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted index sampling with efficient updates
+
+use core::ops::{AddAssign, SubAssign};
+
+use alloc::vec::Vec;
+
+use crate::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use crate::distributions::{Distribution, WeightedError};
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution using weighted sampling of discrete items, supporting
+/// efficient modification of the weights.
+///
+/// Like [`WeightedIndex`], sampling returns an index chosen with probability
+/// proportional to its weight. Unlike [`WeightedIndex`], which must rebuild
+/// its table of cumulative weights when weights change, this stores partial
+/// sums in a Fenwick tree (binary indexed tree): [`update`], [`push`],
+/// [`pop`] and [`swap_remove`] take `O(log N)` time, as does sampling. This
+/// suits evolving populations, e.g. in simulations or schedulers.
+///
+/// Unlike [`WeightedIndex`], the distribution may be empty or have zero
+/// total weight; [`try_sample`] then fails, while [`Distribution::sample`]
+/// panics.
+///
+/// For integer weights, sampling is exact and items of zero weight are
+/// never chosen. For floating-point weights, rounding errors accumulate as
+/// weights are updated (as with any running sum); rebuild the distribution
+/// with [`new`] occasionally if this is a concern.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, DynamicWeightedIndex};
+///
+/// let mut tasks = DynamicWeightedIndex::new([3u32, 1, 0]).unwrap();
+/// tasks.update(2, 4).unwrap();
+/// tasks.push(2).unwrap();
+/// assert_eq!(tasks.total_weight(), 10);
+///
+/// let next = tasks.sample(&mut rand::thread_rng());
+/// assert!(next < tasks.len());
+///
+/// // Remove a completed task; the last task takes its index
+/// tasks.swap_remove(next);
+/// assert_eq!(tasks.len(), 3);
+/// ```
+///
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+/// [`update`]: DynamicWeightedIndex::update
+/// [`push`]: DynamicWeightedIndex::push
+/// [`pop`]: DynamicWeightedIndex::pop
+/// [`swap_remove`]: DynamicWeightedIndex::swap_remove
+/// [`try_sample`]: DynamicWeightedIndex::try_sample
+/// [`new`]: DynamicWeightedIndex::new
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DynamicWeightedIndex<X> {
+    weights: Vec<X>,
+    // Fenwick tree: `tree[i]` is the sum of `weights[i + 1 - lsb(i + 1)..=i]`
+    // where `lsb(k)` is the least significant set bit of `k`.
+    tree: Vec<X>,
+}
+
+/// The least significant set bit of `k`
+#[inline]
+fn lsb(k: usize) -> usize {
+    k & k.wrapping_neg()
+}
+
+impl<X> DynamicWeightedIndex<X>
+where X: SampleUniform + PartialOrd + Clone + Default + for<'a> AddAssign<&'a X> + for<'a> SubAssign<&'a X>
+{
+    /// Creates a new `DynamicWeightedIndex` from the values in `weights`,
+    /// in `O(N)` time.
+    ///
+    /// `weights` may be empty or all zero. Returns an error if any weight is
+    /// `< 0` or not comparable with zero (e.g. NaN).
+    pub fn new<I>(weights: I) -> Result<Self, WeightedError>
+    where
+        I: IntoIterator,
+        I::Item: SampleBorrow<X>,
+    {
+        let weights = weights
+            .into_iter()
+            .map(|w| check_weight(w.borrow().clone()))
+            .collect::<Result<Vec<X>, _>>()?;
+
+        let mut tree = weights.clone();
+        let len = tree.len();
+        for i in 0..len {
+            let parent = i + lsb(i + 1);
+            if parent < len {
+                let sum = tree[i].clone();
+                tree[parent] += &sum;
+            }
+        }
+        Ok(DynamicWeightedIndex { weights, tree })
+    }
+
+    /// Returns the number of items
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns true if there are no items
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the weight of the item at `index`, or `None` if `index` is out
+    /// of bounds.
+    pub fn weight(&self, index: usize) -> Option<X> {
+        self.weights.get(index).cloned()
+    }
+
+    /// Returns the total weight of all items, in `O(log N)` time.
+    pub fn total_weight(&self) -> X {
+        self.prefix_sum(self.len())
+    }
+
+    /// Sum of the first `n` weights
+    fn prefix_sum(&self, mut n: usize) -> X {
+        let mut sum = X::default();
+        while n > 0 {
+            sum += &self.tree[n - 1];
+            n -= lsb(n);
+        }
+        sum
+    }
+
+    /// Set the weight of the item at `index`.
+    ///
+    /// Returns an error if `weight` is invalid, in which case the
+    /// distribution is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn update(&mut self, index: usize, weight: X) -> Result<(), WeightedError> {
+        let weight = check_weight(weight)?;
+        let old = &self.weights[index];
+        let (increase, delta) = if weight >= *old {
+            let mut delta = weight.clone();
+            delta -= old;
+            (true, delta)
+        } else {
+            let mut delta = old.clone();
+            delta -= &weight;
+            (false, delta)
+        };
+
+        let mut k = index + 1;
+        while k <= self.tree.len() {
+            if increase {
+                self.tree[k - 1] += &delta;
+            } else {
+                self.tree[k - 1] -= &delta;
+            }
+            k += lsb(k);
+        }
+        self.weights[index] = weight;
+        Ok(())
+    }
+
+    /// Append an item with the given `weight`.
+    ///
+    /// Returns an error if `weight` is invalid, in which case the
+    /// distribution is unchanged.
+    pub fn push(&mut self, weight: X) -> Result<(), WeightedError> {
+        let weight = check_weight(weight)?;
+        // The new node covers its own weight and those of its children,
+        // which are the nodes `k - c` for powers of two `c < lsb(k)`.
+        let k = self.tree.len() + 1;
+        let mut node = weight.clone();
+        let mut c = 1;
+        while c < lsb(k) {
+            node += &self.tree[k - c - 1];
+            c <<= 1;
+        }
+        self.tree.push(node);
+        self.weights.push(weight);
+        Ok(())
+    }
+
+    /// Remove the last item and return its weight, or `None` if empty.
+    pub fn pop(&mut self) -> Option<X> {
+        // No other node covers the last item
+        self.tree.pop();
+        self.weights.pop()
+    }
+
+    /// Remove the item at `index` and return its weight.
+    ///
+    /// The last item is moved into its place (as by [`Vec::swap_remove`]),
+    /// changing its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> X {
+        let last = self.pop().expect("index out of bounds");
+        if index == self.len() {
+            return last;
+        }
+        let removed = self.weights[index].clone();
+        self.update(index, last).unwrap();
+        removed
+    }
+
+    /// Sample an index, or fail if there are no items
+    /// ([`WeightedError::NoItem`]) or the total weight is zero
+    /// ([`WeightedError::AllWeightsZero`]).
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, WeightedError> {
+        if self.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        let total = self.total_weight();
+        let mut target = X::Sampler::sample_single(X::default(), total, rng)
+            .map_err(|_| WeightedError::AllWeightsZero)?;
+
+        // Descend the tree to find the first index whose prefix sum exceeds
+        // `target`
+        let len = self.tree.len();
+        let mut pos = 0;
+        let mut step = 1 << (usize::BITS - 1 - len.leading_zeros());
+        while step > 0 {
+            if pos + step <= len && self.tree[pos + step - 1] <= target {
+                pos += step;
+                target -= &self.tree[pos - 1];
+            }
+            step >>= 1;
+        }
+        // Only reachable past the end due to floating-point rounding
+        Ok(pos.min(len - 1))
+    }
+}
+
+fn check_weight<X: PartialOrd + Default>(weight: X) -> Result<X, WeightedError> {
+    if weight >= X::default() {
+        Ok(weight)
+    } else {
+        Err(WeightedError::InvalidWeight)
+    }
+}
+
+impl<X> Distribution<usize> for DynamicWeightedIndex<X>
+where X: SampleUniform + PartialOrd + Clone + Default + for<'a> AddAssign<&'a X> + for<'a> SubAssign<&'a X>
+{
+    /// Sample an index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no items or the total weight is zero.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_tree<X>(distr: &DynamicWeightedIndex<X>)
+    where X: SampleUniform + PartialOrd + Clone + Default + core::fmt::Debug
+            + for<'a> AddAssign<&'a X> + for<'a> SubAssign<&'a X>
+    {
+        let rebuilt = DynamicWeightedIndex::new(&distr.weights).unwrap();
+        assert_eq!(distr, &rebuilt);
+    }
+
+    #[test]
+    fn test_modify() {
+        let mut distr = DynamicWeightedIndex::new([1u32, 2, 3, 4, 5]).unwrap();
+        check_tree(&distr);
+        assert_eq!(distr.total_weight(), 15);
+
+        distr.update(1, 10).unwrap();
+        distr.update(4, 0).unwrap();
+        check_tree(&distr);
+        assert_eq!(distr.total_weight(), 18);
+
+        for w in 0..20 {
+            distr.push(w).unwrap();
+            check_tree(&distr);
+        }
+        assert_eq!(distr.len(), 25);
+        assert_eq!(distr.pop(), Some(19));
+        assert_eq!(distr.swap_remove(0), 1);
+        assert_eq!(distr.weight(0), Some(18));
+        assert_eq!(distr.swap_remove(distr.len() - 1), 17);
+        check_tree(&distr);
+
+        while distr.pop().is_some() {
+            check_tree(&distr);
+        }
+        assert!(distr.is_empty());
+        assert_eq!(distr.total_weight(), 0);
+
+        assert_eq!(distr.push(1), Ok(()));
+        let mut distr = DynamicWeightedIndex::new([1.0, 2.0]).unwrap();
+        assert_eq!(distr.push(-1.0), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update(0, f64::NAN), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.weight(0), Some(1.0));
+        assert!(DynamicWeightedIndex::new([1, -1]).is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = crate::test::rng(235);
+        let mut distr = DynamicWeightedIndex::new([0u32, 1, 0, 2, 0, 0, 3]).unwrap();
+        distr.push(0).unwrap();
+        distr.push(4).unwrap();
+        let mut counts = [0; 9];
+        for _ in 0..10000 {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            let expected = distr.weight(i).unwrap() * 1000;
+            if expected == 0 {
+                assert_eq!(c, 0);
+            } else {
+                assert!((expected * 9 / 10..expected * 11 / 10).contains(&c), "{}: {}", i, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_edge_cases() {
+        let mut rng = crate::test::rng(236);
+        let mut distr = DynamicWeightedIndex::<f64>::new(None::<f64>).unwrap();
+        assert_eq!(distr.try_sample(&mut rng), Err(WeightedError::NoItem));
+        distr.push(0.0).unwrap();
+        assert_eq!(distr.try_sample(&mut rng), Err(WeightedError::AllWeightsZero));
+        distr.push(0.5).unwrap();
+        assert!((0..100).all(|_| distr.sample(&mut rng) == 1));
+        distr.update(0, 1e-3).unwrap();
+        distr.update(1, 0.0).unwrap();
+        assert!((0..100).all(|_| distr.sample(&mut rng) == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "zero")]
+    fn test_sample_panic() {
+        let distr = DynamicWeightedIndex::new([0, 0]).unwrap();
+        distr.sample(&mut crate::test::rng(237));
+    }
+}
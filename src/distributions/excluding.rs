@@ -0,0 +1,168 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform sampling of indices with exclusions
+
+use alloc::vec::Vec;
+
+use crate::distributions::uniform::Error;
+use crate::distributions::{Distribution, Uniform};
+use crate::Rng;
+
+/// Sample indices uniformly from `0..n`, excluding a set of forbidden
+/// indices.
+///
+/// Rather than rejecting forbidden samples, an index is sampled from the
+/// `n - m` allowed values directly (where `m` is the number of excluded
+/// indices) and then remapped past the excluded indices via binary search.
+/// Sampling thus takes a single random number and `O(log m)` time, whether
+/// excluding a few indices (e.g. "any tile but the current one") or most
+/// of them (e.g. picking a free ID given the set of allocated IDs).
+///
+/// Excluded indices are stored sorted; duplicates and indices outside of
+/// `0..n` are ignored.
+///
+/// When the number of allowed indices `n - m` is at most `u32::MAX`, the
+/// index is sampled as a `u32`, thus results do not depend on the platform's
+/// pointer width.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, UniformExcluding};
+///
+/// let current = 3;
+/// let next_tile = UniformExcluding::new(8, [current]).unwrap();
+/// let tile = next_tile.sample(&mut rand::thread_rng());
+/// assert!(tile < 8 && tile != current);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniformExcluding {
+    excluded: Vec<usize>,
+    range: IndexRange,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IndexRange {
+    U32(Uniform<u32>),
+    USize(Uniform<usize>),
+}
+
+impl UniformExcluding {
+    /// Construct a sampler over `0..n` which never yields any index in
+    /// `excluded`.
+    ///
+    /// Fails with [`Error::EmptyRange`] if no index is allowed.
+    pub fn new<I>(n: usize, excluded: I) -> Result<Self, Error>
+    where I: IntoIterator<Item = usize> {
+        let mut excluded: Vec<usize> = excluded.into_iter().filter(|&i| i < n).collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+
+        let allowed = n - excluded.len();
+        let range = if allowed <= u32::MAX as usize {
+            IndexRange::U32(Uniform::new(0, allowed as u32)?)
+        } else {
+            IndexRange::USize(Uniform::new(0, allowed)?)
+        };
+        Ok(UniformExcluding { excluded, range })
+    }
+
+    /// Return the sorted list of excluded indices within `0..n`.
+    pub fn excluded(&self) -> &[usize] {
+        &self.excluded
+    }
+}
+
+impl Distribution<usize> for UniformExcluding {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let index = match self.range {
+            IndexRange::U32(range) => range.sample(rng) as usize,
+            IndexRange::USize(range) => range.sample(rng),
+        };
+        // Since the exclusions are sorted and distinct, `excluded[j] - j` is
+        // the number of allowed indices below `excluded[j]` and is
+        // non-decreasing. The result is `index` plus the number of excluded
+        // indices `j` with at most `index` allowed indices below them.
+        let (mut low, mut high) = (0, self.excluded.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.excluded[mid] - mid <= index {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        index + low
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_excluding() {
+        let mut rng = crate::test::rng(222);
+        let distr = UniformExcluding::new(10, [9, 0, 4, 5, 4, 12]).unwrap();
+        assert_eq!(distr.excluded(), [0, 4, 5, 9]);
+
+        let mut counts = [0; 10];
+        for _ in 0..6000 {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            if distr.excluded().contains(&i) {
+                assert_eq!(c, 0);
+            } else {
+                // Expect 1000 of each
+                assert!((850..1150).contains(&c), "{}: {}", i, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_exclusions() {
+        let mut rng = crate::test::rng(225);
+        // Allow only odd indices and the last one
+        let distr = UniformExcluding::new(1001, (0..1000).step_by(2)).unwrap();
+        for _ in 0..1000 {
+            let i = distr.sample(&mut rng);
+            assert!(i < 1001 && (i % 2 == 1 || i == 1000), "{}", i);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let mut rng = crate::test::rng(223);
+        assert_eq!(UniformExcluding::new(0, None), Err(Error::EmptyRange));
+        assert_eq!(UniformExcluding::new(3, 0..3), Err(Error::EmptyRange));
+
+        // A single allowed index
+        let distr = UniformExcluding::new(5, [0, 1, 3, 4]).unwrap();
+        assert!((0..10).all(|_| distr.sample(&mut rng) == 2));
+        let distr = UniformExcluding::new(5, 0..4).unwrap();
+        assert!((0..10).all(|_| distr.sample(&mut rng) == 4));
+
+        // No exclusions
+        let distr = UniformExcluding::new(3, None).unwrap();
+        assert!((0..10).all(|_| distr.sample(&mut rng) < 3));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(224);
+        let distr = UniformExcluding::new(6, [2]).unwrap();
+        let mut buf = [0; 8];
+        for x in &mut buf {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [5, 3, 3, 1, 5, 5, 5, 5]);
+    }
+}
@@ -94,25 +94,22 @@ impl_int_from_uint! { i128, u128 }
 impl_int_from_uint! { isize, usize }
 
 macro_rules! impl_nzint {
-    ($ty:ty, $new:path) => {
+    ($ty:ty, $u:ty) => {
         impl Distribution<$ty> for Standard {
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
-                loop {
-                    if let Some(nz) = $new(rng.gen()) {
-                        break nz;
-                    }
-                }
+                // Uniform over `1..=MAX`, rather than rejecting zero
+                rng.gen_range(<$ty>::new(1).unwrap()..=<$ty>::new(<$u>::MAX).unwrap())
             }
         }
     };
 }
 
-impl_nzint!(NonZeroU8, NonZeroU8::new);
-impl_nzint!(NonZeroU16, NonZeroU16::new);
-impl_nzint!(NonZeroU32, NonZeroU32::new);
-impl_nzint!(NonZeroU64, NonZeroU64::new);
-impl_nzint!(NonZeroU128, NonZeroU128::new);
-impl_nzint!(NonZeroUsize, NonZeroUsize::new);
+impl_nzint!(NonZeroU8, u8);
+impl_nzint!(NonZeroU16, u16);
+impl_nzint!(NonZeroU32, u32);
+impl_nzint!(NonZeroU64, u64);
+impl_nzint!(NonZeroU128, u128);
+impl_nzint!(NonZeroUsize, usize);
 
 macro_rules! x86_intrinsic_impl {
     ($($intrinsic:ident),+) => {$(
@@ -221,6 +218,15 @@ mod tests {
         ]);
 
         test_samples(0i8, &[9, -9, 111]);
+
+        let nz8 = |x| NonZeroU8::new(x).unwrap();
+        test_samples(nz8(1), &[nz8(132), nz8(153), nz8(120)]);
+        let nz64 = |x| NonZeroU64::new(x).unwrap();
+        test_samples(nz64(1), &[
+            nz64(11059617991457472009),
+            nz64(16096616328739788143),
+            nz64(1487364411147516184),
+        ]);
         // Skip further i* types: they are simple reinterpretation of u* samples
 
         #[cfg(feature = "simd_support")]
@@ -44,7 +44,8 @@
 //!
 //! [`Alphanumeric`] is a simple distribution to sample random letters and
 //! numbers of the `char` type; in contrast [`Standard`] may sample any valid
-//! `char`.
+//! `char`. Similarly, [`Ascii`] samples printable ASCII characters,
+//! [`AsciiLowercase`] lowercase letters and [`Digits`] decimal digits.
 //!
 //!
 //! # Uniform numeric ranges
@@ -65,6 +66,13 @@
 //! documentation in the [`uniform`] module). Doing so enables generation of
 //! values of type `T` with  [`Rng::sample(Range)`].
 //!
+//! [`UniformExcluding`] samples indices uniformly from a range while
+//! excluding a (small) set of forbidden indices.
+//!
+//! [`Rendezvous`] is not a distribution but a deterministic counterpart:
+//! it maps hashable keys to stable uniform scores, for consistent selection
+//! via rendezvous hashing.
+//!
 //! ## Open and half-open ranges
 //!
 //! There are surprisingly many ways to uniformly generate random floats. A
@@ -93,14 +101,33 @@
 //! [`rand_distr`]: https://crates.io/crates/rand_distr
 //! [`statrs`]: https://crates.io/crates/statrs
 
+#[cfg(feature = "alloc")]
+mod antithetic;
 mod bernoulli;
 mod distribution;
+#[cfg(feature = "alloc")]
+mod dynamic_weighted_index;
+#[cfg(feature = "alloc")]
+mod excluding;
 mod float;
 mod integer;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
 mod other;
+#[cfg(feature = "alloc")]
+mod random_string;
+mod reject;
+mod rendezvous;
 mod slice;
+mod sub_range;
+mod switch;
+mod transform;
+#[cfg(feature = "alloc")]
+mod uniform_union;
 mod utils;
 #[cfg(feature = "alloc")]
+mod weighted_choice;
+#[cfg(feature = "alloc")]
 mod weighted_index;
 
 #[doc(hidden)]
@@ -116,17 +143,34 @@ pub mod uniform;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod weighted;
 
+#[cfg(feature = "alloc")]
+pub use self::antithetic::Antithetic;
 pub use self::bernoulli::{Bernoulli, BernoulliError};
 pub use self::distribution::{Distribution, DistIter, DistMap};
 #[cfg(feature = "alloc")]
 pub use self::distribution::DistString;
+#[cfg(feature = "alloc")]
+pub use self::dynamic_weighted_index::DynamicWeightedIndex;
+#[cfg(feature = "alloc")]
+pub use self::excluding::UniformExcluding;
 pub use self::float::{Open01, OpenClosed01};
-pub use self::other::Alphanumeric;
+pub use self::other::{Alphanumeric, Ascii, AsciiLowercase, Digits};
+#[cfg(feature = "alloc")]
+pub use self::random_string::RandomString;
+pub use self::reject::{RejectIf, RejectIfError};
+pub use self::rendezvous::Rendezvous;
 pub use self::slice::Slice;
+pub use self::sub_range::{SubRange, SubRangeError};
+pub use self::switch::Switch;
+pub use self::transform::{Clamp, ClampError, Scale, Shift};
 #[doc(inline)]
 pub use self::uniform::Uniform;
 #[cfg(feature = "alloc")]
-pub use self::weighted_index::{WeightedError, WeightedIndex};
+pub use self::uniform_union::UniformUnion;
+#[cfg(feature = "alloc")]
+pub use self::weighted_choice::WeightedChoice;
+#[cfg(feature = "alloc")]
+pub use self::weighted_index::{WeightedError, WeightedIndex, WeightedIndexIter};
 
 #[allow(unused)]
 use crate::Rng;
@@ -157,6 +201,8 @@ use crate::Rng;
 ///   [`mask32x4`] (requires [`simd_support`]), where each lane is distributed
 ///   like their scalar `Standard` variants. See the list of `Standard`
 ///   implementations for more.
+/// * Statically sized vectors and matrices of [`nalgebra`] (requires the
+///   `nalgebra` feature), where each element is generated by `Standard`.
 ///
 /// The `Standard` distribution also supports generation of the following
 /// compound types where all component types are supported:
@@ -224,6 +270,7 @@ use crate::Rng;
 /// [`f32x4`]: std::simd::f32x4
 /// [`mask32x4`]: std::simd::mask32x4
 /// [`simd_support`]: https://github.com/rust-random/rand#crate-features
+/// [`nalgebra`]: https://docs.rs/nalgebra
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
 pub struct Standard;
@@ -0,0 +1,56 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `Standard` implementations for [`nalgebra`](https://docs.rs/nalgebra) types
+
+use ::nalgebra::allocator::Allocator;
+use ::nalgebra::{DefaultAllocator, DimName, OMatrix, Scalar};
+
+use crate::distributions::{Distribution, Standard};
+use crate::Rng;
+
+/// Samples statically sized vectors and matrices, such as
+/// [`SVector`](::nalgebra::SVector) and [`SMatrix`](::nalgebra::SMatrix),
+/// where each element is generated by `Standard` in column-major order.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "nalgebra")))]
+impl<T, R, C> Distribution<OMatrix<T, R, C>> for Standard
+where
+    T: Scalar,
+    R: DimName,
+    C: DimName,
+    DefaultAllocator: Allocator<T, R, C>,
+    Standard: Distribution<T>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> OMatrix<T, R, C> {
+        OMatrix::from_fn(|_, _| rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::nalgebra::{Matrix2x3, Vector3};
+
+    #[test]
+    fn test_nalgebra() {
+        let mut rng = crate::test::rng(208);
+        let v: Vector3<u32> = rng.gen();
+        let m: Matrix2x3<u32> = rng.gen();
+
+        let mut rng = crate::test::rng(208);
+        let expected: [u32; 9] = rng.gen();
+        assert_eq!(v.as_slice(), &expected[..3]);
+        // Column-major order
+        assert_eq!(m.as_slice(), &expected[3..]);
+        assert_eq!(m[(1, 0)], expected[4]);
+
+        let f: Vector3<f64> = rng.gen();
+        assert!(f.iter().all(|&x| (0.0..1.0).contains(&x)));
+    }
+}
@@ -69,6 +69,49 @@ use core::simd::*;
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct Alphanumeric;
 
+/// Sample a `u8`, uniformly distributed over printable ASCII characters:
+/// space (`0x20`) to tilde (`0x7E`).
+///
+/// See also [`Alphanumeric`], [`AsciiLowercase`] and [`Digits`] for smaller
+/// character sets.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Ascii, DistString};
+/// let string = Ascii.sample_string(&mut rand::thread_rng(), 16);
+/// assert!(string.bytes().all(|b| (b' '..=b'~').contains(&b)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Ascii;
+
+/// Sample a `u8`, uniformly distributed over ASCII lowercase letters: a-z.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{AsciiLowercase, DistString};
+/// let string = AsciiLowercase.sample_string(&mut rand::thread_rng(), 8);
+/// assert!(string.bytes().all(|b| b.is_ascii_lowercase()));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AsciiLowercase;
+
+/// Sample a `u8`, uniformly distributed over ASCII decimal digits: 0-9.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Digits, DistString};
+/// let pin = Digits.sample_string(&mut rand::thread_rng(), 4);
+/// assert!(pin.bytes().all(|b| b.is_ascii_digit()));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Digits;
+
 
 // ----- Implementations of distributions -----
 
@@ -135,6 +178,53 @@ impl DistString for Alphanumeric {
     }
 }
 
+/// Sample `first + n` for `n` uniform in `0..range`, where
+/// `2^(bits - 1) < range <= 2^bits`, as for `Alphanumeric`
+#[inline]
+fn sample_ascii_range<R: Rng + ?Sized>(rng: &mut R, first: u8, range: u32, bits: u32) -> u8 {
+    loop {
+        let var = rng.next_u32() >> (32 - bits);
+        if var < range {
+            return first + var as u8;
+        }
+    }
+}
+
+impl Distribution<u8> for Ascii {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        sample_ascii_range(rng, b' ', 95, 7)
+    }
+}
+
+impl Distribution<u8> for AsciiLowercase {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        sample_ascii_range(rng, b'a', 26, 5)
+    }
+}
+
+impl Distribution<u8> for Digits {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        sample_ascii_range(rng, b'0', 10, 4)
+    }
+}
+
+macro_rules! ascii_dist_string_impl {
+    ($($ty:ty),*) => {$(
+        #[cfg(feature = "alloc")]
+        impl DistString for $ty {
+            fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+                // Samples are ASCII, thus valid UTF-8
+                unsafe {
+                    let v = string.as_mut_vec();
+                    v.extend(self.sample_iter(rng).take(len));
+                }
+            }
+        }
+    )*}
+}
+
+ascii_dist_string_impl!(Ascii, AsciiLowercase, Digits);
+
 impl Distribution<bool> for Standard {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
@@ -288,6 +378,24 @@ mod tests {
         rng.sample::<bool, _>(Standard);
     }
 
+    #[test]
+    fn test_arrays() {
+        // Arrays of any length are supported, sampling each element in turn
+        let mut rng = crate::test::rng(274);
+        let mut reference = crate::test::rng(274);
+        let _: [u8; 0] = rng.gen();
+        let key: [u8; 33] = rng.gen();
+        let buf: [u32; 100] = rng.gen();
+        for &x in key.iter() {
+            assert_eq!(x, reference.gen::<u8>());
+        }
+        for &x in buf.iter() {
+            assert_eq!(x, reference.gen::<u32>());
+        }
+        let nested: [[bool; 40]; 40] = rng.gen();
+        assert!(nested.iter().flatten().any(|&b| b));
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn test_chars() {
@@ -319,6 +427,21 @@ mod tests {
         assert!(!incorrect);
     }
 
+    #[test]
+    fn test_ascii() {
+        let mut rng = crate::test::rng(808);
+        let mut seen = [false; 128];
+        for _ in 0..2000 {
+            let c: u8 = rng.sample(Ascii);
+            assert!((0x20..=0x7E).contains(&c));
+            seen[c as usize] = true;
+            assert!(rng.sample(AsciiLowercase).is_ascii_lowercase());
+            assert!(rng.sample(Digits).is_ascii_digit());
+        }
+        // All printable characters are reachable
+        assert!(seen[0x20..=0x7E].iter().all(|&x| x));
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: Copy + core::fmt::Debug + PartialEq, D: Distribution<T>>(
@@ -340,6 +463,9 @@ mod tests {
             '\u{35888}',
         ]);
         test_samples(&Alphanumeric, 0, &[104, 109, 101, 51, 77]);
+        test_samples(&Ascii, 0, &[98, 108, 92, 57, 42]);
+        test_samples(&AsciiLowercase, 0, &[113, 116, 112, 103, 99]);
+        test_samples(&Digits, 0, &[56, 57, 55, 51, 49]);
         test_samples(&Standard, false, &[true, true, false, true, false]);
         test_samples(&Standard, None as Option<bool>, &[
             Some(true),
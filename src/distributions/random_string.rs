@@ -0,0 +1,117 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strings of random length and characters
+
+use alloc::string::String;
+
+use crate::distributions::{DistString, Distribution};
+use crate::Rng;
+
+/// Sample `String`s with a random length and random characters.
+///
+/// The length of each string is sampled from `len`, any distribution of
+/// `usize` values, e.g. [`Uniform`]; use `Uniform::new_inclusive(n, n)`
+/// for a fixed length, or [`Distribution::map`] to adapt another
+/// distribution such as `Poisson` from the `rand_distr` crate. The
+/// characters are then sampled via `chars`, any [`DistString`] such as
+/// [`Alphanumeric`], or [`Slice`] over a set of `char`s.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Alphanumeric, RandomString, Slice, Uniform};
+///
+/// let mut rng = rand::thread_rng();
+/// let username = RandomString::new(Uniform::new_inclusive(3, 12).unwrap(), Alphanumeric);
+/// let name = username.sample(&mut rng);
+/// assert!((3..=12).contains(&name.len()));
+///
+/// let hex: Vec<char> = "0123456789abcdef".chars().collect();
+/// let id = RandomString::new(Uniform::new_inclusive(32, 32).unwrap(), Slice::new(&hex).unwrap());
+/// assert_eq!(id.sample(&mut rng).len(), 32);
+/// ```
+///
+/// [`Uniform`]: crate::distributions::Uniform
+/// [`Alphanumeric`]: crate::distributions::Alphanumeric
+/// [`Slice`]: crate::distributions::Slice
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomString<L, C> {
+    len: L,
+    chars: C,
+}
+
+impl<L, C> RandomString<L, C> {
+    /// Construct, sampling the length from `len` and characters from `chars`.
+    pub fn new(len: L, chars: C) -> Self {
+        RandomString { len, chars }
+    }
+
+    /// Get the distribution of lengths
+    pub fn len_distr(&self) -> &L {
+        &self.len
+    }
+
+    /// Get the distribution of characters
+    pub fn chars(&self) -> &C {
+        &self.chars
+    }
+}
+
+impl<L, C> Distribution<String> for RandomString<L, C>
+where
+    L: Distribution<usize>,
+    C: DistString,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let len = self.len.sample(rng);
+        self.chars.sample_string(rng, len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::{Alphanumeric, Digits, Slice, Standard, Uniform};
+
+    #[test]
+    fn test_random_string() {
+        let mut rng = crate::test::rng(248);
+        let distr = RandomString::new(Uniform::new_inclusive(0, 5).unwrap(), Digits);
+        let mut lens = [0; 6];
+        for _ in 0..600 {
+            let s = distr.sample(&mut rng);
+            assert!(s.bytes().all(|b| b.is_ascii_digit()));
+            lens[s.len()] += 1;
+        }
+        assert!(lens.iter().all(|&n| n > 50), "{:?}", lens);
+
+        // Lengths count chars rather than bytes
+        let distr = RandomString::new(Uniform::new_inclusive(7, 7).unwrap(), Standard);
+        assert_eq!(distr.sample(&mut rng).chars().count(), 7);
+
+        let greek = ['α', 'β', 'γ'];
+        let distr = RandomString::new(Uniform::new(1, 10).unwrap(), Slice::new(&greek).unwrap());
+        let s = distr.sample(&mut rng);
+        assert!((1..10).contains(&s.chars().count()));
+        assert!(s.chars().all(|c| greek.contains(&c)));
+
+        // Mapped length distributions
+        let distr = RandomString::new(Uniform::new(0u8, 4).unwrap().map(|n| 2 * n as usize), Alphanumeric);
+        assert!((0..20).all(|_| distr.sample(&mut rng).len() % 2 == 0));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(249);
+        let distr = RandomString::new(Uniform::new_inclusive(2, 6).unwrap(), Alphanumeric);
+        let strings = [distr.sample(&mut rng), distr.sample(&mut rng)];
+        assert_eq!(strings, ["ee", "DOye"]);
+    }
+}
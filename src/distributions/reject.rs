@@ -0,0 +1,167 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rejection sampling: the [`RejectIf`] combinator.
+
+use core::fmt;
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+/// Sample from a distribution `D`, resampling values matching a predicate.
+///
+/// The predicate `reject` identifies values which must not be produced;
+/// sampling repeats until a value is found for which `reject` returns false.
+/// The result is the distribution `D` *conditioned* on the predicate being
+/// false.
+///
+/// Since the probability of rejection may be arbitrarily close to one (or be
+/// one, in which case sampling would never terminate), the number of
+/// attempts per sample is limited to `max_attempts`. Use
+/// [`RejectIf::try_sample`] to handle failure; [`Distribution::sample`]
+/// panics instead.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{RejectIf, Uniform};
+///
+/// // A point in the unit square, but not inside the obstacle
+/// let obstacle = |&(x, y): &(f64, f64)| (x - 0.5).powi(2) + (y - 0.5).powi(2) < 0.1;
+/// let square = Uniform::new((0.0, 0.0), (1.0, 1.0)).unwrap();
+/// let distr = RejectIf::new(square, obstacle, 1000);
+///
+/// let p = distr.try_sample(&mut rand::thread_rng()).unwrap();
+/// assert!(!obstacle(&p));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RejectIf<D, F> {
+    distr: D,
+    reject: F,
+    max_attempts: u32,
+}
+
+/// Error type returned from [`RejectIf::try_sample`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectIfError {
+    /// All of `max_attempts` samples were rejected.
+    MaxAttemptsExceeded,
+}
+
+impl fmt::Display for RejectIfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RejectIfError::MaxAttemptsExceeded => "all samples were rejected in RejectIf",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl ::std::error::Error for RejectIfError {}
+
+impl<D, F> RejectIf<D, F> {
+    /// Construct, rejecting samples of `distr` for which `reject` returns
+    /// true and making at most `max_attempts` attempts per sample.
+    ///
+    /// If `max_attempts == 0`, sampling always fails.
+    pub fn new(distr: D, reject: F, max_attempts: u32) -> Self {
+        RejectIf {
+            distr,
+            reject,
+            max_attempts,
+        }
+    }
+
+    /// Get the inner distribution
+    pub fn inner(&self) -> &D {
+        &self.distr
+    }
+
+    /// Get the maximum number of attempts per sample
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Sample a value which is not rejected, or fail after `max_attempts`
+    /// rejections.
+    pub fn try_sample<T, R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, RejectIfError>
+    where
+        D: Distribution<T>,
+        F: Fn(&T) -> bool,
+    {
+        for _ in 0..self.max_attempts {
+            let x = self.distr.sample(rng);
+            if !(self.reject)(&x) {
+                return Ok(x);
+            }
+        }
+        Err(RejectIfError::MaxAttemptsExceeded)
+    }
+}
+
+impl<D, F, T> Distribution<T> for RejectIf<D, F>
+where
+    D: Distribution<T>,
+    F: Fn(&T) -> bool,
+{
+    /// Sample a value which is not rejected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` consecutive samples are rejected.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::Uniform;
+
+    #[test]
+    fn test_reject_if() {
+        let mut rng = crate::test::rng(229);
+        let distr = RejectIf::new(Uniform::new(0u32, 10).unwrap(), |x: &u32| x % 3 == 0, 100);
+        let mut counts = [0; 10];
+        for _ in 0..6000 {
+            counts[distr.sample(&mut rng) as usize] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            if i % 3 == 0 {
+                assert_eq!(c, 0);
+            } else {
+                // Expect 1000 of each
+                assert!((850..1150).contains(&c), "{}: {}", i, c);
+            }
+        }
+        assert_eq!(distr.max_attempts(), 100);
+        assert_eq!(distr.inner(), &Uniform::new(0u32, 10).unwrap());
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let mut rng = crate::test::rng(230);
+        let always = RejectIf::new(Uniform::new(0u32, 10).unwrap(), |_: &u32| true, 10);
+        assert_eq!(always.try_sample(&mut rng), Err(RejectIfError::MaxAttemptsExceeded));
+
+        let never = RejectIf::new(Uniform::new(0u32, 10).unwrap(), |_: &u32| false, 0);
+        assert_eq!(never.try_sample(&mut rng), Err(RejectIfError::MaxAttemptsExceeded));
+        let never = RejectIf::new(*never.inner(), |_: &u32| false, 1);
+        assert!(never.try_sample(&mut rng).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "rejected")]
+    fn test_panic() {
+        let mut rng = crate::test::rng(231);
+        let always = RejectIf::new(Uniform::new(0u32, 10).unwrap(), |_: &u32| true, 10);
+        always.sample(&mut rng);
+    }
+}
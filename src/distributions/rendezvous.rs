@@ -0,0 +1,327 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rendezvous (highest random weight) scores
+
+use core::hash::{Hash, Hasher};
+
+/// Deterministic pseudo-random scores of hashable keys, for rendezvous
+/// hashing.
+///
+/// `Rendezvous` maps each key (any type implementing [`Hash`]) and a seed to
+/// a score which looks uniformly distributed over `(0, 1)`, yet is stable:
+/// the same key and seed always give the same score, on every platform and
+/// without any RNG state. This is the building block of rendezvous hashing,
+/// also known as highest random weight (HRW) hashing: each key is assigned to
+/// the candidate (e.g. server) of highest score for the pair
+/// `(candidate, key)`. Adding a candidate moves only those keys for which
+/// the new candidate scores highest, and removing one moves only its own
+/// keys; all other assignments are unchanged.
+///
+/// [`Rendezvous::choose`] selects among equally weighted candidates and
+/// [`Rendezvous::choose_weighted`] (requires `std`) among candidates
+/// weighted by capacity.
+///
+/// Scores are computed with a fast non-cryptographic keyed hash, written
+/// such that integers hash identically on all platforms (`usize` and `isize`
+/// as 64-bit little-endian values). The hash is not resistant to adversarial
+/// keys. Scores are value-stable in the sense of the crate's portability
+/// policy, provided the [`Hash`] implementations of the keys are.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::Rendezvous;
+///
+/// let servers = ["alpha", "beta", "gamma"];
+/// let hrw = Rendezvous::new(0);
+/// let server = hrw.choose(&servers, "user-42").unwrap();
+/// // The assignment is deterministic
+/// assert_eq!(hrw.choose(&servers, "user-42"), Some(server));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rendezvous {
+    seed: u64,
+}
+
+impl Rendezvous {
+    /// Construct with the given seed
+    ///
+    /// Different seeds give independent scores.
+    pub const fn new(seed: u64) -> Rendezvous {
+        Rendezvous { seed }
+    }
+
+    /// Get the 64-bit hash of `key`
+    pub fn hash<K: Hash + ?Sized>(&self, key: &K) -> u64 {
+        let mut hasher = ScoreHasher::new(self.seed);
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Get the score of `key`, in the open interval `(0, 1)`
+    ///
+    /// The score has 52 bits of precision, such that it is exactly
+    /// representable and strictly less than 1.
+    pub fn score<K: Hash + ?Sized>(&self, key: &K) -> f64 {
+        hash_to_score(self.hash(key))
+    }
+
+    /// Choose the candidate of highest score for `key`
+    ///
+    /// Each candidate is scored as the pair `(candidate, key)`. Returns
+    /// `None` only if there are no candidates.
+    pub fn choose<'a, T, K, I>(&self, candidates: I, key: &K) -> Option<&'a T>
+    where
+        T: Hash + 'a,
+        K: Hash + ?Sized,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let mut best = None;
+        for candidate in candidates {
+            let h = self.hash(&(candidate, key));
+            if best.map_or(true, |(_, b)| h > b) {
+                best = Some((candidate, h));
+            }
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+
+    /// Choose a candidate for `key` with probability proportional to its
+    /// weight
+    ///
+    /// Candidates are given as pairs `(candidate, weight)`, and are ranked
+    /// by `-weight / ln(score)` where the score is that of
+    /// `(candidate, key)`. Over many keys, the fraction assigned to each
+    /// candidate is proportional to its weight, while changing the weight of
+    /// one candidate only moves keys to or from that candidate.
+    ///
+    /// Candidates with a weight which is not positive and finite are never
+    /// chosen. Returns `None` if no candidate may be chosen.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn choose_weighted<'a, T, K, I>(&self, candidates: I, key: &K) -> Option<&'a T>
+    where
+        T: Hash + 'a,
+        K: Hash + ?Sized,
+        I: IntoIterator<Item = (&'a T, f64)>,
+    {
+        let mut best = None;
+        for (candidate, weight) in candidates {
+            if !(weight > 0.0 && weight.is_finite()) {
+                continue;
+            }
+            let rank = -weight / self.score(&(candidate, key)).ln();
+            if best.map_or(true, |(_, b)| rank > b) {
+                best = Some((candidate, rank));
+            }
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+}
+
+// Map a hash to the centre of one of 2^52 equal sub-intervals of (0, 1)
+#[inline]
+fn hash_to_score(hash: u64) -> f64 {
+    let scale = 1.0 / ((1u64 << 52) as f64);
+    ((hash >> 12) as f64 + 0.5) * scale
+}
+
+// The SplitMix64 finalizer, a bijective mixing function
+#[inline]
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// A keyed hasher with platform-independent output
+struct ScoreHasher {
+    state: u64,
+    len: u64,
+}
+
+impl ScoreHasher {
+    fn new(seed: u64) -> ScoreHasher {
+        ScoreHasher {
+            state: mix(seed ^ 0x9e3779b97f4a7c15),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn absorb(&mut self, word: u64) {
+        self.state = mix(self.state ^ word).wrapping_add(0x9e3779b97f4a7c15);
+    }
+}
+
+impl Hasher for ScoreHasher {
+    fn finish(&self) -> u64 {
+        mix(self.state ^ self.len)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(chunk);
+            self.absorb(u64::from_le_bytes(buf));
+        }
+        let rem = chunks.remainder();
+        if !rem.is_empty() {
+            let mut buf = [0u8; 8];
+            buf[..rem.len()].copy_from_slice(rem);
+            self.absorb(u64::from_le_bytes(buf));
+        }
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+    }
+
+    // Fixed-width writes make the hash independent of endianness and of the
+    // size of usize
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(u64::from(i));
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.absorb(i);
+        self.len = self.len.wrapping_add(8);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write_u64(i as u64);
+        self.write_u64((i >> 64) as u64);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let hrw = Rendezvous::new(271);
+        assert_eq!(hrw.score("key"), hrw.score("key"));
+        assert_ne!(hrw.score("key"), hrw.score("key2"));
+        assert_ne!(hrw.score("key"), Rendezvous::new(272).score("key"));
+        assert!(hash_to_score(u64::MAX) < 1.0);
+        assert!(hash_to_score(0) > 0.0);
+        // Integers hash alike regardless of width and sign
+        assert_eq!(hrw.hash(&7usize), hrw.hash(&7u64));
+        assert_eq!(hrw.hash(&-1i32), hrw.hash(&u64::MAX));
+
+        // Scores are uniform over (0, 1)
+        let n = 10000;
+        let mut counts = [0u32; 10];
+        for key in 0..n {
+            let s = hrw.score(&key);
+            assert!(s > 0.0 && s < 1.0);
+            counts[(s * 10.0) as usize] += 1;
+        }
+        for &c in &counts {
+            assert!((c as f64 - 1000.0).abs() < 150.0, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_choose() {
+        let hrw = Rendezvous::new(0);
+        let empty: [u32; 0] = [];
+        assert_eq!(hrw.choose(&empty, &0), None);
+
+        // Adding a candidate only moves keys to the new candidate
+        let nodes = [10u32, 11, 12, 13, 14];
+        let more = [10u32, 11, 12, 13, 14, 15];
+        let n = 6000;
+        let mut counts = [0u32; 5];
+        let mut moved = 0;
+        for key in 0..n {
+            let a = *hrw.choose(&nodes, &key).unwrap();
+            let b = *hrw.choose(&more, &key).unwrap();
+            counts[(a - 10) as usize] += 1;
+            if a != b {
+                assert_eq!(b, 15);
+                moved += 1;
+            }
+        }
+        assert!((moved as f64 - 1000.0).abs() < 150.0, "{}", moved);
+        for &c in &counts {
+            assert!((c as f64 - 1200.0).abs() < 150.0, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_choose_weighted() {
+        let hrw = Rendezvous::new(1);
+        let nodes = ['a', 'b', 'c', 'd'];
+        let weights = [1.0, 2.0, 0.0, f64::NAN];
+        let invalid = nodes[2..].iter().zip(weights[2..].iter().cloned());
+        assert_eq!(hrw.choose_weighted(invalid, &0), None);
+
+        let n = 9000;
+        let mut counts = [0u32; 2];
+        for key in 0..n {
+            let c = hrw.choose_weighted(nodes.iter().zip(weights.iter().cloned()), &key).unwrap();
+            counts[(*c as u8 - b'a') as usize] += 1;
+        }
+        assert!((counts[0] as f64 - 3000.0).abs() < 200.0, "{:?}", counts);
+
+        // Increasing a weight only moves keys to that candidate
+        let heavier = [1.0, 3.0];
+        for key in 0..1000 {
+            let a = hrw.choose_weighted(nodes.iter().zip(weights.iter().cloned()), &key);
+            let b = hrw.choose_weighted(nodes.iter().zip(heavier.iter().cloned()), &key);
+            assert!(a == b || b == Some(&'b'));
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let hrw = Rendezvous::new(0x0123_4567_89ab_cdef);
+        assert_eq!(hrw.hash(&0u64), 4712638465158888291);
+        assert_eq!(hrw.hash("hello"), 13809920422519937613);
+        assert_eq!(hrw.score(&(1u8, 'x')), 0.452808551580698);
+        assert_eq!(hrw.choose(&[1u16, 2, 3, 4], &42), Some(&2));
+    }
+}
@@ -7,6 +7,10 @@
 // except according to those terms.
 
 use crate::distributions::{Distribution, Uniform};
+#[cfg(feature = "alloc")]
+use crate::distributions::DistString;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 /// A distribution to sample items uniformly from a slice.
 ///
@@ -99,6 +103,15 @@ impl<'a, T> Distribution<&'a T> for Slice<'a, T> {
     }
 }
 
+/// Sample strings of characters chosen uniformly from the slice.
+#[cfg(feature = "alloc")]
+impl<'a> DistString for Slice<'a, char> {
+    fn append_string<R: crate::Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.reserve(len);
+        string.extend(self.sample_iter(rng).take(len));
+    }
+}
+
 /// Error type indicating that a [`Slice`] distribution was improperly
 /// constructed with an empty slice.
 #[derive(Debug, Clone, Copy)]
@@ -0,0 +1,205 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform sampling of sub-ranges of `0..n`
+
+use core::fmt;
+use core::ops::{Range, RangeInclusive};
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+/// Sample uniformly random non-empty sub-ranges of `0..n`.
+///
+/// [`SubRange::new`] samples uniformly from all `n * (n + 1) / 2` non-empty
+/// ranges `a..b` with `0 <= a < b <= n`. [`SubRange::with_len`] instead
+/// samples ranges of a fixed length at a uniformly random offset. Both are
+/// exact: in particular, ranges touching either end of `0..n` are as likely
+/// as any other (which is easy to get wrong by sampling endpoints with
+/// `gen_range(0..n)`).
+///
+/// Samples may be taken as a half-open [`Range<usize>`] (e.g. for slicing a
+/// buffer) or as a [`RangeInclusive<usize>`] `a..=b - 1` of the same
+/// indices.
+///
+/// Sampling possibly-empty ranges is not supported directly; note however
+/// that mapping `a..b` to `a..b - 1` is a bijection from the non-empty
+/// sub-ranges of `0..n + 1` to all sub-ranges of `0..n`.
+///
+/// When `n <= u32::MAX`, endpoints are sampled as `u32`, thus results do not
+/// depend on the platform's pointer width.
+///
+/// # Example
+///
+/// ```
+/// use core::ops::Range;
+/// use rand::distributions::{Distribution, SubRange};
+///
+/// let buf = [1u8, 2, 3, 4, 5, 6, 7, 8];
+/// let mut rng = rand::thread_rng();
+///
+/// let range: Range<usize> = SubRange::new(buf.len()).unwrap().sample(&mut rng);
+/// assert!(!buf[range].is_empty());
+///
+/// let range: Range<usize> = SubRange::with_len(buf.len(), 3).unwrap().sample(&mut rng);
+/// assert_eq!(buf[range].len(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubRange {
+    n: usize,
+    len: Option<usize>,
+}
+
+/// Error type returned from [`SubRange::new`] and [`SubRange::with_len`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubRangeError {
+    /// `n == 0`: there are no non-empty sub-ranges.
+    EmptyRange,
+    /// `len == 0` or `len > n`.
+    InvalidLength,
+}
+
+impl fmt::Display for SubRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SubRangeError::EmptyRange => "n == 0 in SubRange",
+            SubRangeError::InvalidLength => "len == 0 or len > n in SubRange",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl ::std::error::Error for SubRangeError {}
+
+impl SubRange {
+    /// Construct, sampling uniformly from all non-empty sub-ranges of `0..n`.
+    ///
+    /// Fails with [`SubRangeError::EmptyRange`] if `n == 0`.
+    pub fn new(n: usize) -> Result<SubRange, SubRangeError> {
+        if n == 0 {
+            return Err(SubRangeError::EmptyRange);
+        }
+        Ok(SubRange { n, len: None })
+    }
+
+    /// Construct, sampling sub-ranges of `0..n` of length `len` at a
+    /// uniformly random offset.
+    ///
+    /// Fails with [`SubRangeError::InvalidLength`] unless `0 < len <= n`.
+    pub fn with_len(n: usize, len: usize) -> Result<SubRange, SubRangeError> {
+        if len == 0 || len > n {
+            return Err(SubRangeError::InvalidLength);
+        }
+        Ok(SubRange { n, len: Some(len) })
+    }
+
+    /// Returns `n`, the length of the full range.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+}
+
+impl Distribution<Range<usize>> for SubRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Range<usize> {
+        match self.len {
+            Some(len) => {
+                let start = gen_index_inclusive(rng, self.n - len);
+                start..start + len
+            }
+            None => loop {
+                // Each pair of distinct endpoints is sampled with equal
+                // probability; rejection happens with probability 1 / (n + 1).
+                let a = gen_index_inclusive(rng, self.n);
+                let b = gen_index_inclusive(rng, self.n);
+                if a < b {
+                    return a..b;
+                } else if b < a {
+                    return b..a;
+                }
+            },
+        }
+    }
+}
+
+impl Distribution<RangeInclusive<usize>> for SubRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RangeInclusive<usize> {
+        let range: Range<usize> = self.sample(rng);
+        range.start..=range.end - 1
+    }
+}
+
+// Sample from `0..=high`, as `u32` where possible
+fn gen_index_inclusive<R: Rng + ?Sized>(rng: &mut R, high: usize) -> usize {
+    if high <= u32::MAX as usize {
+        rng.gen_range(0..=high as u32) as usize
+    } else {
+        rng.gen_range(0..=high)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sub_range() {
+        assert_eq!(SubRange::new(0), Err(SubRangeError::EmptyRange));
+        assert_eq!(SubRange::with_len(3, 0), Err(SubRangeError::InvalidLength));
+        assert_eq!(SubRange::with_len(3, 4), Err(SubRangeError::InvalidLength));
+
+        let mut rng = crate::test::rng(256);
+        let r: Range<usize> = SubRange::new(1).unwrap().sample(&mut rng);
+        assert_eq!(r, 0..1);
+        let r: RangeInclusive<usize> = SubRange::with_len(5, 5).unwrap().sample(&mut rng);
+        assert_eq!(r, 0..=4);
+
+        // All 10 sub-ranges of 0..4 are sampled uniformly
+        let distr = SubRange::new(4).unwrap();
+        assert_eq!(distr.n(), 4);
+        let mut counts = [[0u32; 5]; 5];
+        let n = 10000;
+        for _ in 0..n {
+            let r: Range<usize> = distr.sample(&mut rng);
+            counts[r.start][r.end] += 1;
+        }
+        for (a, row) in counts.iter().enumerate() {
+            for (b, &c) in row.iter().enumerate() {
+                if a < b {
+                    assert!((c as f64 - n as f64 / 10.0).abs() < 100.0, "{}..{}: {}", a, b, c);
+                } else {
+                    assert_eq!(c, 0);
+                }
+            }
+        }
+
+        // Both extreme offsets occur for fixed lengths
+        let distr = SubRange::with_len(5, 3).unwrap();
+        let mut counts = [0u32; 3];
+        for _ in 0..300 {
+            let r: RangeInclusive<usize> = distr.sample(&mut rng);
+            assert_eq!(r.end() - r.start(), 2);
+            counts[*r.start()] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 50), "{:?}", counts);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(257);
+        let distr = SubRange::new(100).unwrap();
+        let mut buf = [0..0, 0..0, 0..0];
+        for x in buf.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [39..74, 7..69, 47..72]);
+        let r: RangeInclusive<usize> = SubRange::with_len(100, 10).unwrap().sample(&mut rng);
+        assert_eq!(r, 78..=87);
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Selection between distributions: the [`Switch`] combinator.
+
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+/// Sample from one of several distributions, chosen by another distribution.
+///
+/// Each sample first draws from the `selector`, then samples from the
+/// chosen distribution. This supports:
+///
+/// - two choices `(a, b)` with a `selector` implementing
+///   `Distribution<bool>` (e.g. [`Bernoulli`]): `a` is used if the selector
+///   yields `true`, `b` otherwise
+/// - any number of choices in an array `[D; N]` or (with `alloc`) a
+///   `Vec<D>`, with a `selector` implementing `Distribution<usize>` (e.g.
+///   [`WeightedIndex`] or [`Uniform`]) which yields the index of the choice
+///
+/// All choices must sample the same type `T`. Choices in an array or `Vec`
+/// must also be of the same type; nest `Switch`es to combine distributions
+/// of different types.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Bernoulli, Distribution, Switch, Uniform};
+///
+/// // Most customers buy a few items; a few buy many
+/// let basket = Switch::new(
+///     Bernoulli::new(0.9).unwrap(),
+///     (Uniform::new(1, 5).unwrap(), Uniform::new(20, 50).unwrap()),
+/// );
+/// let items: u32 = basket.sample(&mut rand::thread_rng());
+/// assert!((1..5).contains(&items) || (20..50).contains(&items));
+/// ```
+///
+/// # Panics
+///
+/// Sampling panics if the selector yields an index which is out of bounds.
+///
+/// [`Bernoulli`]: crate::distributions::Bernoulli
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+/// [`Uniform`]: crate::distributions::Uniform
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Switch<S, C> {
+    selector: S,
+    choices: C,
+}
+
+impl<S, C> Switch<S, C> {
+    /// Construct, sampling from one of `choices` as chosen by `selector`.
+    pub fn new(selector: S, choices: C) -> Self {
+        Switch { selector, choices }
+    }
+
+    /// Get the selector
+    pub fn selector(&self) -> &S {
+        &self.selector
+    }
+
+    /// Get the choices
+    pub fn choices(&self) -> &C {
+        &self.choices
+    }
+}
+
+impl<S, A, B, T> Distribution<T> for Switch<S, (A, B)>
+where
+    S: Distribution<bool>,
+    A: Distribution<T>,
+    B: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        if self.selector.sample(rng) {
+            self.choices.0.sample(rng)
+        } else {
+            self.choices.1.sample(rng)
+        }
+    }
+}
+
+impl<S, D, T, const N: usize> Distribution<T> for Switch<S, [D; N]>
+where
+    S: Distribution<usize>,
+    D: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.choices[self.selector.sample(rng)].sample(rng)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, D, T> Distribution<T> for Switch<S, Vec<D>>
+where
+    S: Distribution<usize>,
+    D: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.choices[self.selector.sample(rng)].sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::{Bernoulli, Uniform};
+
+    #[test]
+    fn test_two() {
+        let mut rng = crate::test::rng(230);
+        let distr = Switch::new(
+            Bernoulli::new(0.25).unwrap(),
+            (Uniform::new(0u32, 10).unwrap(), Uniform::new(100u32, 110).unwrap()),
+        );
+        let mut low = 0;
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!(x < 10 || (100..110).contains(&x));
+            low += (x < 10) as u32;
+        }
+        // Expect 250
+        assert!((200..300).contains(&low), "{}", low);
+    }
+
+    #[test]
+    fn test_array() {
+        let mut rng = crate::test::rng(231);
+        let distr = Switch::new(Uniform::new(0usize, 3).unwrap(), [
+            Uniform::new_inclusive(0u8, 0).unwrap(),
+            Uniform::new_inclusive(1u8, 1).unwrap(),
+            Uniform::new_inclusive(2u8, 2).unwrap(),
+        ]);
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[distr.sample(&mut rng) as usize] += 1;
+        }
+        // Expect 1000 of each
+        assert!(counts.iter().all(|c| (900..1100).contains(c)), "{:?}", counts);
+        assert_eq!(distr.choices().len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_vec() {
+        use crate::distributions::WeightedIndex;
+
+        let mut rng = crate::test::rng(232);
+        let choices = alloc::vec![
+            Uniform::new_inclusive(0u8, 0).unwrap(),
+            Uniform::new_inclusive(1u8, 1).unwrap(),
+        ];
+        let distr = Switch::new(WeightedIndex::new([0, 1]).unwrap(), choices);
+        assert!((0..100).all(|_| distr.sample(&mut rng) == 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_bounds() {
+        let mut rng = crate::test::rng(233);
+        let distr = Switch::new(Uniform::new_inclusive(5usize, 5).unwrap(), [
+            Uniform::new(0u8, 1).unwrap(),
+        ]);
+        distr.sample(&mut rng);
+    }
+}
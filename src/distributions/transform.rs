@@ -0,0 +1,234 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Numeric transformations of distributions: [`Clamp`], [`Shift`] and
+//! [`Scale`].
+//!
+//! Unlike [`Distribution::map`], these adapters are nameable types which may
+//! be stored, compared and (with the `serde1` feature) serialized.
+//!
+//! ```
+//! use rand::distributions::{Distribution, Scale, Shift, Uniform};
+//!
+//! // Multiples of 5 in [100, 140)
+//! let grid = Shift::new(Scale::new(Uniform::new(0, 8).unwrap(), 5), 100);
+//! let x: i32 = grid.sample(&mut rand::thread_rng());
+//! assert!(x % 5 == 0 && (100..140).contains(&x));
+//! ```
+
+use core::fmt;
+use core::ops::{Add, Mul};
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// Clamp samples of a distribution `D` to the closed interval `[min, max]`.
+///
+/// Samples below `min` yield `min` and samples above `max` yield `max`;
+/// samples which are not comparable with the bounds (e.g. `NaN`) are passed
+/// through unchanged.
+///
+/// Note that clamping moves all probability mass outside of the interval
+/// onto its bounds. To restrict a distribution to an interval while keeping
+/// the shape of its density (i.e. to *condition* on the interval), resample
+/// values outside of it instead.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Clamp, Distribution, Uniform};
+///
+/// // Half of all samples are exactly zero
+/// let d = Clamp::new(Uniform::new(-1.0, 1.0).unwrap(), 0.0, f64::INFINITY).unwrap();
+/// let x = d.sample(&mut rand::thread_rng());
+/// assert!(x >= 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Clamp<D, T> {
+    distr: D,
+    min: T,
+    max: T,
+}
+
+/// Error type returned from [`Clamp::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClampError {
+    /// `min > max`, or the bounds are not comparable (e.g. `NaN`).
+    InvalidBounds,
+}
+
+impl fmt::Display for ClampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ClampError::InvalidBounds => "min > max or bounds are not comparable in Clamp",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl ::std::error::Error for ClampError {}
+
+impl<D, T: PartialOrd> Clamp<D, T> {
+    /// Construct, clamping samples of `distr` to `[min, max]`.
+    ///
+    /// Either bound may be infinite in order to clamp from one side only.
+    pub fn new(distr: D, min: T, max: T) -> Result<Self, ClampError> {
+        if !(min <= max) {
+            return Err(ClampError::InvalidBounds);
+        }
+        Ok(Clamp { distr, min, max })
+    }
+
+    /// Get the inner distribution
+    pub fn inner(&self) -> &D {
+        &self.distr
+    }
+}
+
+impl<D, T> Distribution<T> for Clamp<D, T>
+where
+    D: Distribution<T>,
+    T: PartialOrd + Clone,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let x = self.distr.sample(rng);
+        if x < self.min {
+            self.min.clone()
+        } else if x > self.max {
+            self.max.clone()
+        } else {
+            x
+        }
+    }
+}
+
+/// Shift samples of a distribution `D` by adding `offset`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Shift, Uniform};
+///
+/// let d = Shift::new(Uniform::new(0u32, 6).unwrap(), 1);
+/// let roll = d.sample(&mut rand::thread_rng());
+/// assert!((1..=6).contains(&roll));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Shift<D, T> {
+    distr: D,
+    offset: T,
+}
+
+impl<D, T> Shift<D, T> {
+    /// Construct, adding `offset` to samples of `distr`.
+    pub fn new(distr: D, offset: T) -> Self {
+        Shift { distr, offset }
+    }
+
+    /// Get the inner distribution
+    pub fn inner(&self) -> &D {
+        &self.distr
+    }
+}
+
+impl<D, T, X> Distribution<T> for Shift<D, X>
+where
+    D: Distribution<X>,
+    X: Add<Output = T> + Clone,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.distr.sample(rng) + self.offset.clone()
+    }
+}
+
+/// Scale samples of a distribution `D` by multiplying with `factor`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Scale, Standard};
+///
+/// // Uniform over [0, 2π)
+/// let d = Scale::new(Standard, core::f64::consts::TAU);
+/// let angle: f64 = d.sample(&mut rand::thread_rng());
+/// assert!((0.0..core::f64::consts::TAU).contains(&angle));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Scale<D, T> {
+    distr: D,
+    factor: T,
+}
+
+impl<D, T> Scale<D, T> {
+    /// Construct, multiplying samples of `distr` with `factor`.
+    pub fn new(distr: D, factor: T) -> Self {
+        Scale { distr, factor }
+    }
+
+    /// Get the inner distribution
+    pub fn inner(&self) -> &D {
+        &self.distr
+    }
+}
+
+impl<D, T, X> Distribution<T> for Scale<D, X>
+where
+    D: Distribution<X>,
+    X: Mul<Output = T> + Clone,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.distr.sample(rng) * self.factor.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::Uniform;
+
+    #[test]
+    fn test_clamp() {
+        let mut rng = crate::test::rng(228);
+        let d = Clamp::new(Uniform::new(-10i32, 10).unwrap(), -2, 3).unwrap();
+        let mut counts = [0; 6];
+        for _ in 0..1000 {
+            let x = d.sample(&mut rng);
+            counts[(x + 2) as usize] += 1;
+        }
+        // The bounds are taken far more often than interior values
+        assert!(counts[0] > 2 * counts[1] && counts[5] > 2 * counts[4]);
+
+        let d = Clamp::new(Uniform::new(0.0, 1.0).unwrap(), f64::NEG_INFINITY, 0.5).unwrap();
+        assert!((0..100).all(|_| d.sample(&mut rng) <= 0.5));
+
+        assert!(Clamp::new(d, 1.0, 1.0).is_ok());
+        assert_eq!(Clamp::new(d, 1.0, 0.0), Err(ClampError::InvalidBounds));
+        assert_eq!(Clamp::new(d, f64::NAN, 0.0), Err(ClampError::InvalidBounds));
+    }
+
+    #[test]
+    fn test_shift_scale() {
+        let mut rng = crate::test::rng(229);
+        let d = Shift::new(Scale::new(Uniform::new(0u8, 4).unwrap(), 10u8), 1u8);
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!([1, 11, 21, 31].contains(&x));
+        }
+        assert_eq!(d.inner().inner(), &Uniform::new(0u8, 4).unwrap());
+
+        let d = Scale::new(Uniform::new(1.0, 2.0).unwrap(), -1.0);
+        assert!((0..100).all(|_| (-2.0..=-1.0).contains(&d.sample(&mut rng))));
+    }
+}
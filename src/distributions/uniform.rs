@@ -18,10 +18,17 @@
 //! and supports extension to user-defined types via a type-specific *back-end*
 //! implementation.
 //!
+//! Arrays and tuples (of up to four elements) of supported types are also
+//! supported, sampling each element independently from its own range. For
+//! example, `rng.gen_range([0.0, 0.0]..[1.0, 2.0])` samples a point from the
+//! rectangle `[0, 1) × [0, 2)`.
+//!
 //! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
 //! back-ends supporting sampling from primitive integer and floating-point
 //! ranges as well as from [`std::time::Duration`]; these types do not normally
 //! need to be used directly (unless implementing a derived back-end).
+//! Similarly, [`UniformNonZero`] supports ranges of non-zero unsigned
+//! integers such as [`NonZeroU32`].
 //!
 //! # Example usage
 //!
@@ -46,6 +53,9 @@
 //! implements the [`UniformSampler`] trait, then implement the [`SampleUniform`]
 //! helper trait to "register" your back-end. See the `MyF32` example below.
 //!
+//! For newtypes wrapping a supported type, [`UniformNewtype`] provides such
+//! a back-end, requiring only conversions between the two types.
+//!
 //! At a minimum, the back-end needs to store any parameters needed for sampling
 //! (e.g. the target range) and implement `new`, `new_inclusive` and `sample`.
 //! Those methods should include an assertion to check the range is valid (i.e.
@@ -101,12 +111,17 @@
 //! [`UniformInt`]: crate::distributions::uniform::UniformInt
 //! [`UniformFloat`]: crate::distributions::uniform::UniformFloat
 //! [`UniformDuration`]: crate::distributions::uniform::UniformDuration
+//! [`UniformNonZero`]: crate::distributions::uniform::UniformNonZero
+//! [`UniformNewtype`]: crate::distributions::uniform::UniformNewtype
 //! [`SampleBorrow::borrow`]: crate::distributions::uniform::SampleBorrow::borrow
 
 use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
 use core::time::Duration;
 use core::ops::{Range, RangeInclusive};
 use core::convert::TryFrom;
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 
 use crate::distributions::float::IntoFloat;
 use crate::distributions::utils::{BoolAsSIMD, FloatAsSIMD, FloatSIMDUtils, IntAsSIMD, WideningMultiply};
@@ -192,6 +207,27 @@ use serde::{Serialize, Deserialize};
 /// println!("{}", rng.gen_range(0..10));
 /// ```
 ///
+/// For integer types, [`Uniform::new_inclusive_const`] may be used to
+/// construct samplers at compile time:
+///
+/// ```
+/// use rand::distributions::{Distribution, Uniform};
+///
+/// const DICE: [Uniform<u8>; 3] = {
+///     const fn die(sides: u8) -> Uniform<u8> {
+///         match Uniform::<u8>::new_inclusive_const(1, sides) {
+///             Ok(die) => die,
+///             Err(_) => panic!("invalid die"),
+///         }
+///     }
+///     [die(4), die(6), die(20)]
+/// };
+///
+/// let mut rng = rand::thread_rng();
+/// let total: u32 = DICE.iter().map(|d| u32::from(d.sample(&mut rng))).sum();
+/// assert!((3..=30).contains(&total));
+/// ```
+///
 /// [`new`]: Uniform::new
 /// [`new_inclusive`]: Uniform::new_inclusive
 /// [`Rng::gen_range`]: Rng::gen_range
@@ -409,6 +445,32 @@ impl<T: SampleUniform + PartialOrd> SampleRange<T> for RangeInclusive<T> {
     }
 }
 
+/// Ranges of references sample without copying or cloning their bounds,
+/// e.g. for big integer types.
+impl<T: SampleUniform + PartialOrd> SampleRange<T> for Range<&T> {
+    #[inline]
+    fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<T, Error> {
+        T::Sampler::sample_single(self.start, self.end, rng)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !(self.start < self.end)
+    }
+}
+
+impl<T: SampleUniform + PartialOrd> SampleRange<T> for RangeInclusive<&T> {
+    #[inline]
+    fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<T, Error> {
+        T::Sampler::sample_single_inclusive(*self.start(), *self.end(), rng)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !(self.start() <= self.end())
+    }
+}
+
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -436,9 +498,9 @@ impl<T: SampleUniform + PartialOrd> SampleRange<T> for RangeInclusive<T> {
 ///
 /// The optimum `zone` is the largest product of `range` which fits in our
 /// (unsigned) target type. We calculate this by calculating how many numbers we
-/// must reject: `reject = (MAX + 1) % range = (MAX - range + 1) % range`. Any (large)
-/// product of `range` will suffice, thus in `sample_single` we multiply by a
-/// power of 2 via bit-shifting (faster but may cause more rejections).
+/// must reject: `reject = (MAX + 1) % range = (MAX - range + 1) % range`. In
+/// `sample_single` this modulus is only computed when a sample might be
+/// rejected, i.e. when the low word of the product is less than `range`.
 ///
 /// The smallest integer PRNGs generate is `u32`. For 8- and 16-bit outputs we
 /// use `u32` for our `zone` and samples (because it's not slower and because
@@ -463,6 +525,44 @@ macro_rules! uniform_int_impl {
             type Sampler = UniformInt<$ty>;
         }
 
+        impl UniformInt<$ty> {
+            /// Construct a sampler over the closed range `[low, high]`, as
+            /// [`UniformSampler::new_inclusive`], in a `const` context.
+            pub const fn new_inclusive_const(low: $ty, high: $ty) -> Result<Self, Error> {
+                if !(low <= high) {
+                    return Err(Error::EmptyRange);
+                }
+
+                let range = high.wrapping_sub(low).wrapping_add(1) as $uty;
+                let thresh = if range > 0 {
+                    let range = range as $sample_ty;
+                    (range.wrapping_neg() % range)
+                } else {
+                    0
+                };
+
+                Ok(UniformInt {
+                    low,
+                    range: range as $ty, // type: $uty
+                    thresh: thresh as $uty as $ty, // type: $sample_ty
+                })
+            }
+        }
+
+        impl Uniform<$ty> {
+            /// Create a new `Uniform` instance over the closed range
+            /// `[low, high]`, as [`Uniform::new_inclusive`], in a `const`
+            /// context.
+            ///
+            /// This is only available for integer types.
+            pub const fn new_inclusive_const(low: $ty, high: $ty) -> Result<Self, Error> {
+                match UniformInt::<$ty>::new_inclusive_const(low, high) {
+                    Ok(sampler) => Ok(Uniform(sampler)),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+
         impl UniformSampler for UniformInt<$ty> {
             // We play free and fast with unsigned vs signed here
             // (when $ty is signed), but that's fine, since the
@@ -493,25 +593,7 @@ macro_rules! uniform_int_impl {
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
-                let low = *low_b.borrow();
-                let high = *high_b.borrow();
-                if !(low <= high) {
-                    return Err(Error::EmptyRange);
-                }
-
-                let range = high.wrapping_sub(low).wrapping_add(1) as $uty;
-                let thresh = if range > 0 {
-                    let range = $sample_ty::from(range);
-                    (range.wrapping_neg() % range)
-                } else {
-                    0
-                };
-
-                Ok(UniformInt {
-                    low,
-                    range: range as $ty, // type: $uty
-                    thresh: thresh as $uty as $ty, // type: $sample_ty
-                })
+                Self::new_inclusive_const(*low_b.borrow(), *high_b.borrow())
             }
 
             /// Sample from distribution, Lemire's method, unbiased
@@ -546,11 +628,10 @@ macro_rules! uniform_int_impl {
                 Self::sample_single_inclusive(low, high - 1, rng)
             }
 
-            /// Sample single value, Canon's method, biased
+            /// Sample single value, Lemire's method, unbiased
             ///
-            /// In the worst case, bias affects 1 in `2^n` samples where n is
-            /// 56 (`i8`), 48 (`i16`), 96 (`i32`), 64 (`i64`), 128 (`i128`).
-            #[cfg(not(feature = "unbiased"))]
+            /// The rejection threshold (which costs a division) is only
+            /// computed when the first sample might be rejected.
             #[inline]
             fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(
                 low_b: B1, high_b: B2, rng: &mut R,
@@ -570,66 +651,17 @@ macro_rules! uniform_int_impl {
                     return Ok(rng.gen());
                 }
 
-                // generate a sample using a sensible integer type
-                let (mut result, lo_order) = rng.gen::<$sample_ty>().wmul(range);
-
-                // if the sample is biased...
-                if lo_order > range.wrapping_neg() {
-                    // ...generate a new sample to reduce bias...
-                    let (new_hi_order, _) = (rng.gen::<$sample_ty>()).wmul(range as $sample_ty);
-                    // ... incrementing result on overflow
-                    let is_overflow = lo_order.checked_add(new_hi_order as $sample_ty).is_none();
-                    result += is_overflow as $sample_ty;
-                }
-
-                Ok(low.wrapping_add(result as $ty))
-            }
-
-            /// Sample single value, Canon's method, unbiased
-            #[cfg(feature = "unbiased")]
-            #[inline]
-            fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(
-                low_b: B1, high_b: B2, rng: &mut R,
-            ) -> Result<Self::X, Error>
-            where
-                B1: SampleBorrow<$ty> + Sized,
-                B2: SampleBorrow<$ty> + Sized,
-            {
-                let low = *low_b.borrow();
-                let high = *high_b.borrow();
-                if !(low <= high) {
-                    return Err(Error::EmptyRange);
-                }
-                let range = high.wrapping_sub(low).wrapping_add(1) as $uty as $sample_ty;
-                if range == 0 {
-                    // Range is MAX+1 (unrepresentable), so we need a special case
-                    return Ok(rng.gen());
-                }
-
-                let (mut result, mut lo) = rng.gen::<$sample_ty>().wmul(range);
-
-                // In constrast to the biased sampler, we use a loop:
-                while lo > range.wrapping_neg() {
-                    let (new_hi, new_lo) = (rng.gen::<$sample_ty>()).wmul(range);
-                    match lo.checked_add(new_hi) {
-                        Some(x) if x < $sample_ty::MAX => {
-                            // Anything less than MAX: last term is 0
-                            break;
-                        }
-                        None => {
-                            // Overflow: last term is 1
-                            result += 1;
-                            break;
-                        }
-                        _ => {
-                            // Unlikely case: must check next sample
-                            lo = new_lo;
-                            continue;
-                        }
+                let (mut hi, mut lo) = rng.gen::<$sample_ty>().wmul(range);
+                // The threshold is less than range, thus larger lo are accepted
+                if lo < range {
+                    let thresh = range.wrapping_neg() % range;
+                    while lo < thresh {
+                        let (new_hi, new_lo) = rng.gen::<$sample_ty>().wmul(range);
+                        hi = new_hi;
+                        lo = new_lo;
                     }
                 }
-
-                Ok(low.wrapping_add(result as $ty))
+                Ok(low.wrapping_add(hi as $ty))
             }
         }
     };
@@ -648,6 +680,61 @@ uniform_int_impl! { u64, u64, u64 }
 uniform_int_impl! { usize, usize, usize }
 uniform_int_impl! { u128, u128, u128 }
 
+macro_rules! uniform_small_int_impl {
+    ($ty:ty, $uty:ty, $lane:ty) => {
+        impl UniformInt<$ty> {
+            /// Fill `dest` with independent samples from the distribution.
+            ///
+            /// This is faster than sampling each element individually: each
+            /// `u64` drawn from `rng` is split into lanes of
+            #[doc = concat!("`", stringify!($lane), "`")]
+            /// and each lane yields one sample (or is rejected, in order to
+            /// avoid bias). The results are thus not the same as those of
+            /// repeated calls to [`UniformSampler::sample`].
+            pub fn fill<R: Rng + ?Sized>(&self, rng: &mut R, dest: &mut [$ty]) {
+                let range = self.range as $uty as $lane;
+                if range == 0 {
+                    return rng.fill(dest);
+                }
+
+                const LANES: usize = 64 / <$lane>::BITS as usize;
+                let thresh = range.wrapping_neg() % range;
+                let mut i = 0;
+                while i < dest.len() {
+                    let mut word = rng.next_u64();
+                    for _ in 0..LANES {
+                        let (hi, lo) = (word as $lane).wmul(range);
+                        word >>= <$lane>::BITS;
+                        if lo >= thresh {
+                            dest[i] = self.low.wrapping_add(hi as $ty);
+                            i += 1;
+                            if i == dest.len() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl Uniform<$ty> {
+            /// Fill `dest` with independent samples from the distribution.
+            ///
+            /// See [`UniformInt::fill`]: this batches several samples per
+            /// random `u64`, and does not produce the same values as
+            /// repeated calls to [`Distribution::sample`].
+            pub fn fill<R: Rng + ?Sized>(&self, rng: &mut R, dest: &mut [$ty]) {
+                self.0.fill(rng, dest)
+            }
+        }
+    };
+}
+
+uniform_small_int_impl! { i8, u8, u16 }
+uniform_small_int_impl! { u8, u8, u16 }
+uniform_small_int_impl! { i16, u16, u32 }
+uniform_small_int_impl! { u16, u16, u32 }
+
 #[cfg(feature = "simd_support")]
 macro_rules! uniform_simd_int_impl {
     ($ty:ident, $unsigned:ident) => {
@@ -1195,11 +1282,343 @@ impl UniformSampler for UniformDuration {
     }
 }
 
+// Compound types: each element is sampled independently from its own range.
+
+/// Fill an array from a fallible function of the index
+fn try_array_from_fn<T, const N: usize, F>(mut f: F) -> Result<[T; N], Error>
+where F: FnMut(usize) -> Result<T, Error>
+{
+    // On error, previously constructed elements are leaked (not dropped)
+    let mut buf: [MaybeUninit<T>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+    for (i, elem) in buf.iter_mut().enumerate() {
+        *elem = MaybeUninit::new(f(i)?);
+    }
+    Ok(unsafe { mem::transmute_copy::<_, _>(&buf) })
+}
+
+impl<T: SampleUniform, const N: usize> SampleUniform for [T; N] {
+    type Sampler = [T::Sampler; N];
+}
+
+/// Samples arrays uniformly within an axis-aligned box: element `i` is
+/// sampled from the range `low[i]..high[i]`.
+///
+/// Construction fails if any of these ranges is invalid.
+impl<S, const N: usize> UniformSampler for [S; N]
+where
+    S: UniformSampler,
+    S::X: SampleUniform,
+{
+    type X = [S::X; N];
+
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low_b.borrow(), high_b.borrow());
+        try_array_from_fn(|i| S::new(&low[i], &high[i]))
+    }
+
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low_b.borrow(), high_b.borrow());
+        try_array_from_fn(|i| S::new_inclusive(&low[i], &high[i]))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        let mut buf: [MaybeUninit<S::X>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+        for (elem, sampler) in buf.iter_mut().zip(self.iter()) {
+            *elem = MaybeUninit::new(sampler.sample(rng));
+        }
+        unsafe { mem::transmute_copy::<_, _>(&buf) }
+    }
+
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R) -> Result<Self::X, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low_b.borrow(), high_b.borrow());
+        try_array_from_fn(|i| S::sample_single(&low[i], &high[i], rng))
+    }
+
+    fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R)
+        -> Result<Self::X, Error>
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let (low, high) = (low_b.borrow(), high_b.borrow());
+        try_array_from_fn(|i| S::sample_single_inclusive(&low[i], &high[i], rng))
+    }
+}
+
+macro_rules! uniform_tuple_impl {
+    ($($T:ident $S:ident $i:tt),+) => {
+        impl<$($T: SampleUniform),+> SampleUniform for ($($T,)+) {
+            type Sampler = ($($T::Sampler,)+);
+        }
+
+        /// Samples tuples uniformly within an axis-aligned box: element `i`
+        /// is sampled from the range `low.i..high.i`.
+        impl<$($S),+> UniformSampler for ($($S,)+)
+        where
+            $($S: UniformSampler, $S::X: SampleUniform,)+
+        {
+            type X = ($($S::X,)+);
+
+            fn new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let (low, high) = (low_b.borrow(), high_b.borrow());
+                Ok(($($S::new(&low.$i, &high.$i)?,)+))
+            }
+
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let (low, high) = (low_b.borrow(), high_b.borrow());
+                Ok(($($S::new_inclusive(&low.$i, &high.$i)?,)+))
+            }
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                ($(self.$i.sample(rng),)+)
+            }
+
+            fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R) -> Result<Self::X, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let (low, high) = (low_b.borrow(), high_b.borrow());
+                Ok(($($S::sample_single(&low.$i, &high.$i, rng)?,)+))
+            }
+
+            fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R)
+                -> Result<Self::X, Error>
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                let (low, high) = (low_b.borrow(), high_b.borrow());
+                Ok(($($S::sample_single_inclusive(&low.$i, &high.$i, rng)?,)+))
+            }
+        }
+    }
+}
+
+uniform_tuple_impl! {A SA 0}
+uniform_tuple_impl! {A SA 0, B SB 1}
+uniform_tuple_impl! {A SA 0, B SB 1, C SC 2}
+uniform_tuple_impl! {A SA 0, B SB 1, C SC 2, D SD 3}
+
+/// The back-end for newtypes, sampling via conversion from and into the
+/// wrapped type `U`.
+///
+/// Sampling a range of `X` samples the corresponding range of `U` (as
+/// converted via `Into<U>`), then converts the result via `From<U>`. The
+/// conversions must preserve the order of values. This allows newtypes
+/// around integers or floats, such as IDs or fixed-point numbers, to support
+/// [`Uniform`] and [`Rng::gen_range`] with a single line:
+///
+/// ```
+/// use rand::distributions::uniform::{SampleUniform, UniformNewtype};
+/// use rand::Rng;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// struct UserId(u32);
+///
+/// impl From<u32> for UserId {
+///     fn from(id: u32) -> Self {
+///         UserId(id)
+///     }
+/// }
+///
+/// impl From<UserId> for u32 {
+///     fn from(id: UserId) -> Self {
+///         id.0
+///     }
+/// }
+///
+/// impl SampleUniform for UserId {
+///     type Sampler = UniformNewtype<UserId, u32>;
+/// }
+///
+/// let id = rand::thread_rng().gen_range(UserId(1000)..UserId(2000));
+/// assert!((1000..2000).contains(&id.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(bound(serialize = "U::Sampler: Serialize")))]
+#[cfg_attr(feature = "serde1", serde(bound(deserialize = "U::Sampler: Deserialize<'de>")))]
+pub struct UniformNewtype<X, U: SampleUniform> {
+    inner: U::Sampler,
+    phantom: PhantomData<fn() -> X>,
+}
+
+impl<X, U> UniformSampler for UniformNewtype<X, U>
+where
+    X: Clone + Into<U> + From<U>,
+    U: SampleUniform,
+{
+    type X = X;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (U, U) = (low.borrow().clone().into(), high.borrow().clone().into());
+        Ok(UniformNewtype {
+            inner: U::Sampler::new(low, high)?,
+            phantom: PhantomData,
+        })
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (U, U) = (low.borrow().clone().into(), high.borrow().clone().into());
+        Ok(UniformNewtype {
+            inner: U::Sampler::new_inclusive(low, high)?,
+            phantom: PhantomData,
+        })
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        X::from(self.inner.sample(rng))
+    }
+
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Result<Self::X, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (U, U) = (low.borrow().clone().into(), high.borrow().clone().into());
+        U::Sampler::sample_single(low, high, rng).map(X::from)
+    }
+
+    fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R)
+        -> Result<Self::X, Error>
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let (low, high): (U, U) = (low.borrow().clone().into(), high.borrow().clone().into());
+        U::Sampler::sample_single_inclusive(low, high, rng).map(X::from)
+    }
+}
+
+/// The back-end implementing [`UniformSampler`] for non-zero integers.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this
+/// type should not be used directly, use [`Uniform`] instead.
+///
+/// A range of non-zero integers is sampled as the corresponding range of the
+/// underlying integer type `U` (via [`UniformInt`]), which never contains
+/// zero; no rejection of zero is required.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct UniformNonZero<U>(UniformInt<U>);
+
+macro_rules! uniform_non_zero_impl {
+    ($ty:ty, $u:ty) => {
+        impl SampleUniform for $ty {
+            type Sampler = UniformNonZero<$u>;
+        }
+
+        impl UniformSampler for UniformNonZero<$u> {
+            type X = $ty;
+
+            #[inline]
+            fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<$u>::new(low.borrow().get(), high.borrow().get()).map(UniformNonZero)
+            }
+
+            #[inline]
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<$u>::new_inclusive(low.borrow().get(), high.borrow().get())
+                    .map(UniformNonZero)
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                // The range excludes zero since its bounds are non-zero
+                <$ty>::new(self.0.sample(rng)).unwrap()
+            }
+
+            #[inline]
+            fn sample_single<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Result<Self::X, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let x = UniformInt::<$u>::sample_single(low.borrow().get(), high.borrow().get(), rng)?;
+                Ok(<$ty>::new(x).unwrap())
+            }
+
+            #[inline]
+            fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R)
+                -> Result<Self::X, Error>
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                let x = UniformInt::<$u>::sample_single_inclusive(
+                    low.borrow().get(), high.borrow().get(), rng)?;
+                Ok(<$ty>::new(x).unwrap())
+            }
+        }
+    };
+}
+
+uniform_non_zero_impl! { NonZeroU8, u8 }
+uniform_non_zero_impl! { NonZeroU16, u16 }
+uniform_non_zero_impl! { NonZeroU32, u32 }
+uniform_non_zero_impl! { NonZeroU64, u64 }
+uniform_non_zero_impl! { NonZeroU128, u128 }
+uniform_non_zero_impl! { NonZeroUsize, usize }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rngs::mock::StepRng;
 
+    #[test]
+    fn test_non_zero() {
+        use core::num::{NonZeroU64, NonZeroU8};
+        let mut rng = crate::test::rng(275);
+        let nz8 = |x| NonZeroU8::new(x).unwrap();
+        let distr = Uniform::new_inclusive(nz8(1), nz8(3)).unwrap();
+        let mut counts = [0u32; 4];
+        for _ in 0..3000 {
+            counts[distr.sample(&mut rng).get() as usize] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert!(counts[1..].iter().all(|&c| (c as f64 - 1000.0).abs() < 120.0), "{:?}", counts);
+
+        let x = rng.gen_range(nz8(200)..=nz8(u8::MAX));
+        assert!(x.get() >= 200);
+        let nz64 = |x| NonZeroU64::new(x).unwrap();
+        let x = rng.gen_range(nz64(1)..nz64(2));
+        assert_eq!(x.get(), 1);
+        assert_eq!(Uniform::new(nz8(5), nz8(5)), Err(Error::EmptyRange));
+    }
+
     #[test]
     #[cfg(feature = "serde1")]
     fn test_serialization_uniform_duration() {
@@ -1234,6 +1653,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_uniform_new_inclusive_const() {
+        const D6: Result<Uniform<u8>, Error> = Uniform::<u8>::new_inclusive_const(1, 6);
+        const FULL: Result<Uniform<i64>, Error> = Uniform::<i64>::new_inclusive_const(i64::MIN, i64::MAX);
+        assert_eq!(D6, Uniform::new_inclusive(1, 6));
+        assert_eq!(FULL, Uniform::new_inclusive(i64::MIN, i64::MAX));
+        assert_eq!(Uniform::<i32>::new_inclusive_const(-3, 100), Uniform::new_inclusive(-3, 100));
+        assert_eq!(Uniform::<u128>::new_inclusive_const(5, 4), Err(Error::EmptyRange));
+    }
+
+    #[test]
+    fn test_uniform_array() {
+        let mut rng = crate::test::rng(219);
+        for _ in 0..100 {
+            let [x, y] = rng.gen_range([0.0, -1.0]..[1.0, 2.0]);
+            assert!((0.0..1.0).contains(&x) && (-1.0..2.0).contains(&y));
+            let v: [u8; 3] = rng.gen_range([1, 10, 100]..=[1, 20, 200]);
+            assert!(v[0] == 1 && (10..=20).contains(&v[1]) && (100..=200).contains(&v[2]));
+        }
+
+        let distr = Uniform::new([0u32, 5], [10, 6]).unwrap();
+        for _ in 0..100 {
+            let [x, y] = distr.sample(&mut rng);
+            assert!(x < 10 && y == 5);
+        }
+
+        // Any empty axis is an error, even if the bounds are ordered
+        assert_eq!(Uniform::new([0, 0], [1, 0]), Err(Error::EmptyRange));
+        assert_eq!(Uniform::new_inclusive([0.0, 1.0], [1.0, 0.0]), Err(Error::EmptyRange));
+        assert!(Uniform::<[f64; 0]>::new([], []).is_ok());
+    }
+
+    #[test]
+    fn test_uniform_tuple() {
+        let mut rng = crate::test::rng(220);
+        for _ in 0..100 {
+            let (a, b, c) = rng.gen_range((0u8, -5i64, 0.5f32)..(3, 5, 1.0));
+            assert!(a < 3 && (-5..5).contains(&b) && (0.5..1.0).contains(&c));
+            let (d,) = rng.gen_range((7u16,)..=(7,));
+            assert_eq!(d, 7);
+        }
+
+        let distr = Uniform::new_inclusive((0u32, 1.0, 'a', 0usize), (0, 2.0, 'z', 9)).unwrap();
+        let (a, b, c, d) = distr.sample(&mut rng);
+        assert!(a == 0 && (1.0..=2.0).contains(&b) && c.is_ascii_lowercase() && d <= 9);
+        assert_eq!(Uniform::new((0, 1), (1, 1)), Err(Error::EmptyRange));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_array_empty_axis() {
+        // The bounds are ordered lexicographically but the second axis is empty
+        crate::test::rng(221).gen_range([0, 5]..[1, 5]);
+    }
+
     #[test]
     fn test_uniform_bad_limits_flipped_int() {
         assert_eq!(Uniform::new(10, 5), Err(Error::EmptyRange));
@@ -1325,6 +1799,75 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_integers_unbiased() {
+        let mut rng = crate::test::rng(277);
+
+        // Each method is uniform over a small range
+        macro_rules! t {
+            ($($ty:ident),*) => {{
+                $(
+                    let uniform = Uniform::<$ty>::new(0, 6).unwrap();
+                    let mut counts = [[0u32; 6]; 3];
+                    for _ in 0..6000 {
+                        type Sampler = <$ty as SampleUniform>::Sampler;
+                        let a: $ty = rng.sample(uniform);
+                        let b = Sampler::sample_single(0, 6, &mut rng).unwrap();
+                        let c = Sampler::sample_single_inclusive(0, 5, &mut rng).unwrap();
+                        counts[0][a as usize] += 1;
+                        counts[1][b as usize] += 1;
+                        counts[2][c as usize] += 1;
+                    }
+                    for &c in counts.iter().flatten() {
+                        let ty = stringify!($ty);
+                        assert!((c as f64 - 1000.0).abs() < 150.0, "{}: {:?}", ty, counts);
+                    }
+                )*
+            }};
+        }
+        t!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, i128, u128);
+
+        // With range 3 * 2^62, reducing a u64 modulo the range would give
+        // results below 2^62 with probability 1/2 rather than 1/3
+        let high = 3 << 62;
+        let uniform = Uniform::new(0u64, high).unwrap();
+        let n = 6000;
+        let mut low_counts = [0u32; 2];
+        for _ in 0..n {
+            low_counts[0] += (rng.sample(uniform) < 1 << 62) as u32;
+            let x = <u64 as SampleUniform>::Sampler::sample_single(0, high, &mut rng).unwrap();
+            low_counts[1] += (x < 1 << 62) as u32;
+        }
+        for &c in &low_counts {
+            assert!((c as f64 - 2000.0).abs() < 150.0, "{:?}", low_counts);
+        }
+
+        // Given the same random numbers, single samples are exactly those of
+        // the distribution, including for ranges rejecting a quarter of the
+        // random numbers
+        macro_rules! t {
+            ($ty:ident, $low:expr, $high:expr) => {{
+                let uniform = Uniform::<$ty>::new_inclusive($low, $high).unwrap();
+                let (mut rng1, mut rng2) = (crate::test::rng(278), crate::test::rng(278));
+                for _ in 0..1000 {
+                    let x = <$ty as SampleUniform>::Sampler::sample_single_inclusive(
+                        $low, $high, &mut rng1,
+                    )
+                    .unwrap();
+                    assert_eq!(x, rng2.sample(uniform));
+                }
+                assert_eq!(rng1.next_u64(), rng2.next_u64());
+            }};
+        }
+        t!(u8, 0, 5);
+        t!(i16, -300, 20000);
+        t!(u32, 0, (3 << 30) - 1);
+        t!(i32, i32::MIN, i32::MAX);
+        t!(u64, 1, 3 << 62);
+        t!(i128, -1, i128::MAX);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_char() {
@@ -1588,6 +2131,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_non_copy_uniform() {
+        use crate::distributions::uniform::{
+            SampleBorrow, SampleUniform, UniformInt, UniformSampler,
+        };
+        // Not `Copy` or `Clone`, like a big integer type
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct MyU64 {
+            x: u64,
+        }
+        #[derive(Clone, Copy, Debug)]
+        struct UniformMyU64(UniformInt<u64>);
+        impl UniformSampler for UniformMyU64 {
+            type X = MyU64;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<u64>::new(low.borrow().x, high.borrow().x).map(UniformMyU64)
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<u64>::new_inclusive(low.borrow().x, high.borrow().x).map(UniformMyU64)
+            }
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                MyU64 {
+                    x: self.0.sample(rng),
+                }
+            }
+        }
+        impl SampleUniform for MyU64 {
+            type Sampler = UniformMyU64;
+        }
+
+        let (low, high) = (MyU64 { x: 17 }, MyU64 { x: 22 });
+        let mut rng = crate::test::rng(805);
+        let uniform = Uniform::new(&low, &high).unwrap();
+        for _ in 0..100 {
+            let x: MyU64 = rng.sample(&uniform);
+            assert!(low <= x && x < high);
+            let x: MyU64 = rng.gen_range(&low..&high);
+            assert!(low <= x && x < high);
+            let x: MyU64 = rng.gen_range(&low..=&high);
+            assert!(low <= x && x <= high);
+        }
+        assert_eq!(rng.gen_range(&low..=&low), low);
+    }
+
+    #[test]
+    fn test_newtype_uniform() {
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+        struct Fixed(i32); // 16.16 fixed-point
+        impl From<i32> for Fixed {
+            fn from(x: i32) -> Self {
+                Fixed(x)
+            }
+        }
+        impl From<Fixed> for i32 {
+            fn from(x: Fixed) -> Self {
+                x.0
+            }
+        }
+        impl SampleUniform for Fixed {
+            type Sampler = UniformNewtype<Fixed, i32>;
+        }
+
+        let mut rng = crate::test::rng(806);
+        let (low, high) = (Fixed(-3 << 16), Fixed(2 << 16));
+        let uniform = Uniform::new(low, high).unwrap();
+        for _ in 0..100 {
+            let x = rng.sample(uniform);
+            assert!(low <= x && x < high);
+            let x = rng.gen_range(low..=high);
+            assert!(low <= x && x <= high);
+            let x = rng.gen_range(&low..&high);
+            assert!(low <= x && x < high);
+        }
+        assert_eq!(rng.gen_range(low..=low), low);
+        assert!(Uniform::new(high, low).is_err());
+
+        // Results match sampling the wrapped type
+        let mut rng2 = crate::test::rng(807);
+        let mut rng = crate::test::rng(807);
+        assert_eq!(rng.gen_range(low..high).0, rng2.gen_range(low.0..high.0));
+
+        #[cfg(feature = "serde1")]
+        {
+            let de_uniform: Uniform<Fixed> =
+                bincode::deserialize(&bincode::serialize(&uniform).unwrap()).unwrap();
+            assert_eq!(uniform, de_uniform);
+        }
+    }
+
     #[test]
     fn test_uniform_from_std_range() {
         let r = Uniform::try_from(2u32..7).unwrap();
@@ -1609,6 +2252,41 @@ mod tests {
         assert!(r.0.scale < 5.0 + 1e-14);
     }
 
+    #[test]
+    fn test_fill_small_ints() {
+        let mut rng = crate::test::rng(227);
+        let mut counts = [0u32; 6];
+        let mut buf = [0u8; 6000];
+        Uniform::new(1u8, 7).unwrap().fill(&mut rng, &mut buf);
+        for &x in buf.iter() {
+            counts[x as usize - 1] += 1;
+        }
+        for &c in counts.iter() {
+            // Expect 1000 of each
+            assert!((850..1150).contains(&c), "{:?}", counts);
+        }
+
+        let mut buf = [0i8; 37];
+        Uniform::new_inclusive(-3i8, 3).unwrap().fill(&mut rng, &mut buf);
+        assert!(buf.iter().all(|x| (-3..=3).contains(x)));
+        Uniform::new_inclusive(i8::MIN, i8::MAX).unwrap().fill(&mut rng, &mut buf);
+        assert!(buf.iter().any(|&x| x < 0));
+
+        let mut buf = [0u16; 37];
+        Uniform::new(1000u16, 1010).unwrap().fill(&mut rng, &mut buf);
+        assert!(buf.iter().all(|x| (1000..1010).contains(x)));
+        let mut buf = [0i16; 3];
+        Uniform::new(-1i16, 0).unwrap().fill(&mut rng, &mut buf);
+        assert_eq!(buf, [-1; 3]);
+        Uniform::new(0i16, 10).unwrap().fill(&mut rng, &mut []);
+
+        // Value stability
+        let mut rng = crate::test::rng(897);
+        let mut buf = [0u8; 6];
+        Uniform::new(11u8, 219).unwrap().fill(&mut rng, &mut buf);
+        assert_eq!(buf, [15, 17, 163, 66, 82, 214]);
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: SampleUniform + Copy + core::fmt::Debug + PartialEq>(
@@ -1634,6 +2312,21 @@ mod tests {
 
         test_samples(11u8, 219, &[17, 66, 214], &[181, 93, 165]);
         test_samples(11u32, 219, &[17, 66, 214], &[181, 93, 165]);
+        test_samples(11u128, 219, &[181, 127, 139], &[167, 141, 197]);
+        test_samples(
+            i128::MIN,
+            i128::MAX,
+            &[
+                108690737959967267092156201862508054018,
+                19963944724362385072679454105528081013,
+                40239685927218134710752026420535168070,
+            ],
+            &[
+                86553147657262057488521518886565232738,
+                42893478821252418682476711888131277715,
+                135331824679874007704492585303125727682,
+            ],
+        );
 
         test_samples(0f32, 1e-2f32, &[0.0003070104, 0.0026630748, 0.00979833], &[
             0.008194133,
@@ -0,0 +1,229 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform sampling over unions of integer ranges
+
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::distributions::uniform::Error;
+use crate::distributions::{Distribution, Uniform};
+use crate::Rng;
+
+/// Sample integers uniformly from a union of ranges.
+///
+/// Each value in the union is equally likely; thus each range is chosen with
+/// probability proportional to its length. Rather than rejecting values
+/// outside of the ranges, a single index is sampled over the total length
+/// and mapped onto the ranges via binary search, thus sampling takes
+/// `O(log k)` time for `k` ranges.
+///
+/// Ranges are given as inclusive ranges, so that the maximum value of the
+/// type may be included. Empty ranges are ignored while overlapping or
+/// adjacent ranges are merged. This is implemented for all integer types up
+/// to 64 bits.
+///
+/// When the union has at most `u32::MAX + 1` values, the index is sampled as
+/// a `u32`, otherwise as a `u64`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, UniformUnion};
+///
+/// // Non-privileged ports, excluding a reserved block
+/// let ports = UniformUnion::new([1024..=5999, 6064..=65535]).unwrap();
+/// let port: u16 = ports.sample(&mut rand::thread_rng());
+/// assert!(port >= 1024 && !(6000..6064).contains(&port));
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniformUnion<X> {
+    ranges: Vec<RangeInclusive<X>>,
+    // `ends[i]` is the last index mapped onto `ranges[i]`
+    ends: Vec<u64>,
+    index: IndexRange,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IndexRange {
+    U32(Uniform<u32>),
+    U64(Uniform<u64>),
+}
+
+// Integer types supported by `UniformUnion`. This is a public trait in a
+// private module, hence cannot be named or implemented outside of Rand.
+#[doc(hidden)]
+pub trait UnionInt: Copy + Ord {
+    /// Map `self` to `self - Self::MIN` in a `u64`, preserving order.
+    fn to_offset(self) -> u64;
+
+    /// Invert `to_offset`, truncating to the width of `Self`.
+    fn from_offset(offset: u64) -> Self;
+}
+
+macro_rules! union_int_impl {
+    ($ty:ty, $unsigned:ty) => {
+        impl UnionInt for $ty {
+            #[inline]
+            fn to_offset(self) -> u64 {
+                self.wrapping_sub(<$ty>::MIN) as $unsigned as u64
+            }
+
+            #[inline]
+            fn from_offset(offset: u64) -> Self {
+                (offset as $ty).wrapping_add(<$ty>::MIN)
+            }
+        }
+    };
+}
+
+union_int_impl!(i8, u8);
+union_int_impl!(i16, u16);
+union_int_impl!(i32, u32);
+union_int_impl!(i64, u64);
+union_int_impl!(isize, usize);
+union_int_impl!(u8, u8);
+union_int_impl!(u16, u16);
+union_int_impl!(u32, u32);
+union_int_impl!(u64, u64);
+union_int_impl!(usize, usize);
+
+impl<X: UnionInt> UniformUnion<X> {
+    /// Construct a sampler over the union of `ranges`.
+    ///
+    /// Fails with [`Error::EmptyRange`] if all ranges are empty.
+    pub fn new<I>(ranges: I) -> Result<Self, Error>
+    where I: IntoIterator<Item = RangeInclusive<X>> {
+        let mut bounds: Vec<(u64, u64)> = ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| (r.start().to_offset(), r.end().to_offset()))
+            .collect();
+        bounds.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(bounds.len());
+        for (start, end) in bounds {
+            if let Some(last) = merged.last_mut() {
+                // Since ranges are sorted, `start > 0` if the first
+                // condition fails
+                if start <= last.1 || start - 1 == last.1 {
+                    last.1 = last.1.max(end);
+                    continue;
+                }
+            }
+            merged.push((start, end));
+        }
+
+        // The union has at most 2^64 values, hence the last index fits in a
+        // `u64`
+        let mut ends = Vec::with_capacity(merged.len());
+        let mut last: Option<u64> = None;
+        for &(start, end) in &merged {
+            let index = match last {
+                None => end - start,
+                Some(last) => last + (end - start) + 1,
+            };
+            ends.push(index);
+            last = Some(index);
+        }
+
+        let last = last.ok_or(Error::EmptyRange)?;
+        let index = if last <= u32::MAX as u64 {
+            IndexRange::U32(Uniform::new_inclusive(0, last as u32)?)
+        } else {
+            IndexRange::U64(Uniform::new_inclusive(0, last)?)
+        };
+        let ranges = merged
+            .into_iter()
+            .map(|(start, end)| X::from_offset(start)..=X::from_offset(end))
+            .collect();
+        Ok(UniformUnion { ranges, ends, index })
+    }
+}
+
+impl<X> UniformUnion<X> {
+    /// Return the ranges of the union, sorted and merged.
+    pub fn ranges(&self) -> &[RangeInclusive<X>] {
+        &self.ranges
+    }
+}
+
+impl<X: UnionInt> Distribution<X> for UniformUnion<X> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> X {
+        let index = match self.index {
+            IndexRange::U32(range) => range.sample(rng) as u64,
+            IndexRange::U64(range) => range.sample(rng),
+        };
+        let i = self.ends.partition_point(|&end| end < index);
+        let first = if i == 0 { 0 } else { self.ends[i - 1] + 1 };
+        X::from_offset(self.ranges[i].start().to_offset() + (index - first))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_union() {
+        let mut rng = crate::test::rng(241);
+        let distr = UniformUnion::new([7i8..=9, -3..=-2, 0..=0, 5..=4]).unwrap();
+        assert_eq!(distr.ranges(), [-3..=-2, 0..=0, 7..=9]);
+
+        let mut counts = [0; 13];
+        for _ in 0..6000 {
+            counts[(distr.sample(&mut rng) + 3) as usize] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            if [0, 1, 3, 10, 11, 12].contains(&i) {
+                // Expect 1000 of each
+                assert!((850..1150).contains(&c), "{}: {}", i, c);
+            } else {
+                assert_eq!(c, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let distr = UniformUnion::new([5u8..=10, 0..=2, 3..=4, 8..=12, 9..=9]).unwrap();
+        assert_eq!(distr.ranges(), [0..=12]);
+        let distr = UniformUnion::new([0u8..=2, 4..=5]).unwrap();
+        assert_eq!(distr.ranges(), [0..=2, 4..=5]);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_edge_cases() {
+        let mut rng = crate::test::rng(242);
+        assert_eq!(UniformUnion::<u32>::new(None), Err(Error::EmptyRange));
+        assert_eq!(UniformUnion::new([3u32..=2]), Err(Error::EmptyRange));
+
+        // Full ranges, split or not
+        let distr = UniformUnion::new([i64::MIN..=-1, 0..=i64::MAX]).unwrap();
+        assert_eq!(distr.ranges(), [i64::MIN..=i64::MAX]);
+        distr.sample(&mut rng);
+        let distr = UniformUnion::new([u64::MIN..=5, 7..=u64::MAX]).unwrap();
+        assert!((0..100).all(|_| distr.sample(&mut rng) != 6));
+        let distr = UniformUnion::new([0u8..=0, 255..=255]).unwrap();
+        assert!((0..100).all(|_| [0, 255].contains(&distr.sample(&mut rng))));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(243);
+        let distr = UniformUnion::new([0u16..=9, 100..=109]).unwrap();
+        let mut buf = [0; 8];
+        for x in &mut buf {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [7, 7, 2, 5, 4, 4, 9, 3]);
+    }
+}
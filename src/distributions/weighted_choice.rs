@@ -0,0 +1,212 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted choice of values, and the [`weighted_enum!`] macro
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::distributions::uniform::SampleUniform;
+use crate::distributions::{Distribution, WeightedError, WeightedIndex};
+use crate::Rng;
+
+/// A distribution choosing from a fixed set of values with given weights
+///
+/// This pairs each value with its weight, and samples clones of the values
+/// via a [`WeightedIndex`] over the weights. It is a declarative alternative
+/// to keeping a [`WeightedIndex`] and a separate slice of values in sync,
+/// e.g. for loot tables or the transitions of a state machine. See also the
+/// [`weighted_enum!`] macro for choosing enum variants.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, WeightedChoice};
+///
+/// let greeting = WeightedChoice::new([("hello", 3), ("hi", 1), ("hey", 0)]).unwrap();
+/// let s = greeting.sample(&mut rand::thread_rng());
+/// assert!(s == "hello" || s == "hi");
+/// ```
+///
+/// [`weighted_enum!`]: crate::weighted_enum
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct WeightedChoice<T, X: SampleUniform + PartialOrd> {
+    values: Vec<T>,
+    index: WeightedIndex<X>,
+}
+
+// Derived impls would not be bounded on the sampler of `X`
+impl<T: fmt::Debug, X: SampleUniform + PartialOrd> fmt::Debug for WeightedChoice<T, X>
+where WeightedIndex<X>: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedChoice")
+            .field("values", &self.values)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<T: Clone, X: SampleUniform + PartialOrd> Clone for WeightedChoice<T, X>
+where WeightedIndex<X>: Clone
+{
+    fn clone(&self) -> Self {
+        WeightedChoice {
+            values: self.values.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, X: SampleUniform + PartialOrd> PartialEq for WeightedChoice<T, X>
+where WeightedIndex<X>: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values && self.index == other.index
+    }
+}
+
+impl<T, X: SampleUniform + PartialOrd> WeightedChoice<T, X> {
+    /// Construct from pairs `(value, weight)`.
+    ///
+    /// Errors are as for [`WeightedIndex::new`]: the pairs must not be
+    /// empty, no weight may be negative (or NaN) and the total weight must
+    /// be positive.
+    pub fn new<I>(pairs: I) -> Result<Self, WeightedError>
+    where
+        I: IntoIterator<Item = (T, X)>,
+        X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default,
+    {
+        let iter = pairs.into_iter();
+        let mut values = Vec::with_capacity(iter.size_hint().0);
+        let mut weights = Vec::with_capacity(iter.size_hint().0);
+        for (value, weight) in iter {
+            values.push(value);
+            weights.push(weight);
+        }
+        let index = WeightedIndex::new(weights)?;
+        Ok(WeightedChoice { values, index })
+    }
+
+    /// Get the values, in the order given on construction
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Get the underlying [`WeightedIndex`] over the weights
+    pub fn index(&self) -> &WeightedIndex<X> {
+        &self.index
+    }
+}
+
+impl<T: Clone, X: SampleUniform + PartialOrd> Distribution<T> for WeightedChoice<T, X> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.values[self.index.sample(rng)].clone()
+    }
+}
+
+/// Construct a weighted distribution over the variants of an enum.
+///
+/// `weighted_enum!(Enum { A: wa, B: wb, ... })` constructs a
+/// [`WeightedChoice`] sampling the (unit) variants `Enum::A`, `Enum::B`, ...
+/// with probabilities proportional to the weights `wa`, `wb`, ... The weights
+/// may be any expressions of a type supported by [`WeightedIndex`]; integer
+/// literals default to `i32` and float literals to `f64` as usual.
+///
+/// The result is `Result<WeightedChoice<Enum, _>, WeightedError>`: weights
+/// are validated on construction, as by [`WeightedIndex::new`]. The enum
+/// must implement `Clone` to be sampled, and must be named by an identifier
+/// (import it first if necessary).
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::Distribution;
+/// use rand::weighted_enum;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let distr = weighted_enum!(Color { Red: 3, Green: 1, Blue: 1 }).unwrap();
+/// let color = distr.sample(&mut rand::thread_rng());
+/// println!("{:?}", color);
+///
+/// // Invalid weights are detected on construction
+/// assert!(weighted_enum!(Color { Red: 0, Green: 0 }).is_err());
+/// ```
+///
+/// [`WeightedChoice`]: crate::distributions::WeightedChoice
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+/// [`WeightedIndex::new`]: crate::distributions::WeightedIndex::new
+#[macro_export]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+macro_rules! weighted_enum {
+    ($enum:ident { $($variant:ident : $weight:expr),+ $(,)? }) => {
+        $crate::distributions::WeightedChoice::new([
+            $( ($enum::$variant, $weight) ),+
+        ])
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[test]
+    fn test_weighted_enum() {
+        let distr = weighted_enum!(Color { Red: 3, Green: 1, Blue: 0, }).unwrap();
+        assert_eq!(distr.values(), &[Color::Red, Color::Green, Color::Blue]);
+        assert_eq!(distr.index().total_weight(), 4);
+
+        let mut rng = crate::test::rng(265);
+        let mut counts = [0u32; 3];
+        for _ in 0..4000 {
+            counts[distr.sample(&mut rng) as usize] += 1;
+        }
+        assert!((counts[0] as f64 - 3000.0).abs() < 100.0, "{:?}", counts);
+        assert_eq!(counts[2], 0);
+
+        let distr = weighted_enum!(Color { Green: 0.5 }).unwrap();
+        assert_eq!(distr.sample(&mut rng), Color::Green);
+
+        assert_eq!(
+            weighted_enum!(Color { Red: 0, Green: 0 }).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            weighted_enum!(Color { Red: 1.0, Blue: -1.0 }).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedChoice::<Color, u32>::new(None).unwrap_err(),
+            WeightedError::NoItem
+        );
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(266);
+        let distr = WeightedChoice::new([('a', 1u32), ('b', 2), ('c', 3)]).unwrap();
+        let mut buf = ['x'; 6];
+        for x in buf.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, ['c', 'b', 'c', 'b', 'b', 'c']);
+    }
+}
@@ -101,7 +101,39 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         I::Item: SampleBorrow<X>,
         X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default,
     {
-        let mut iter = weights.into_iter();
+        let iter = weights.into_iter();
+        let capacity = iter.size_hint().0.saturating_sub(1);
+        Self::with_capacity(iter, capacity)
+    }
+
+    /// Creates a new `WeightedIndex` [`Distribution`] using the values in
+    /// `weights`, iterating over them twice.
+    ///
+    /// The first pass only counts the weights, allowing the second pass to
+    /// accumulate them into an allocation of exactly the required size. For
+    /// huge lazily generated sources which don't provide a useful
+    /// [`Iterator::size_hint`], this avoids the repeated re-allocation (and up
+    /// to double memory usage) of [`WeightedIndex::new`], at the cost of
+    /// generating the weights twice. `weights` must yield the same sequence
+    /// on both passes.
+    ///
+    /// Errors are as for [`WeightedIndex::new`].
+    pub fn new_two_pass<I>(weights: I) -> Result<WeightedIndex<X>, WeightedError>
+    where
+        I: IntoIterator + Clone,
+        I::Item: SampleBorrow<X>,
+        X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default,
+    {
+        let len = weights.clone().into_iter().count();
+        Self::with_capacity(weights.into_iter(), len.saturating_sub(1))
+    }
+
+    fn with_capacity<I>(mut iter: I, capacity: usize) -> Result<WeightedIndex<X>, WeightedError>
+    where
+        I: Iterator,
+        I::Item: SampleBorrow<X>,
+        X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default,
+    {
         let mut total_weight: X = iter.next().ok_or(WeightedError::NoItem)?.borrow().clone();
 
         let zero = <X as Default>::default();
@@ -109,7 +141,7 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
             return Err(WeightedError::InvalidWeight);
         }
 
-        let mut weights = Vec::<X>::with_capacity(iter.size_hint().0);
+        let mut weights = Vec::<X>::with_capacity(capacity);
         for w in iter {
             // Note that `!(w >= x)` is not equivalent to `w < x` for partially
             // ordered types due to NaNs which are equal to nothing.
@@ -132,6 +164,52 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         })
     }
 
+    /// Creates a new `WeightedIndex` [`Distribution`] from pre-accumulated
+    /// weights.
+    ///
+    /// Element `i` of `cumulative_weights` must be the sum of the weights of
+    /// items `0..=i`, thus the last element is the total weight. The vector
+    /// is used as storage without re-summing or copying the weights.
+    ///
+    /// Returns an error if the vector is empty, if any element is `< 0` or is
+    /// smaller than its predecessor, or if the total weight is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::WeightedIndex;
+    ///
+    /// // Equivalent to weights [2, 1, 1]
+    /// let a = WeightedIndex::from_cumulative_weights(vec![2, 3, 4]).unwrap();
+    /// let b = WeightedIndex::new(&[2, 1, 1]).unwrap();
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn from_cumulative_weights(
+        mut cumulative_weights: Vec<X>,
+    ) -> Result<WeightedIndex<X>, WeightedError>
+    where X: Clone + Default {
+        let zero = <X as Default>::default();
+        let mut prev = &zero;
+        for w in &cumulative_weights {
+            if !(w >= prev) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            prev = w;
+        }
+
+        let total_weight = cumulative_weights.pop().ok_or(WeightedError::NoItem)?;
+        if total_weight == zero {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        let distr = X::Sampler::new(zero, total_weight.clone()).unwrap();
+
+        Ok(WeightedIndex {
+            cumulative_weights,
+            total_weight,
+            weight_distribution: distr,
+        })
+    }
+
     /// Update a subset of weights, without changing the number of weights.
     ///
     /// `new_weights` must be sorted by the index.
@@ -226,6 +304,135 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
     }
 }
 
+impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
+    /// Returns the total weight of all items.
+    pub fn total_weight(&self) -> X
+    where X: Clone {
+        self.total_weight.clone()
+    }
+
+    /// Returns the weight of the item at `index`, or `None` if `index` is out
+    /// of bounds.
+    ///
+    /// Weights are recovered from the stored cumulative weights, thus for
+    /// floating-point types the result may differ slightly from the weight
+    /// originally given.
+    pub fn weight(&self, index: usize) -> Option<X>
+    where X: for<'a> ::core::ops::SubAssign<&'a X> + Clone {
+        use ::core::cmp::Ordering;
+        let mut weight = match index.cmp(&self.cumulative_weights.len()) {
+            Ordering::Less => self.cumulative_weights[index].clone(),
+            Ordering::Equal => self.total_weight.clone(),
+            Ordering::Greater => return None,
+        };
+        if index > 0 {
+            weight -= &self.cumulative_weights[index - 1];
+        }
+        Some(weight)
+    }
+
+    /// Returns an iterator over the weights of all items, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::WeightedIndex;
+    ///
+    /// let dist = WeightedIndex::new([1, 2, 3]).unwrap();
+    /// assert_eq!(dist.weights().collect::<Vec<_>>(), [1, 2, 3]);
+    /// assert_eq!(dist.total_weight(), 6);
+    /// ```
+    pub fn weights(&self) -> WeightedIndexIter<'_, X>
+    where X: for<'a> ::core::ops::SubAssign<&'a X> + Clone {
+        WeightedIndexIter {
+            weighted_index: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the probability of sampling the item at `index`, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// This requires a lossless conversion of the weight type to `f64`, which
+    /// is available for `f32`, `f64` and integer types up to 32 bits.
+    pub fn probability(&self, index: usize) -> Option<f64>
+    where X: for<'a> ::core::ops::SubAssign<&'a X> + Clone + Into<f64> {
+        let total: f64 = self.total_weight().into();
+        self.weight(index).map(|w| w.into() / total)
+    }
+
+    /// Returns an iterator over the probabilities of all items, in order.
+    ///
+    /// See [`WeightedIndex::probability`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::WeightedIndex;
+    ///
+    /// let dist = WeightedIndex::new([2u32, 1, 1]).unwrap();
+    /// assert_eq!(dist.probabilities().collect::<Vec<_>>(), [0.5, 0.25, 0.25]);
+    /// ```
+    pub fn probabilities(&self) -> impl Iterator<Item = f64> + '_
+    where X: for<'a> ::core::ops::SubAssign<&'a X> + Clone + Into<f64> {
+        let total: f64 = self.total_weight().into();
+        self.weights().map(move |w| w.into() / total)
+    }
+}
+
+/// An iterator over the weights of a [`WeightedIndex`].
+///
+/// This `struct` is created by [`WeightedIndex::weights`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct WeightedIndexIter<'a, X: SampleUniform + PartialOrd> {
+    weighted_index: &'a WeightedIndex<X>,
+    index: usize,
+}
+
+impl<'a, X> fmt::Debug for WeightedIndexIter<'a, X>
+where
+    X: SampleUniform + PartialOrd + fmt::Debug,
+    X::Sampler: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedIndexIter")
+            .field("weighted_index", &self.weighted_index)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<'a, X: SampleUniform + PartialOrd> Clone for WeightedIndexIter<'a, X> {
+    fn clone(&self) -> Self {
+        WeightedIndexIter {
+            weighted_index: self.weighted_index,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, X> Iterator for WeightedIndexIter<'a, X>
+where X: for<'b> ::core::ops::SubAssign<&'b X> + SampleUniform + PartialOrd + Clone
+{
+    type Item = X;
+
+    fn next(&mut self) -> Option<X> {
+        let weight = self.weighted_index.weight(self.index)?;
+        self.index += 1;
+        Some(weight)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.weighted_index.cumulative_weights.len() + 1).saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl<'a, X> ExactSizeIterator for WeightedIndexIter<'a, X>
+where X: for<'b> ::core::ops::SubAssign<&'b X> + SampleUniform + PartialOrd + Clone
+{
+}
+
 impl<X> Distribution<usize> for WeightedIndex<X>
 where X: SampleUniform + PartialOrd
 {
@@ -357,6 +564,98 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_new_two_pass() {
+        let weights = (0..100u32).map(|i| i % 7);
+        let a = WeightedIndex::new_two_pass(weights.clone()).unwrap();
+        assert_eq!(a, WeightedIndex::new(weights.clone()).unwrap());
+        assert_eq!(a.cumulative_weights.capacity(), 99);
+
+        // A filter gives no useful size hint
+        let filtered = weights.filter(|&w| w > 0);
+        assert_eq!(filtered.size_hint().0, 0);
+        let b = WeightedIndex::new_two_pass(filtered.clone()).unwrap();
+        assert_eq!(b, WeightedIndex::new(filtered.clone()).unwrap());
+        assert_eq!(b.cumulative_weights.capacity(), filtered.count() - 1);
+
+        assert_eq!(
+            WeightedIndex::<u32>::new_two_pass(0..0).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            WeightedIndex::new_two_pass([1.0, f64::NAN].iter()).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn test_from_cumulative_weights() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let mut cumulative = Vec::new();
+        let mut sum = 0;
+        for w in weights.iter() {
+            sum += w;
+            cumulative.push(sum);
+        }
+        let a = WeightedIndex::from_cumulative_weights(cumulative).unwrap();
+        assert_eq!(a, WeightedIndex::new(weights).unwrap());
+
+        let mut r = crate::test::rng(702);
+        let b = WeightedIndex::from_cumulative_weights([0.0, 0.5, 0.5, 1.0].to_vec()).unwrap();
+        for _ in 0..100 {
+            let i = b.sample(&mut r);
+            assert!(i == 1 || i == 3);
+        }
+
+        assert_eq!(
+            WeightedIndex::<u32>::from_cumulative_weights(Vec::new()).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            WeightedIndex::from_cumulative_weights([0, 0].to_vec()).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            WeightedIndex::from_cumulative_weights([1, 3, 2].to_vec()).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedIndex::from_cumulative_weights([-1, 3].to_vec()).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedIndex::from_cumulative_weights([0.5, f32::NAN].to_vec()).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn test_inspection() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let total = weights.iter().sum::<u32>();
+        let distr = WeightedIndex::new(weights).unwrap();
+        assert_eq!(distr.total_weight(), total);
+        for (i, &w) in weights.iter().enumerate() {
+            assert_eq!(distr.weight(i), Some(w));
+            assert_eq!(distr.probability(i), Some(w as f64 / total as f64));
+        }
+        assert_eq!(distr.weight(weights.len()), None);
+        assert_eq!(distr.probability(weights.len()), None);
+
+        let mut iter = distr.weights();
+        assert_eq!(iter.len(), weights.len());
+        iter.next();
+        assert_eq!(iter.len(), weights.len() - 1);
+        assert_eq!(iter.collect::<Vec<_>>(), weights[1..]);
+
+        let sum: f64 = distr.probabilities().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+
+        let single = WeightedIndex::new([0.5f32]).unwrap();
+        assert_eq!(single.weights().collect::<Vec<_>>(), [0.5]);
+        assert_eq!(single.probabilities().collect::<Vec<_>>(), [1.0]);
+    }
+
     #[test]
     fn test_update_weights() {
         let data = [
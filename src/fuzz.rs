@@ -0,0 +1,178 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Building blocks for fuzzing
+//!
+//! This module provides:
+//!
+//! *   [`Mutator`] to apply a given number of random substitutions,
+//!     insertions and deletions to a byte string
+//!
+//! Samplers elsewhere in Rand are often useful for fuzzing too, e.g.
+//! [`SubRange`] to pick a random slice of a buffer or
+//! [`random_split`] to split a message into random fragments.
+//!
+//! [`SubRange`]: crate::distributions::SubRange
+//! [`random_split`]: crate::seq::random_split
+
+use alloc::vec::Vec;
+
+use crate::seq::{gen_index, index};
+use crate::Rng;
+
+/// Mutate byte strings by random substitutions, insertions and deletions.
+///
+/// [`Mutator::apply`] takes a copy of the input and, in this order,
+///
+/// 1.  deletes `deletions` bytes at distinct random positions,
+/// 2.  substitutes `substitutions` of the remaining bytes, at distinct
+///     random positions, by a different random byte each,
+/// 3.  inserts `insertions` random bytes, each at a random position.
+///
+/// The output thus has length `input.len() - deletions + insertions`, and
+/// its edit (Levenshtein) distance to the input is at most (and usually
+/// exactly) `deletions + substitutions + insertions`. It may be less where
+/// an inserted byte happens to restore a deleted one.
+///
+/// # Example
+///
+/// ```
+/// use rand::fuzz::Mutator;
+///
+/// let mutator = Mutator::new().substitutions(2).insertions(1);
+/// let message = b"GET / HTTP/1.1\r\n";
+/// let fuzzed = mutator.apply(&mut rand::thread_rng(), message);
+/// assert_eq!(fuzzed.len(), message.len() + 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mutator {
+    substitutions: usize,
+    insertions: usize,
+    deletions: usize,
+}
+
+impl Mutator {
+    /// Construct, applying no edits
+    pub fn new() -> Self {
+        Mutator::default()
+    }
+
+    /// Set the number of bytes to substitute
+    pub fn substitutions(self, n: usize) -> Self {
+        Mutator {
+            substitutions: n,
+            ..self
+        }
+    }
+
+    /// Set the number of bytes to insert
+    pub fn insertions(self, n: usize) -> Self {
+        Mutator { insertions: n, ..self }
+    }
+
+    /// Set the number of bytes to delete
+    pub fn deletions(self, n: usize) -> Self {
+        Mutator { deletions: n, ..self }
+    }
+
+    /// Returns the total number of edits applied
+    pub fn edits(&self) -> usize {
+        self.substitutions + self.insertions + self.deletions
+    }
+
+    /// Apply the edits to a copy of `input`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `deletions + substitutions > input.len()`.
+    pub fn apply<R: Rng + ?Sized>(&self, rng: &mut R, input: &[u8]) -> Vec<u8> {
+        assert!(
+            self.deletions + self.substitutions <= input.len(),
+            "more deletions and substitutions than input bytes"
+        );
+
+        let mut deleted = index::sample(rng, input.len(), self.deletions).into_vec();
+        deleted.sort_unstable();
+        let mut deleted = deleted.into_iter().peekable();
+        let mut output = Vec::with_capacity(input.len() - self.deletions + self.insertions);
+        for (i, &byte) in input.iter().enumerate() {
+            if deleted.peek() == Some(&i) {
+                deleted.next();
+            } else {
+                output.push(byte);
+            }
+        }
+
+        for i in index::sample(rng, output.len(), self.substitutions) {
+            // XOR with a non-zero byte yields a different byte
+            output[i] ^= rng.gen_range(1..=u8::MAX);
+        }
+
+        for _ in 0..self.insertions {
+            let i = gen_index(rng, output.len() + 1);
+            output.insert(i, rng.gen());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, x) in a.iter().enumerate() {
+            let mut diag = row[0];
+            row[0] = i + 1;
+            for (j, y) in b.iter().enumerate() {
+                let next = (diag + (x != y) as usize).min(row[j] + 1).min(row[j + 1] + 1);
+                diag = row[j + 1];
+                row[j + 1] = next;
+            }
+        }
+        row[b.len()]
+    }
+
+    #[test]
+    fn test_mutator() {
+        let mut rng = crate::test::rng(258);
+        let input: Vec<u8> = (0..50).collect();
+        assert_eq!(Mutator::new().apply(&mut rng, &input), input);
+
+        let mutator = Mutator::new().substitutions(5);
+        let output = mutator.apply(&mut rng, &input);
+        assert_eq!(output.iter().zip(&input).filter(|(a, b)| a != b).count(), 5);
+
+        let mutator = Mutator::new().substitutions(3).insertions(4).deletions(6);
+        assert_eq!(mutator.edits(), 13);
+        for _ in 0..100 {
+            let output = mutator.apply(&mut rng, &input);
+            assert_eq!(output.len(), 48);
+            assert!(edit_distance(&input, &output) <= 13);
+        }
+
+        let output = Mutator::new().deletions(50).insertions(2).apply(&mut rng, &input);
+        assert_eq!(output.len(), 2);
+        assert_eq!(Mutator::new().insertions(3).apply(&mut rng, &[]).len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mutator_too_many_edits() {
+        Mutator::new().substitutions(2).deletions(2).apply(&mut crate::test::rng(259), b"abc");
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(260);
+        let mutator = Mutator::new().substitutions(1).insertions(1).deletions(1);
+        assert_eq!(mutator.apply(&mut rng, b"abcdef"), [181, 97, 99, 225, 101, 102]);
+    }
+}
@@ -91,18 +91,33 @@ macro_rules! error { ($($x:tt)*) => (
 ) }
 
 // Re-exports from rand_core
-pub use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+pub use rand_core::{CryptoRng, Error, ParallelRng, RngCore, SeedBytes, SeedableRng, SplitRng};
 
 // Public modules
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub mod datetime;
 pub mod distributions;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod fuzz;
+#[cfg(feature = "ndarray")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "ndarray")))]
+pub mod ndarray;
 pub mod prelude;
 mod rng;
 pub mod rngs;
 pub mod seq;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod table;
 
 // Public exports
 #[cfg(all(feature = "std", feature = "std_rng"))]
-pub use crate::rngs::thread::thread_rng;
+pub use crate::rngs::thread::{
+    named_rng, restore_thread_rng, thread_rng, thread_rng_seeded, thread_rng_snapshot, thread_rng_stats,
+    thread_rng_with, thread_rng_with_core,
+};
 pub use rng::{Fill, Rng};
 
 #[cfg(all(feature = "std", feature = "std_rng"))]
@@ -0,0 +1,118 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random arrays for the [`ndarray`](https://docs.rs/ndarray) crate
+//!
+//! This module provides:
+//!
+//! *   [`random_array`] to construct a new array of a given shape, with
+//!     elements sampled from a distribution
+//! *   [`fill_with_dist`] to overwrite the elements of an existing array
+//!     (or view) in place
+//!
+//! ```
+//! use rand::distributions::Uniform;
+//! use rand::ndarray::{fill_with_dist, random_array};
+//! use ndarray::{s, Array2};
+//!
+//! let mut rng = rand::thread_rng();
+//! let mut a: Array2<f64> = random_array((3, 4), Uniform::new(0.0, 1.0).unwrap(), &mut rng);
+//! assert_eq!(a.shape(), [3, 4]);
+//!
+//! // Re-sample the first column only
+//! fill_with_dist(a.slice_mut(s![.., 0]), rand::distributions::Standard, &mut rng);
+//! ```
+
+use ::ndarray::{Array, ArrayViewMut, Dimension, ShapeBuilder};
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+/// Construct an array of the given `shape` with elements sampled from
+/// `distr`.
+///
+/// The `shape` may be anything accepted by [`Array::from_shape_simple_fn`],
+/// e.g. `(m, n)` or `[m, n]`, optionally with `.f()` for column-major
+/// layout. Elements are sampled in memory order, thus the layout affects
+/// which element receives which sample.
+pub fn random_array<T, Sh, D, Dist, R>(shape: Sh, distr: Dist, rng: &mut R) -> Array<T, D>
+where
+    Sh: ShapeBuilder<Dim = D>,
+    D: Dimension,
+    Dist: Distribution<T>,
+    R: Rng + ?Sized,
+{
+    Array::from_shape_simple_fn(shape, || distr.sample(rng))
+}
+
+/// Overwrite all elements of `array` with samples from `distr`.
+///
+/// Use `a.view_mut()` to fill an owned array, or `a.slice_mut(s![.., 0])`
+/// to fill only part of one. Elements are
+/// sampled in logical (row-major) order, independent of memory layout.
+pub fn fill_with_dist<T, D, Dist, R>(mut array: ArrayViewMut<T, D>, distr: Dist, rng: &mut R)
+where
+    D: Dimension,
+    Dist: Distribution<T>,
+    R: Rng + ?Sized,
+{
+    // Standard layout is the common case and allows a plain slice loop
+    if let Some(slice) = array.as_slice_mut() {
+        for x in slice {
+            *x = distr.sample(rng);
+        }
+    } else {
+        for x in array.iter_mut() {
+            *x = distr.sample(rng);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::{Standard, Uniform};
+    use ::ndarray::{s, Array1, Array2, Array3, ShapeBuilder};
+
+    #[test]
+    fn test_random_array() {
+        let mut rng = crate::test::rng(207);
+        let a: Array3<u8> = random_array((2, 3, 4), Uniform::new(10, 20).unwrap(), &mut rng);
+        assert_eq!(a.shape(), [2, 3, 4]);
+        assert!(a.iter().all(|&x| (10..20).contains(&x)));
+
+        let a: Array1<u32> = random_array(3, Standard, &mut crate::test::rng(207));
+        assert_eq!(a.to_vec(), [1216707456, 4709815, 1215271726]);
+    }
+
+    #[test]
+    fn test_layout() {
+        let a: Array2<u32> = random_array((3, 2), Standard, &mut crate::test::rng(208));
+        let b: Array2<u32> = random_array((3, 2).f(), Standard, &mut crate::test::rng(208));
+        assert!(!b.is_standard_layout());
+        assert_eq!(a, b.t().to_owned().into_shape((3, 2)).unwrap());
+
+        // Filling is independent of layout
+        let mut c = Array2::zeros((3, 2).f());
+        fill_with_dist(c.view_mut(), Standard, &mut crate::test::rng(208));
+        assert_eq!(a, c);
+    }
+
+    #[test]
+    fn test_fill_with_dist() {
+        let mut rng = crate::test::rng(209);
+        let mut a = Array2::<u32>::zeros((4, 5));
+        fill_with_dist(a.slice_mut(s![.., 1]), Uniform::new(1, 10).unwrap(), &mut rng);
+        for ((_, j), &x) in a.indexed_iter() {
+            assert_eq!(x == 0, j != 1);
+        }
+
+        fill_with_dist(a.view_mut(), Uniform::new(1, 10).unwrap(), &mut rng);
+        assert!(a.iter().all(|&x| x != 0));
+    }
+}
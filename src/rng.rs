@@ -10,6 +10,8 @@
 //! [`Rng`] trait
 
 use rand_core::{Error, RngCore};
+#[cfg(feature = "alloc")] use rand_core::ParallelRng;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
 use crate::distributions::uniform::{SampleRange, SampleUniform};
 use crate::distributions::{self, Distribution, Standard};
 use core::num::Wrapping;
@@ -100,6 +102,10 @@ pub trait Rng: RngCore {
     /// type which may be faster if sampling from the same range repeatedly.
     ///
     /// Only `gen_range(low..high)` and `gen_range(low..=high)` are supported.
+    /// Bounds may be arrays or tuples, in which case each element is sampled
+    /// from its own range. Bounds may also be references, e.g.
+    /// `gen_range(&low..&high)`, allowing types which are not `Copy` to be
+    /// sampled without cloning the bounds.
     ///
     /// # Panics
     ///
@@ -121,6 +127,10 @@ pub trait Rng: RngCore {
     /// // Inclusive range
     /// let n: u32 = rng.gen_range(0..=10);
     /// println!("{}", n);
+    ///
+    /// // Point in the rectangle [0, 1) × [0, 2)
+    /// let [x, y] = rng.gen_range([0.0, 0.0]..[1.0, 2.0]);
+    /// println!("({}, {})", x, y);
     /// ```
     ///
     /// [`Uniform`]: distributions::uniform::Uniform
@@ -298,6 +308,62 @@ pub trait Rng: RngCore {
         let d = distributions::Bernoulli::from_ratio(numerator, denominator).unwrap();
         self.sample(d)
     }
+
+    /// Return a bool which is true with probability exactly `1/n`.
+    ///
+    /// Unlike `gen_ratio(1, n)`, no rounding of the probability occurs: a
+    /// uniform integer is sampled from `0..n` via integer arithmetic only.
+    /// If `n == 1` the result is always `true` and the RNG is not used.
+    ///
+    /// # Panics
+    ///
+    /// If `n == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// if rng.one_in(6) {
+    ///     println!("Rolled a six!");
+    /// }
+    /// ```
+    #[inline]
+    fn one_in(&mut self, n: u32) -> bool {
+        assert!(n != 0, "one_in: n must be positive");
+        if n == 1 {
+            return true;
+        }
+        let d = distributions::Uniform::new(0, n).unwrap();
+        self.sample(d) == 0
+    }
+
+    /// Split off `n` generators with independent streams.
+    ///
+    /// Each generator is obtained via [`ParallelRng::fork`], thus their
+    /// outputs do not overlap with each other or with the subsequent output
+    /// of `self`. This is intended for handing out generators to the `n`
+    /// workers or jobs of a parallel computation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{Rng, SeedableRng};
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(7);
+    /// let children = rng.split_n(4);
+    /// assert_eq!(children.len(), 4);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn split_n(&mut self, n: usize) -> Vec<Self>
+    where
+        Self: ParallelRng,
+    {
+        (0..n).map(|_| self.fork()).collect()
+    }
 }
 
 impl<R: RngCore + ?Sized> Rng for R {}
@@ -305,6 +371,9 @@ impl<R: RngCore + ?Sized> Rng for R {}
 /// Types which may be filled with random data
 ///
 /// This trait allows arrays to be efficiently filled with random data.
+/// Slices of integers and floats are filled with a single call to
+/// [`RngCore::try_fill_bytes`]; floats are then converted to `[0, 1)` as by
+/// the [`Standard`] distribution.
 ///
 /// Implementations are expected to be portable across machines unless
 /// clearly documented otherwise (see the
@@ -332,7 +401,7 @@ macro_rules! impl_fill_each {
     };
 }
 
-impl_fill_each!(bool, char, f32, f64,);
+impl_fill_each!(bool, char,);
 
 impl Fill for [u8] {
     fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
@@ -390,6 +459,39 @@ macro_rules! impl_fill {
 impl_fill!(u16, u32, u64, usize, u128,);
 impl_fill!(i8, i16, i32, i64, isize, i128,);
 
+macro_rules! impl_fill_float {
+    ($t:ty, $u:ty) => {
+        impl Fill for [$t] {
+            /// Fill with floats in `[0, 1)`, converting words of the byte
+            /// stream in little-endian order as by [`Standard`]
+            ///
+            /// [`Standard`]: crate::distributions::Standard
+            #[inline(never)]
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                if self.len() > 0 {
+                    // Any bit pattern is a valid float
+                    rng.try_fill_bytes(unsafe {
+                        slice::from_raw_parts_mut(self.as_mut_ptr()
+                            as *mut u8,
+                            self.len() * mem::size_of::<$t>()
+                        )
+                    })?;
+                    let precision = <$t>::MANTISSA_DIGITS;
+                    let scale = 1.0 / ((1 as $u << precision) as $t);
+                    for x in self {
+                        let bits = <$u>::from_le(x.to_bits());
+                        *x = (bits >> (<$u>::BITS - precision)) as $t * scale;
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_fill_float!(f32, u32);
+impl_fill_float!(f64, u64);
+
 impl<T, const N: usize> Fill for [T; N]
 where [T]: Fill
 {
@@ -442,17 +544,46 @@ mod test {
         assert_eq!(array, [x as u32, (x >> 32) as u32]);
         assert_eq!(rng.next_u32(), x as u32);
 
+        // Convert to bytes then u128 and i128 in LE order
+        let wide = u128::from(x) | (u128::from(x) << 64);
+        let mut array128 = [0u128; 2];
+        rng.fill(&mut array128[..]);
+        assert_eq!(array128, [wide, wide]);
+        let mut array128 = [0i128; 1];
+        rng.fill(&mut array128[..]);
+        assert_eq!(array128, [wide as i128]);
+
         // Check equivalence using wrapped arrays
         let mut warray = [Wrapping(0u32); 2];
         rng.fill(&mut warray[..]);
         assert_eq!(array[0], warray[0].0);
         assert_eq!(array[1], warray[1].0);
 
-        // Check equivalence for generated floats
+        // Floats are converted from words of the byte stream as by Standard
         let mut array = [0f32; 2];
         rng.fill(&mut array);
-        let gen: [f32; 2] = rng.gen();
-        assert_eq!(array, gen);
+        assert_eq!(array, [
+            (x as u32 >> 8) as f32 / (1 << 24) as f32,
+            ((x >> 32) as u32 >> 8) as f32 / (1 << 24) as f32,
+        ]);
+        let mut array = [0f64; 2];
+        rng.fill(&mut array[..]);
+        assert_eq!(array, [(x >> 11) as f64 / (1u64 << 53) as f64; 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std_rng")]
+    fn test_fill_float() {
+        use crate::rngs::StdRng;
+        use crate::SeedableRng;
+
+        // Block RNGs output the same stream via next_u64 and fill_bytes
+        let mut a = StdRng::seed_from_u64(272);
+        let mut b = a.clone();
+        let mut array = [0f64; 17];
+        a.fill(&mut array);
+        assert!(array.iter().all(|x| (0.0..1.0).contains(x)));
+        assert_eq!(array, b.gen::<[f64; 17]>());
     }
 
     #[test]
@@ -526,27 +657,59 @@ mod test {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "std_rng"))]
+    fn test_split_n() {
+        use crate::rngs::StdRng;
+        use crate::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(110);
+        let mut children = rng.split_n(3);
+        assert_eq!(children.len(), 3);
+        let mut outputs = [0u64; 4];
+        for (x, child) in outputs.iter_mut().zip(children.iter_mut()) {
+            *x = child.next_u64();
+        }
+        outputs[3] = rng.next_u64();
+        for i in 0..4 {
+            for j in 0..i {
+                assert_ne!(outputs[i], outputs[j]);
+            }
+        }
+    }
+
     #[test]
     fn test_rng_trait_object() {
         use crate::distributions::{Distribution, Standard};
+        use crate::seq::SliceRandom;
         let mut rng = rng(109);
         let mut r = &mut rng as &mut dyn RngCore;
         r.next_u32();
         r.gen::<i32>();
         assert_eq!(r.gen_range(0..1), 0);
         let _c: u8 = Standard.sample(&mut r);
+        // Samplers and sequence methods also take the unsized `dyn RngCore`
+        let _c: u8 = Standard.sample(r);
+        let mut v = [1, 2, 3];
+        v.shuffle(r);
+        assert!(v.choose(r).is_some());
+        assert!(crate::distributions::Uniform::new(0, 10).unwrap().sample(r) < 10);
     }
 
     #[test]
     #[cfg(feature = "alloc")]
     fn test_rng_boxed_trait() {
         use crate::distributions::{Distribution, Standard};
+        use crate::seq::SliceRandom;
         let rng = rng(110);
         let mut r = Box::new(rng) as Box<dyn RngCore>;
         r.next_u32();
         r.gen::<i32>();
         assert_eq!(r.gen_range(0..1), 0);
         let _c: u8 = Standard.sample(&mut r);
+        let _c: u8 = Standard.sample(&mut *r);
+        let mut v = [1, 2, 3];
+        v.shuffle(&mut r);
     }
 
     #[test]
@@ -567,4 +730,24 @@ mod test {
         let expected = (NUM * N) / DENOM; // exact integer
         assert!(((sum - expected) as i32).abs() < 500);
     }
+
+    #[test]
+    fn test_one_in() {
+        let mut r = rng(112);
+        assert!((0..10).all(|_| r.one_in(1)));
+
+        const N: u32 = 100_000;
+        let sum = (0..N).filter(|_| r.one_in(10)).count() as i32;
+        // Have Binomial(N, 1/10) distribution
+        assert!((sum - (N / 10) as i32).abs() < 500);
+
+        // Large n, including the maximum
+        assert!(!r.one_in(u32::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_one_in_zero() {
+        rng(113).one_in(0);
+    }
 }
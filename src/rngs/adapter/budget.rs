@@ -0,0 +1,188 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper enforcing a maximum output per seed.
+
+use std::fmt;
+
+use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+
+/// A wrapper around an RNG which limits the output per seed.
+///
+/// `BudgetRng` tracks the number of bytes output since it was (re)seeded.
+/// Once the `budget` is exhausted, no further output is produced until the
+/// wrapped RNG is reseeded via [`reseed`] or [`set_rng`]. This enforces a
+/// maximum reseed interval, as required by some standards for deterministic
+/// random bit generators (e.g. NIST SP 800-90A).
+///
+/// Unlike [`ReseedingRng`], which reseeds automatically and continues with
+/// its current state if reseeding fails, this adapter never continues
+/// silently: the caller is responsible for reseeding.
+///
+/// # Errors and panics
+///
+/// A request which does not fit in the remaining budget fails as a whole;
+/// no partial output is produced and the budget is unchanged.
+/// [`try_fill_bytes`] reports this via an error of type
+/// [`BudgetExhausted`]. The other [`RngCore`] methods panic instead.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::BudgetRng;
+/// use rand::rngs::{OsRng, StdRng};
+/// use rand::{RngCore, SeedableRng};
+///
+/// let mut rng = BudgetRng::new(StdRng::from_entropy(), 64);
+/// let mut key = [0u8; 32];
+/// rng.try_fill_bytes(&mut key).unwrap();
+/// rng.try_fill_bytes(&mut key).unwrap();
+/// assert!(rng.try_fill_bytes(&mut key).is_err());
+///
+/// rng.reseed(OsRng).unwrap();
+/// assert!(rng.try_fill_bytes(&mut key).is_ok());
+/// ```
+///
+/// [`reseed`]: BudgetRng::reseed
+/// [`set_rng`]: BudgetRng::set_rng
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+/// [`try_fill_bytes`]: RngCore::try_fill_bytes
+#[derive(Debug)]
+pub struct BudgetRng<R> {
+    rng: R,
+    budget: u64,
+    remaining: u64,
+}
+
+impl<R: RngCore> BudgetRng<R> {
+    /// Wrap `rng`, allowing at most `budget` bytes of output per seed.
+    pub fn new(rng: R, budget: u64) -> Self {
+        BudgetRng {
+            rng,
+            budget,
+            remaining: budget,
+        }
+    }
+
+    /// The number of bytes which may be output before reseeding.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// The maximum number of bytes output per seed.
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// Replace the wrapped RNG with one newly seeded from `source`, and reset
+    /// the budget.
+    ///
+    /// On error the budget is unchanged.
+    pub fn reseed<S: RngCore>(&mut self, source: S) -> Result<(), Error>
+    where R: SeedableRng {
+        self.rng = R::from_rng(source)?;
+        self.remaining = self.budget;
+        Ok(())
+    }
+
+    /// Replace the wrapped RNG, and reset the budget.
+    pub fn set_rng(&mut self, rng: R) {
+        self.rng = rng;
+        self.remaining = self.budget;
+    }
+
+    fn consume(&mut self, bytes: usize) -> Result<(), BudgetExhausted> {
+        match self.remaining.checked_sub(bytes as u64) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                Ok(())
+            }
+            None => Err(BudgetExhausted),
+        }
+    }
+}
+
+impl<R: RngCore> RngCore for BudgetRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.consume(4).unwrap_or_else(|e| panic!("{}", e));
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.consume(8).unwrap_or_else(|e| panic!("{}", e));
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.consume(dest.len()).unwrap_or_else(|e| panic!("{}", e));
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.consume(dest.len()).map_err(Error::new)?;
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for BudgetRng<R> {}
+
+/// Error reported by [`BudgetRng`] when its output budget is exhausted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExhausted;
+
+impl fmt::Display for BudgetExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("output budget of RNG exhausted; reseed required")
+    }
+}
+
+impl std::error::Error for BudgetExhausted {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_budget() {
+        let mut rng = BudgetRng::new(StepRng::new(0, 1), 20);
+        assert_eq!(rng.next_u64(), 0);
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.remaining(), 8);
+
+        // Requests exceeding the budget fail as a whole
+        let mut buf = [0u8; 9];
+        let err = rng.try_fill_bytes(&mut buf).unwrap_err();
+        assert!(err.inner().downcast_ref::<BudgetExhausted>().is_some());
+        assert_eq!(rng.remaining(), 8);
+        assert_eq!(buf, [0; 9]);
+        rng.try_fill_bytes(&mut buf[..8]).unwrap();
+        assert_eq!(rng.remaining(), 0);
+        assert!(rng.try_fill_bytes(&mut []).is_ok());
+
+        rng.set_rng(StepRng::new(10, 1));
+        assert_eq!(rng.remaining(), 20);
+        assert_eq!(rng.next_u64(), 10);
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut rng = BudgetRng::new(rand_pcg::Pcg32::new(225, 0), 8);
+        rng.next_u64();
+        rng.reseed(crate::test::rng(226)).unwrap();
+        assert_eq!(rng.remaining(), 8);
+        assert_eq!(rng.budget(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "budget")]
+    fn test_panic() {
+        let mut rng = BudgetRng::new(StepRng::new(0, 1), 7);
+        rng.next_u64();
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper counting the output of an RNG.
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// A wrapper around an RNG which counts its output.
+///
+/// `CountingRng` forwards all calls to the wrapped RNG, counting the
+/// numbers of `u32` and `u64` words and bytes taken from it. This is useful
+/// to audit how much randomness an algorithm consumes, e.g. to check that a
+/// sampling algorithm's consumption (and thus the reproducibility of
+/// anything sampled after it) does not change between versions.
+///
+/// Counting is a few additions per call; the wrapper is otherwise
+/// transparent and may wrap (or be wrapped by) other adapters such as
+/// [`ReseedingRng`]. Failed calls to [`RngCore::try_fill_bytes`] are not
+/// counted.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::CountingRng;
+/// use rand::Rng;
+///
+/// let mut rng = CountingRng::new(rand::thread_rng());
+/// let _: f64 = rng.gen();
+/// let _: [u8; 3] = rng.gen();
+/// let counts = rng.counts();
+/// assert_eq!(counts.u64s, 1);
+/// assert_eq!(counts.total_bytes(), 8 + 3 * 4);
+/// ```
+///
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CountingRng<R> {
+    rng: R,
+    counts: RngCounts,
+}
+
+/// Counts of the output of an RNG, as reported by [`CountingRng::counts`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RngCounts {
+    /// Number of calls to `next_u32`
+    pub u32s: u64,
+    /// Number of calls to `next_u64`
+    pub u64s: u64,
+    /// Number of calls to `fill_bytes` and successful calls to
+    /// `try_fill_bytes`
+    pub fills: u64,
+    /// Number of bytes output by `fill_bytes` and `try_fill_bytes`
+    pub fill_bytes: u64,
+}
+
+impl RngCounts {
+    /// Total number of bytes output by all methods
+    pub fn total_bytes(&self) -> u64 {
+        self.u32s
+            .wrapping_mul(4)
+            .wrapping_add(self.u64s.wrapping_mul(8))
+            .wrapping_add(self.fill_bytes)
+    }
+}
+
+impl<R: RngCore> CountingRng<R> {
+    /// Wrap `rng`, with all counts zero.
+    pub fn new(rng: R) -> Self {
+        CountingRng {
+            rng,
+            counts: RngCounts::default(),
+        }
+    }
+
+    /// Get the counts of output since construction or the last
+    /// [`reset`](Self::reset)
+    pub fn counts(&self) -> RngCounts {
+        self.counts
+    }
+
+    /// Reset all counts to zero
+    pub fn reset(&mut self) {
+        self.counts = RngCounts::default();
+    }
+
+    /// Get a reference to the wrapped RNG
+    pub fn inner(&self) -> &R {
+        &self.rng
+    }
+
+    /// Get a mutable reference to the wrapped RNG
+    ///
+    /// Output taken directly from the wrapped RNG is not counted.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Unwrap the RNG, discarding the counts
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> RngCore for CountingRng<R> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.counts.u32s = self.counts.u32s.wrapping_add(1);
+        self.rng.next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.counts.u64s = self.counts.u64s.wrapping_add(1);
+        self.rng.next_u64()
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        self.count_fill(dest.len());
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)?;
+        self.count_fill(dest.len());
+        Ok(())
+    }
+}
+
+impl<R> CountingRng<R> {
+    #[inline(always)]
+    fn count_fill(&mut self, len: usize) {
+        self.counts.fills = self.counts.fills.wrapping_add(1);
+        self.counts.fill_bytes = self.counts.fill_bytes.wrapping_add(len as u64);
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for CountingRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+    use crate::Rng;
+
+    #[test]
+    fn test_counting() {
+        let mut rng = CountingRng::new(StepRng::new(3, 1));
+        assert_eq!(rng.next_u32(), 3);
+        assert_eq!(rng.next_u64(), 4);
+        rng.next_u64();
+        let mut buf = [0u8; 5];
+        rng.fill_bytes(&mut buf);
+        rng.try_fill_bytes(&mut buf[..2]).unwrap();
+        assert_eq!(rng.counts(), RngCounts {
+            u32s: 1,
+            u64s: 2,
+            fills: 2,
+            fill_bytes: 7,
+        });
+        assert_eq!(rng.counts().total_bytes(), 4 + 16 + 7);
+
+        rng.inner_mut().next_u64();
+        assert_eq!(rng.counts().u64s, 2);
+        rng.reset();
+        assert_eq!(rng.counts(), RngCounts::default());
+        assert_eq!(rng.into_inner().next_u64(), 9);
+    }
+
+    #[test]
+    fn test_counting_distributions() {
+        // Output is unchanged by counting
+        let mut rng = CountingRng::new(crate::test::rng(267));
+        let mut reference = crate::test::rng(267);
+        for _ in 0..10 {
+            assert_eq!(rng.gen_range(0..1000u32), reference.gen_range(0..1000u32));
+        }
+        let x: f64 = rng.gen();
+        assert_eq!(x, reference.gen::<f64>());
+        // Each f64 takes one u64; uniform u32 ranges take a u32 per attempt
+        assert_eq!(rng.counts().u64s, 1);
+        assert!(rng.counts().u32s >= 10);
+    }
+
+    #[test]
+    #[cfg(feature = "std_rng")]
+    fn test_counting_reseeding() {
+        use crate::rngs::adapter::ReseedingRng;
+        use crate::rngs::std::Core;
+        use crate::SeedableRng;
+
+        let reseeding = ReseedingRng::new(Core::seed_from_u64(0), 1024, StepRng::new(0, 1));
+        let mut rng = CountingRng::new(reseeding);
+        let mut buf = [0u32; 100];
+        rng.fill(&mut buf[..]);
+        assert_eq!(rng.counts().total_bytes(), 400);
+        assert_eq!(rng.inner().stats().bytes_since_seed, 512);
+    }
+}
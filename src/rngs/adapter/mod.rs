@@ -8,9 +8,18 @@
 
 //! Wrappers / adapters forming RNGs
 
+mod budget;
+mod counting;
 mod read;
+mod replay;
 mod reseeding;
 
-#[allow(deprecated)]
+pub use self::budget::{BudgetExhausted, BudgetRng};
+pub use self::counting::{CountingRng, RngCounts};
 pub use self::read::{ReadError, ReadRng};
-pub use self::reseeding::ReseedingRng;
+pub use self::replay::{RecordingRng, ReplayRng};
+pub use self::reseeding::{
+    fork_protection_token, invalidate_all, ForkProtectionToken, ReseedErrorPolicy, ReseedingRng,
+    ReseedingStats,
+};
+pub use rand_core::RngReadAdapter;
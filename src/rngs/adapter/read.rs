@@ -9,41 +9,60 @@
 
 //! A wrapper around any Read to treat it as an RNG.
 
-#![allow(deprecated)]
-
 use std::fmt;
-use std::io::Read;
+use std::io::{self, BufReader, Read};
 
 use rand_core::{impls, Error, RngCore};
 
 
-/// An RNG that reads random bytes straight from any type supporting
+/// An RNG that reads random bytes from any type supporting
 /// [`std::io::Read`], for example files.
 ///
+/// Reads are buffered, thus requests for a few bytes (e.g. via
+/// [`RngCore::next_u32`]) do not each cause a read from the underlying
+/// reader. Integers are read in little-endian order.
+///
 /// This will work best with an infinite reader, but that is not required.
+/// To replay a fixed sequence of bytes, e.g. recorded from a real run, use
+/// [`ReplayRng`] instead.
 ///
 /// This can be used with `/dev/urandom` on Unix but it is recommended to use
 /// [`OsRng`] instead.
 ///
-/// # Panics
+/// # Errors and panics
+///
+/// Reads are retried on interrupts. All other errors from the underlying
+/// reader are reported through [`try_fill_bytes`] as a [`ReadError`].
+///
+/// If the reader ends before a request is filled, the request fails with a
+/// [`ReadError`] of kind [`io::ErrorKind::UnexpectedEof`]. The bytes
+/// remaining in the reader are consumed by the failed request; later
+/// requests fail likewise unless the reader yields further data.
 ///
-/// `ReadRng` uses [`std::io::Read::read_exact`], which retries on interrupts.
-/// All other errors from the underlying reader, including when it does not
-/// have enough data, will only be reported through [`try_fill_bytes`].
-/// The other [`RngCore`] methods will panic in case of an error.
+/// The other [`RngCore`] methods panic in case of an error.
 ///
 /// [`OsRng`]: crate::rngs::OsRng
+/// [`ReplayRng`]: crate::rngs::adapter::ReplayRng
 /// [`try_fill_bytes`]: RngCore::try_fill_bytes
 #[derive(Debug)]
-#[deprecated(since="0.8.4", note="removal due to lack of usage")]
 pub struct ReadRng<R> {
-    reader: R,
+    reader: BufReader<R>,
 }
 
 impl<R: Read> ReadRng<R> {
     /// Create a new `ReadRng` from a `Read`.
     pub fn new(r: R) -> ReadRng<R> {
-        ReadRng { reader: r }
+        ReadRng {
+            reader: BufReader::new(r),
+        }
+    }
+
+    /// Create a new `ReadRng` from a `Read`, reading `capacity` bytes at a
+    /// time.
+    pub fn with_capacity(capacity: usize, r: R) -> ReadRng<R> {
+        ReadRng {
+            reader: BufReader::with_capacity(capacity, r),
+        }
     }
 }
 
@@ -76,10 +95,19 @@ impl<R: Read> RngCore for ReadRng<R> {
     }
 }
 
-/// `ReadRng` error type
+/// `ReadRng` and `ReplayRng` error type
 #[derive(Debug)]
-#[deprecated(since="0.8.4")]
-pub struct ReadError(std::io::Error);
+pub struct ReadError(pub(super) io::Error);
+
+impl ReadError {
+    /// The kind of the underlying I/O error
+    ///
+    /// This is [`io::ErrorKind::UnexpectedEof`] if the source of random
+    /// bytes was exhausted.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.0.kind()
+    }
+}
 
 impl fmt::Display for ReadError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -96,9 +124,10 @@ impl std::error::Error for ReadError {
 
 #[cfg(test)]
 mod test {
+    use std::io::{self, Read};
     use std::println;
 
-    use super::ReadRng;
+    use super::{ReadError, ReadRng};
     use crate::RngCore;
 
     #[test]
@@ -145,6 +174,29 @@ mod test {
 
         let result = rng.try_fill_bytes(&mut w);
         assert!(result.is_err());
-        println!("Error: {}", result.unwrap_err());
+        let err = result.unwrap_err();
+        println!("Error: {}", err);
+        let err = err.inner().downcast_ref::<ReadError>().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_reader_rng_buffered() {
+        struct CountingReader(usize);
+        impl Read for CountingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.0 += 1;
+                buf.fill(7);
+                Ok(buf.len())
+            }
+        }
+
+        let mut rng = ReadRng::with_capacity(64, CountingReader(0));
+        for _ in 0..16 {
+            assert_eq!(rng.next_u32(), 0x07070707);
+        }
+        assert_eq!(rng.reader.get_ref().0, 1);
+        rng.next_u32();
+        assert_eq!(rng.reader.get_ref().0, 2);
     }
 }
@@ -0,0 +1,247 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Recording and replaying random bytes.
+
+use std::io::{self, Write};
+use std::vec::Vec;
+
+use rand_core::{impls, Error, RngCore};
+
+use super::read::ReadError;
+
+/// An RNG which replays a fixed sequence of bytes.
+///
+/// Output is taken from the bytes in order; integers are read in
+/// little-endian order as by [`ReadRng`]. This allows replaying test vectors
+/// or output recorded from a real run, in order to reproduce a result
+/// deterministically; see also [`RecordingRng`].
+///
+/// # Errors and panics
+///
+/// A request which exceeds the remaining bytes fails as a whole: no bytes
+/// are consumed. [`try_fill_bytes`] reports this via a [`ReadError`] of kind
+/// [`io::ErrorKind::UnexpectedEof`]; the other [`RngCore`] methods panic.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::ReplayRng;
+/// use rand::RngCore;
+///
+/// let mut rng = ReplayRng::from_bytes(vec![1, 0, 0, 0, 2, 3]);
+/// assert_eq!(rng.next_u32(), 1);
+/// assert!(rng.try_fill_bytes(&mut [0; 4]).is_err());
+/// assert_eq!(rng.remaining(), 2);
+/// ```
+///
+/// [`ReadRng`]: crate::rngs::adapter::ReadRng
+/// [`try_fill_bytes`]: RngCore::try_fill_bytes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayRng {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl ReplayRng {
+    /// Construct, replaying `bytes`
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        ReplayRng { bytes, pos: 0 }
+    }
+
+    /// The number of bytes not yet replayed
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+}
+
+impl RngCore for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap_or_else(|err| {
+            panic!("replaying random bytes failed; error: {}", err)
+        });
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if dest.len() > self.remaining() {
+            let err = io::Error::new(io::ErrorKind::UnexpectedEof, "recorded bytes exhausted");
+            return Err(Error::new(ReadError(err)));
+        }
+        let end = self.pos + dest.len();
+        dest.copy_from_slice(&self.bytes[self.pos..end]);
+        self.pos = end;
+        Ok(())
+    }
+}
+
+/// A wrapper around an RNG which records all output.
+///
+/// Every random number produced by the wrapped RNG is passed through and
+/// written to a [`Write`] implementation (by default a `Vec<u8>`): `u32`
+/// and `u64` values as little-endian bytes, and the output of
+/// [`RngCore::fill_bytes`] as is. Replaying the recording with a
+/// [`ReplayRng`] thus reproduces the same sequence of random numbers, as
+/// long as they are requested in the same way. This makes it possible to
+/// reproduce a failing randomized test exactly, even where the RNG used is
+/// not seedable.
+///
+/// # Errors and panics
+///
+/// Errors of the wrapped RNG or the writer are reported through
+/// [`try_fill_bytes`]; the other [`RngCore`] methods panic in this case.
+/// Output of the RNG which could not be recorded is not passed on.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::RecordingRng;
+/// use rand::Rng;
+///
+/// let mut rng = RecordingRng::new(rand::thread_rng());
+/// let x: f64 = rng.gen();
+/// let y = rng.gen_range(0..10);
+///
+/// let mut replay = rng.replay();
+/// assert_eq!(replay.gen::<f64>(), x);
+/// assert_eq!(replay.gen_range(0..10), y);
+/// ```
+///
+/// [`try_fill_bytes`]: RngCore::try_fill_bytes
+#[derive(Debug)]
+pub struct RecordingRng<R, W = Vec<u8>> {
+    rng: R,
+    writer: W,
+}
+
+impl<R: RngCore> RecordingRng<R> {
+    /// Wrap `rng`, recording its output to a `Vec<u8>`
+    pub fn new(rng: R) -> Self {
+        RecordingRng {
+            rng,
+            writer: Vec::new(),
+        }
+    }
+
+    /// Construct a [`ReplayRng`] replaying the output recorded so far
+    pub fn replay(&self) -> ReplayRng {
+        ReplayRng::from_bytes(self.writer.clone())
+    }
+}
+
+impl<R: RngCore, W: Write> RecordingRng<R, W> {
+    /// Wrap `rng`, recording its output to `writer`
+    pub fn with_writer(rng: R, writer: W) -> Self {
+        RecordingRng { rng, writer }
+    }
+
+    /// Reference the writer
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Unwrap the RNG and the writer
+    pub fn into_inner(self) -> (R, W) {
+        (self.rng, self.writer)
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        self.writer.write_all(bytes).unwrap_or_else(|err| {
+            panic!("recording random bytes failed; error: {}", err)
+        });
+    }
+}
+
+impl<R: RngCore, W: Write> RngCore for RecordingRng<R, W> {
+    fn next_u32(&mut self) -> u32 {
+        let x = self.rng.next_u32();
+        self.record(&x.to_le_bytes());
+        x
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let x = self.rng.next_u64();
+        self.record(&x.to_le_bytes());
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        self.record(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)?;
+        self.writer.write_all(dest).map_err(Error::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_replay_rng() {
+        let bytes: Vec<u8> = (1..=20).collect();
+        let mut rng = ReplayRng::from_bytes(bytes);
+        assert_eq!(rng.next_u64(), u64::from_le_bytes([1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_eq!(rng.next_u32(), u32::from_le_bytes([9, 10, 11, 12]));
+        assert_eq!(rng.remaining(), 8);
+
+        let mut buf = [0u8; 9];
+        let err = rng.try_fill_bytes(&mut buf).unwrap_err();
+        let err = err.inner().downcast_ref::<ReadError>().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(rng.remaining(), 8);
+
+        rng.fill_bytes(&mut buf[..8]);
+        assert_eq!(buf[..8], [13, 14, 15, 16, 17, 18, 19, 20]);
+        rng.fill_bytes(&mut []);
+    }
+
+    #[test]
+    fn test_recording_rng() {
+        use crate::distributions::Uniform;
+        use crate::Rng;
+
+        let mut rng = RecordingRng::new(crate::test::rng(261));
+        let x: u32 = rng.gen();
+        let y: u64 = rng.gen();
+        let mut bytes = [0u8; 7];
+        rng.fill(&mut bytes);
+        let z = rng.sample(Uniform::new(0.0, 1.0).unwrap());
+        assert_eq!(rng.writer().len(), 4 + 8 + 7 + 8);
+
+        let mut replay = rng.replay();
+        assert_eq!(replay.gen::<u32>(), x);
+        assert_eq!(replay.gen::<u64>(), y);
+        let mut replayed = [0u8; 7];
+        replay.fill(&mut replayed);
+        assert_eq!(replayed, bytes);
+        assert_eq!(replay.sample(Uniform::new(0.0, 1.0).unwrap()), z);
+        assert_eq!(replay.remaining(), 0);
+
+        let mut rng = RecordingRng::with_writer(crate::test::rng(262), io::sink());
+        rng.next_u32();
+        let (mut inner, _) = rng.into_inner();
+        inner.next_u32();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_replay_rng_exhausted() {
+        ReplayRng::from_bytes(Vec::from([1, 2, 3])).next_u32();
+    }
+}
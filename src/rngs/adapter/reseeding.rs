@@ -10,7 +10,9 @@
 //! A wrapper around another PRNG that reseeds it after it
 //! generates a certain number of random bytes.
 
-use core::mem::size_of;
+use core::mem::{size_of, size_of_val};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
 
 use rand_core::block::{BlockRng, BlockRngCore, CryptoBlockRng};
 use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
@@ -23,10 +25,11 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// - On a manual call to [`reseed()`].
 /// - After `clone()`, the clone will be reseeded on first use.
 /// - When a process is forked on UNIX, the RNGs in both the parent and child
-///   processes will be reseeded just before the next call to
-///   [`BlockRngCore::generate`], i.e. "soon". For ChaCha and Hc128 this is a
-///   maximum of fifteen `u32` values before reseeding.
+///   processes discard any buffered output and reseed before producing
+///   any further output, thus the two processes never share a stream.
 /// - After the PRNG has generated a configurable number of random bytes.
+/// - Before the next output after a call to [`invalidate_all`], e.g. when a
+///   virtual machine or container is restored from a snapshot.
 ///
 /// # When should reseeding after a fixed number of generated bytes be used?
 ///
@@ -55,7 +58,9 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// never panic but try to handle the error intelligently through some
 /// combination of retrying and delaying reseeding until later.
 /// If handling the source error fails `ReseedingRng` will continue generating
-/// data from the wrapped PRNG without reseeding.
+/// data from the wrapped PRNG without reseeding. This behaviour may be
+/// configured via [`ReseedingRng::set_error_policy`]; see
+/// [`ReseedErrorPolicy`].
 ///
 /// Manually calling [`reseed()`] will not have this retry or delay logic, but
 /// reports the error.
@@ -81,6 +86,7 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// [`BlockRngCore`]: rand_core::block::BlockRngCore
 /// [`ReseedingRng::new`]: ReseedingRng::new
 /// [`reseed()`]: ReseedingRng::reseed
+/// [`invalidate_all`]: crate::rngs::adapter::invalidate_all
 #[derive(Debug)]
 pub struct ReseedingRng<R, Rsdr>(BlockRng<ReseedingCore<R, Rsdr>>)
 where
@@ -103,11 +109,169 @@ where
     }
 
     /// Reseed the internal PRNG.
+    ///
+    /// This may be used to force a reseed at specific points, e.g. after
+    /// handling untrusted input. On success, buffered output generated
+    /// before the reseed is discarded and the count towards the threshold
+    /// restarts. On failure, the PRNG is unchanged.
     pub fn reseed(&mut self) -> Result<(), Error> {
-        self.0.core.reseed()
+        self.0.core.reseed()?;
+        self.0.reset();
+        Ok(())
+    }
+
+    /// Get the number of bytes which may be output before the next automatic
+    /// reseed.
+    ///
+    /// This includes output buffered by the generator, and is a multiple of
+    /// the generator's block size since output is generated by whole blocks
+    /// before each check of the threshold. A reseed may happen
+    /// earlier if the process forks or [`invalidate_all`] is called. With a
+    /// threshold of zero (never reseed based on output), the result is very
+    /// large.
+    pub fn bytes_until_reseed(&self) -> u64 {
+        let results = R::Results::default();
+        let results = results.as_ref();
+        let block_len = size_of_val(results) as u64;
+        let buffered = size_of_val(&results[self.0.index().min(results.len())..]) as u64;
+        // Whole blocks are generated while the count is positive
+        let remaining = self.0.core.bytes_until_reseed.max(0) as u64;
+        let blocks = (remaining + block_len - 1) / block_len;
+        buffered + blocks * block_len
+    }
+
+    /// Set the number of generated bytes after which to reseed the PRNG.
+    ///
+    /// The new threshold applies to the output since the last reseed: if
+    /// more bytes than `threshold` were generated since, the PRNG is reseeded
+    /// before generating further output. Set it to zero to never reseed based
+    /// on the number of generated values.
+    pub fn set_threshold(&mut self, threshold: u64) {
+        let core = &mut self.0.core;
+        let bytes_since_seed = core.bytes_since_seed.min(i64::MAX as u64) as i64;
+        core.threshold = clamp_threshold(threshold);
+        core.bytes_until_reseed = core.threshold - bytes_since_seed;
+    }
+
+    /// Set the policy applied when automatic reseeding fails.
+    ///
+    /// The default is [`ReseedErrorPolicy::ContinueSilently`].
+    pub fn set_error_policy(&mut self, policy: ReseedErrorPolicy) {
+        self.0.core.error_policy = policy;
+    }
+
+    /// Discard buffered output if the process was forked or `invalidate_all`
+    /// was called since the last reseed, thus forcing a reseed before the
+    /// next output.
+    #[inline(always)]
+    fn check_generation(&mut self) {
+        if self.0.core.is_stale() || self.0.core.is_forked(fork::get_fork_counter()) {
+            self.0.reset();
+        }
+    }
+
+    /// Get statistics on the use of this generator since construction.
+    pub fn stats(&self) -> ReseedingStats {
+        let core = &self.0.core;
+        ReseedingStats {
+            bytes_since_seed: core.bytes_since_seed,
+            reseeds: core.reseeds,
+            last_reseed: core.last_reseed,
+        }
+    }
+}
+
+/// Generation of all `ReseedingRng`s; see `invalidate_all`
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// A token identifying the current generation of [`ReseedingRng`]s
+///
+/// See [`fork_protection_token`] and [`invalidate_all`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkProtectionToken(usize);
+
+impl ForkProtectionToken {
+    /// Returns `false` if [`invalidate_all`] was called since this token
+    /// was created.
+    pub fn is_current(&self) -> bool {
+        self.0 == GENERATION.load(Ordering::Relaxed)
     }
 }
 
+/// Get a token identifying the current generation of [`ReseedingRng`]s
+///
+/// Embedders may use this to detect whether [`invalidate_all`] was called
+/// (by any thread) since the token was obtained, e.g. to reseed their own
+/// generators.
+pub fn fork_protection_token() -> ForkProtectionToken {
+    ForkProtectionToken(GENERATION.load(Ordering::Relaxed))
+}
+
+/// Mark all existing [`ReseedingRng`]s (including [`ThreadRng`]) as stale
+///
+/// Each `ReseedingRng` discards any buffered output and reseeds itself before
+/// producing any further output. Call this after the state of the process
+/// may have been duplicated in a way which is not detected automatically
+/// (unlike `fork` on UNIX), e.g. after a virtual machine or container is
+/// restored from a snapshot or checkpoint.
+///
+/// Generators are not reseeded immediately, but on their next use, from
+/// the thread owning them. If reseeding fails, the [`ReseedErrorPolicy`] of
+/// the generator applies.
+///
+/// [`ThreadRng`]: crate::rngs::ThreadRng
+pub fn invalidate_all() {
+    // Note: fetch_add is defined to wrap on overflow
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Policy applied by [`ReseedingRng`] when automatic reseeding fails
+///
+/// Failures of automatic reseeding (periodic, after a fork or after a
+/// clone) are always logged (if the `log` feature is enabled). In all cases
+/// except a panic, the generator then continues with its current state and
+/// retries reseeding after `threshold` further bytes.
+///
+/// Manual calls to [`ReseedingRng::reseed`] report the error instead and are
+/// not affected by this policy.
+#[derive(Clone, Copy, Debug)]
+pub enum ReseedErrorPolicy {
+    /// Continue with the current state. This is the default.
+    ContinueSilently,
+    /// Call the given function with the error, then continue with the
+    /// current state.
+    ContinueWithCallback(fn(&Error)),
+    /// Continue with the current state on the first failure, but panic if
+    /// the next attempt to reseed also fails.
+    PanicOnSecondFailure,
+}
+
+impl Default for ReseedErrorPolicy {
+    fn default() -> Self {
+        ReseedErrorPolicy::ContinueSilently
+    }
+}
+
+/// Statistics on the use of a [`ReseedingRng`]
+///
+/// Returned by [`ReseedingRng::stats`] and [`thread_rng_stats`].
+///
+/// Bytes are counted when the wrapped PRNG generates a block of output, thus
+/// include output which is buffered but not yet consumed.
+///
+/// [`thread_rng_stats`]: crate::thread_rng_stats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReseedingStats {
+    /// Number of bytes generated since the last (re)seeding.
+    pub bytes_since_seed: u64,
+    /// Number of successful reseeds, not counting the initial seed.
+    pub reseeds: u64,
+    /// Time of the last successful reseed, or `None` if the generator was
+    /// never reseeded (or the system time is unavailable).
+    pub last_reseed: Option<SystemTime>,
+}
+
 // TODO: this should be implemented for any type where the inner type
 // implements RngCore, but we can't specify that because ReseedingCore is private
 impl<R, Rsdr: RngCore> RngCore for ReseedingRng<R, Rsdr>
@@ -116,19 +280,23 @@ where
 {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
+        self.check_generation();
         self.0.next_u32()
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
+        self.check_generation();
         self.0.next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.check_generation();
         self.0.fill_bytes(dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.check_generation();
         self.0.try_fill_bytes(dest)
     }
 }
@@ -159,6 +327,12 @@ struct ReseedingCore<R, Rsdr> {
     threshold: i64,
     bytes_until_reseed: i64,
     fork_counter: usize,
+    generation: usize,
+    bytes_since_seed: u64,
+    reseeds: u64,
+    last_reseed: Option<SystemTime>,
+    error_policy: ReseedErrorPolicy,
+    consecutive_failures: u32,
 }
 
 impl<R, Rsdr> BlockRngCore for ReseedingCore<R, Rsdr>
@@ -171,7 +345,7 @@ where
 
     fn generate(&mut self, results: &mut Self::Results) {
         let global_fork_counter = fork::get_fork_counter();
-        if self.bytes_until_reseed <= 0 || self.is_forked(global_fork_counter) {
+        if self.bytes_until_reseed <= 0 || self.is_forked(global_fork_counter) || self.is_stale() {
             // We get better performance by not calling only `reseed` here
             // and continuing with the rest of the function, but by directly
             // returning from a non-inlined function.
@@ -179,6 +353,7 @@ where
         }
         let num_bytes = results.as_ref().len() * size_of::<Self::Item>();
         self.bytes_until_reseed -= num_bytes as i64;
+        self.bytes_since_seed += num_bytes as u64;
         self.inner.generate(results);
     }
 }
@@ -190,20 +365,8 @@ where
 {
     /// Create a new `ReseedingCore`.
     fn new(rng: R, threshold: u64, reseeder: Rsdr) -> Self {
-        use ::core::i64::MAX;
         fork::register_fork_handler();
-
-        // Because generating more values than `i64::MAX` takes centuries on
-        // current hardware, we just clamp to that value.
-        // Also we set a threshold of 0, which indicates no limit, to that
-        // value.
-        let threshold = if threshold == 0 {
-            MAX
-        } else if threshold <= MAX as u64 {
-            threshold as i64
-        } else {
-            MAX
-        };
+        let threshold = clamp_threshold(threshold);
 
         ReseedingCore {
             inner: rng,
@@ -211,13 +374,24 @@ where
             threshold,
             bytes_until_reseed: threshold,
             fork_counter: 0,
+            generation: GENERATION.load(Ordering::Relaxed),
+            bytes_since_seed: 0,
+            reseeds: 0,
+            last_reseed: None,
+            error_policy: ReseedErrorPolicy::default(),
+            consecutive_failures: 0,
         }
     }
 
     /// Reseed the internal PRNG.
     fn reseed(&mut self) -> Result<(), Error> {
+        let generation = GENERATION.load(Ordering::Relaxed);
         R::from_rng(&mut self.reseeder).map(|result| {
+            self.generation = generation;
             self.bytes_until_reseed = self.threshold;
+            self.bytes_since_seed = 0;
+            self.reseeds += 1;
+            self.last_reseed = now();
             self.inner = result
         })
     }
@@ -236,6 +410,10 @@ where
         (self.fork_counter.wrapping_sub(global_fork_counter) as isize) < 0
     }
 
+    fn is_stale(&self) -> bool {
+        self.generation != GENERATION.load(Ordering::Relaxed)
+    }
+
     #[inline(never)]
     fn reseed_and_generate(
         &mut self, results: &mut <Self as BlockRngCore>::Results, global_fork_counter: usize,
@@ -243,23 +421,51 @@ where
         #![allow(clippy::if_same_then_else)] // false positive
         if self.is_forked(global_fork_counter) {
             info!("Fork detected, reseeding RNG");
+        } else if self.is_stale() {
+            info!("Generators invalidated, reseeding RNG");
         } else {
             trace!("Reseeding RNG (periodic reseed)");
         }
 
         let num_bytes = results.as_ref().len() * size_of::<<R as BlockRngCore>::Item>();
 
-        if let Err(e) = self.reseed() {
-            warn!("Reseeding RNG failed: {}", e);
-            let _ = e;
+        match self.reseed() {
+            Ok(()) => self.consecutive_failures = 0,
+            Err(e) => {
+                warn!("Reseeding RNG failed: {}", e);
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                match self.error_policy {
+                    ReseedErrorPolicy::ContinueSilently => {}
+                    ReseedErrorPolicy::ContinueWithCallback(f) => f(&e),
+                    ReseedErrorPolicy::PanicOnSecondFailure => {
+                        if self.consecutive_failures >= 2 {
+                            panic!("Reseeding RNG failed twice: {}", e);
+                        }
+                    }
+                }
+            }
         }
         self.fork_counter = global_fork_counter;
+        // As for forks, do not retry on every block if reseeding failed
+        self.generation = GENERATION.load(Ordering::Relaxed);
 
         self.bytes_until_reseed = self.threshold - num_bytes as i64;
+        self.bytes_since_seed += num_bytes as u64;
         self.inner.generate(results);
     }
 }
 
+// Because generating more values than `i64::MAX` takes centuries on current
+// hardware, we just clamp to that value. Also we set a threshold of 0, which
+// indicates no limit, to that value.
+fn clamp_threshold(threshold: u64) -> i64 {
+    if threshold == 0 || threshold > i64::MAX as u64 {
+        i64::MAX
+    } else {
+        threshold as i64
+    }
+}
+
 impl<R, Rsdr> Clone for ReseedingCore<R, Rsdr>
 where
     R: BlockRngCore + SeedableRng + Clone,
@@ -272,10 +478,27 @@ where
             threshold: self.threshold,
             bytes_until_reseed: 0, // reseed clone on first use
             fork_counter: self.fork_counter,
+            generation: self.generation,
+            bytes_since_seed: self.bytes_since_seed,
+            reseeds: self.reseeds,
+            last_reseed: self.last_reseed,
+            error_policy: self.error_policy,
+            consecutive_failures: 0,
         }
     }
 }
 
+// `SystemTime::now` panics on targets without a system clock
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn now() -> Option<SystemTime> {
+    Some(SystemTime::now())
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn now() -> Option<SystemTime> {
+    None
+}
+
 impl<R, Rsdr> CryptoBlockRng for ReseedingCore<R, Rsdr>
 where
     R: BlockRngCore<Item = u32> + SeedableRng + CryptoBlockRng,
@@ -341,10 +564,10 @@ mod fork {
 #[cfg(feature = "std_rng")]
 #[cfg(test)]
 mod test {
-    use super::ReseedingRng;
+    use super::{fork_protection_token, ForkProtectionToken, ReseedErrorPolicy, ReseedingRng};
     use crate::rngs::mock::StepRng;
     use crate::rngs::std::Core;
-    use crate::{Rng, SeedableRng};
+    use crate::{Error, Rng, RngCore, SeedableRng};
 
     #[test]
     fn test_reseeding() {
@@ -366,6 +589,64 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_stats() {
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1024, zero);
+        assert_eq!(reseeding.stats().bytes_since_seed, 0);
+        assert_eq!(reseeding.stats().reseeds, 0);
+        assert_eq!(reseeding.stats().last_reseed, None);
+
+        // Core generates blocks of 256 bytes
+        let _ = reseeding.gen::<u32>();
+        assert_eq!(reseeding.stats().bytes_since_seed, 256);
+        let mut buf = [0u8; 768];
+        reseeding.fill(&mut buf[..]);
+        assert_eq!(reseeding.stats().bytes_since_seed, 1024);
+        assert_eq!(reseeding.stats().reseeds, 0);
+
+        // Threshold is exhausted: reseed on next block
+        reseeding.fill(&mut buf[..300]);
+        let stats = reseeding.stats();
+        assert_eq!(stats.bytes_since_seed, 256);
+        assert_eq!(stats.reseeds, 1);
+        assert!(stats.last_reseed.is_some());
+
+        reseeding.reseed().unwrap();
+        assert_eq!(reseeding.stats().bytes_since_seed, 0);
+        assert_eq!(reseeding.stats().reseeds, 2);
+    }
+
+    #[test]
+    fn test_bytes_until_reseed() {
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1000, zero);
+        // Four blocks of 256 bytes are generated before a reseed
+        assert_eq!(reseeding.bytes_until_reseed(), 1024);
+        let first = reseeding.gen::<u32>();
+        assert_eq!(reseeding.bytes_until_reseed(), 1020);
+
+        // A manual reseed discards buffered output; since the reseeder
+        // always yields the same seed, output restarts
+        let _ = reseeding.gen::<u32>();
+        reseeding.reseed().unwrap();
+        assert_eq!(reseeding.bytes_until_reseed(), 1024);
+        assert_eq!(reseeding.gen::<u32>(), first);
+
+        reseeding.set_threshold(100);
+        assert_eq!(reseeding.bytes_until_reseed(), 252);
+        let mut buf = [0u8; 252];
+        reseeding.fill(&mut buf[..]);
+        assert_eq!(reseeding.stats().reseeds, 1);
+        let _ = reseeding.gen::<u32>();
+        assert_eq!(reseeding.stats().reseeds, 2);
+
+        reseeding.set_threshold(0);
+        assert!(reseeding.bytes_until_reseed() > 1 << 60);
+    }
+
     #[test]
     fn test_clone_reseeding() {
         #![allow(clippy::redundant_clone)]
@@ -382,4 +663,106 @@ mod test {
         let mut rng2 = rng1.clone();
         assert_eq!(first, rng2.gen::<u32>());
     }
+
+    /// A reseeder which always fails
+    #[derive(Debug)]
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!("FailingRng only supports try_fill_bytes")
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            unreachable!("FailingRng only supports try_fill_bytes")
+        }
+
+        fn fill_bytes(&mut self, _: &mut [u8]) {
+            unreachable!("FailingRng only supports try_fill_bytes")
+        }
+
+        fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Error> {
+            Err(Error::new("failing reseeder"))
+        }
+    }
+
+    #[test]
+    fn test_error_policy() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn callback(_: &Error) {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let rng = Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1, FailingRng);
+        assert!(reseeding.reseed().is_err());
+
+        // Default policy: continue
+        let mut buf = [0u32; 64];
+        reseeding.fill(&mut buf[..]);
+        reseeding.fill(&mut buf[..]);
+        assert_eq!(reseeding.stats().reseeds, 0);
+
+        reseeding.set_error_policy(ReseedErrorPolicy::ContinueWithCallback(callback));
+        reseeding.fill(&mut buf[..]);
+        reseeding.fill(&mut buf[..]);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_stale_generation() {
+        // Calling `invalidate_all` here would affect other tests running
+        // concurrently, thus simulate it by marking the generator stale.
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 0, zero);
+        assert!(fork_protection_token().is_current());
+        assert!(!ForkProtectionToken(fork_protection_token().0.wrapping_sub(1)).is_current());
+
+        let _ = reseeding.next_u32();
+        let reseeds = reseeding.stats().reseeds;
+        reseeding.0.core.generation = reseeding.0.core.generation.wrapping_sub(1);
+
+        // Buffered output is discarded and the generator reseeded
+        let _ = reseeding.next_u32();
+        let stats = reseeding.stats();
+        assert_eq!(stats.reseeds, reseeds + 1);
+        assert_eq!(stats.bytes_since_seed, 256);
+        assert!(!reseeding.0.core.is_stale());
+    }
+
+    #[test]
+    fn test_fork_detection() {
+        // As above, simulate a fork rather than bumping the global counter.
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 0, zero);
+
+        let _ = reseeding.next_u32();
+        let reseeds = reseeding.stats().reseeds;
+        reseeding.0.core.fork_counter = reseeding.0.core.fork_counter.wrapping_sub(1);
+
+        // The child reseeds before its next output, not only once the
+        // buffer is exhausted
+        let _ = reseeding.next_u32();
+        let stats = reseeding.stats();
+        assert_eq!(stats.reseeds, reseeds + 1);
+        assert_eq!(stats.bytes_since_seed, 256);
+        assert!(!reseeding.0.core.is_forked(super::fork::get_fork_counter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed twice")]
+    fn test_error_policy_panic() {
+        let rng = Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1, FailingRng);
+        reseeding.set_error_policy(ReseedErrorPolicy::PanicOnSecondFailure);
+        let mut buf = [0u32; 64];
+        // The first block is generated without reseeding
+        reseeding.fill(&mut buf[..]);
+        // First failure is tolerated
+        reseeding.fill(&mut buf[..]);
+        reseeding.fill(&mut buf[..]);
+    }
 }
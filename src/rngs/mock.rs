@@ -8,6 +8,7 @@
 
 //! Mock random number generator
 
+use core::fmt;
 use rand_core::{impls, Error, RngCore};
 
 #[cfg(feature = "serde1")]
@@ -48,7 +49,7 @@ pub struct StepRng {
 impl StepRng {
     /// Create a `StepRng`, yielding an arithmetic sequence starting with
     /// `initial` and incremented by `increment` each time.
-    pub fn new(initial: u64, increment: u64) -> Self {
+    pub const fn new(initial: u64, increment: u64) -> Self {
         StepRng {
             v: initial,
             a: increment,
@@ -81,6 +82,62 @@ impl RngCore for StepRng {
     }
 }
 
+/// A generator adapting a closure yielding `u64` values
+///
+/// Each call to [`RngCore::next_u64`] returns the next value of the closure.
+/// Other outputs are derived from these values: `next_u32` truncates a `u64`,
+/// and bytes are filled from successive `u64` values in little-endian order.
+/// This makes it easy to adapt an external source of randomness, such as an
+/// FFI callback or the generator of a simulation framework.
+///
+/// Note that no checks are made on the quality of the closure's output.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::mock::ClosureRng;
+///
+/// let mut state = 0u64;
+/// let mut rng = ClosureRng(|| {
+///     state += 3;
+///     state
+/// });
+/// let sample: [u64; 3] = rng.gen();
+/// assert_eq!(sample, [3, 6, 9]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ClosureRng<F>(pub F);
+
+impl<F: FnMut() -> u64> RngCore for ClosureRng<F> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        (self.0)()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<F> fmt::Debug for ClosureRng<F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "ClosureRng {{ .. }}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(any(feature = "alloc", feature = "serde1"))]
@@ -108,4 +165,20 @@ mod tests {
             rng.sample_iter(Standard).take(6).collect();
         assert_eq!(&result, &[false, true, false, true, false, true]);
     }
+
+    #[test]
+    fn test_closure_rng() {
+        use super::ClosureRng;
+        use rand_core::RngCore;
+
+        let mut values = [0x0807_0605_0403_0201u64, 0x1_0000_0002].iter().copied();
+        let mut rng = ClosureRng(move || values.next().unwrap());
+        let mut buf = [0u8; 10];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8, 2, 0]);
+
+        let mut rng = ClosureRng(|| 0x1_0000_0002);
+        assert_eq!(rng.next_u32(), 2);
+        assert_eq!(rng.next_u64(), 0x1_0000_0002);
+    }
 }
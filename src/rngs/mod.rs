@@ -56,7 +56,8 @@
 //! -   [`ThreadRng`], provided by the [`thread_rng`] function, is a handle to a
 //!     thread-local CSPRNG with periodic seeding from [`OsRng`]. Because this
 //!     is local, it is typically much faster than [`OsRng`]. It should be
-//!     secure, though the paranoid may prefer [`OsRng`].
+//!     secure, though the paranoid may prefer [`OsRng`]. [`GlobalRng`] is a
+//!     `Send + Sync` handle to the same generators, e.g. for async tasks.
 //! -   [`StdRng`] is a CSPRNG chosen for good performance and trust of security
 //!     (based on reviews, maturity and usage). The current algorithm is ChaCha12,
 //!     which is well established and rigorously analysed.
@@ -64,6 +65,11 @@
 //!     periodic reseeding.
 //! -   [`SmallRng`] is an **insecure** PRNG designed to be fast, simple, require
 //!     little memory, and have good output quality.
+//! -   [`PortableRng`] is an **insecure** PRNG like [`SmallRng`], but with
+//!     output which is reproducible across platforms and versions.
+//! -   [`Xoshiro256PlusPlus`] and [`Xoshiro128PlusPlus`] are the **insecure**
+//!     PRNGs behind [`SmallRng`], also supporting jumps to non-overlapping
+//!     substreams.
 //!
 //! The algorithms selected for [`StdRng`] and [`SmallRng`] may change in any
 //! release and may be platform-dependent, therefore they should be considered
@@ -102,18 +108,26 @@
 pub mod mock; // Public so we don't export `StepRng` directly, making it a bit
               // more clear it is intended for testing.
 
-#[cfg(all(feature = "small_rng", target_pointer_width = "64"))]
+#[cfg(feature = "small_rng")]
 mod xoshiro256plusplus;
-#[cfg(all(feature = "small_rng", not(target_pointer_width = "64")))]
+#[cfg(feature = "small_rng")]
 mod xoshiro128plusplus;
+#[cfg(feature = "small_rng")] mod portable;
 #[cfg(feature = "small_rng")] mod small;
 
+#[cfg(feature = "std_rng")] mod rng_tree;
+#[cfg(feature = "std_rng")] mod seed_deriver;
 #[cfg(feature = "std_rng")] mod std;
 #[cfg(all(feature = "std", feature = "std_rng"))] pub(crate) mod thread;
 
+#[cfg(feature = "small_rng")] pub use self::portable::PortableRng;
 #[cfg(feature = "small_rng")] pub use self::small::SmallRng;
+#[cfg(feature = "small_rng")] pub use self::xoshiro128plusplus::Xoshiro128PlusPlus;
+#[cfg(feature = "small_rng")] pub use self::xoshiro256plusplus::Xoshiro256PlusPlus;
+#[cfg(feature = "std_rng")] pub use self::rng_tree::RngTree;
+#[cfg(feature = "std_rng")] pub use self::seed_deriver::SeedDeriver;
 #[cfg(feature = "std_rng")] pub use self::std::StdRng;
-#[cfg(all(feature = "std", feature = "std_rng"))] pub use self::thread::ThreadRng;
+#[cfg(all(feature = "std", feature = "std_rng"))] pub use self::thread::{GlobalRng, NamedRng, ThreadRng, ThreadRngGuard, ThreadRngSnapshot};
 
 #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
 #[cfg(feature = "getrandom")] pub use rand_core::OsRng;
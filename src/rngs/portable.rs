@@ -0,0 +1,107 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A small fast RNG with reproducible output
+
+use super::xoshiro256plusplus::Xoshiro256PlusPlus;
+
+/// A small-state, fast non-crypto PRNG with reproducible output
+///
+/// `PortableRng` is like [`SmallRng`], except that its output is
+/// guaranteed: for a given seed, it produces the same sequence of values on
+/// all platforms (including 32-bit and WASM targets) and in all future
+/// versions of Rand. Changing its output is considered a breaking change.
+/// This makes it suitable for simulations and games which must replay
+/// exactly from a stored seed.
+///
+/// This is an alias of [`Xoshiro256PlusPlus`], adding no behaviour of its
+/// own: it names the generator whose output Rand guarantees, such that it
+/// will not be changed to another algorithm. The output is identical to that
+/// of `Xoshiro256PlusPlus` in the [rand_xoshiro] crate:
+///
+/// - [`SeedableRng::from_seed`] reads the state as four little-endian
+///   `u64` words; an all-zero seed is replaced by `seed_from_u64(0)`
+/// - [`SeedableRng::seed_from_u64`] expands its argument with SplitMix64
+/// - [`RngCore::next_u32`] yields the upper 32 bits of `next_u64`
+/// - [`RngCore::fill_bytes`] fills bytes from `next_u64` in little-endian
+///   order, using `next_u32` for a final chunk of 4 or fewer bytes
+///
+/// Note that the same is not true of values sampled *from* `PortableRng`,
+/// which depend on the algorithms used by the distributions: those of
+/// [`Rng::gen`] and [`Rng::gen_range`] for `usize` for example depend on
+/// the platform, and sampling algorithms may change in new versions.
+///
+/// `PortableRng` is not suitable where security against prediction is
+/// important; use [`StdRng`] instead.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::PortableRng;
+/// use rand::{RngCore, SeedableRng};
+///
+/// let mut rng = PortableRng::seed_from_u64(42);
+/// // This value is the same everywhere
+/// assert_eq!(rng.next_u64(), 15021278609987233951);
+/// ```
+///
+/// [`SmallRng`]: crate::rngs::SmallRng
+/// [`StdRng`]: crate::rngs::StdRng
+/// [`Rng::gen`]: crate::Rng::gen
+/// [`SeedableRng::from_seed`]: crate::SeedableRng::from_seed
+/// [`SeedableRng::seed_from_u64`]: crate::SeedableRng::seed_from_u64
+/// [`RngCore::next_u32`]: crate::RngCore::next_u32
+/// [`RngCore::fill_bytes`]: crate::RngCore::fill_bytes
+/// [`Rng::gen_range`]: crate::Rng::gen_range
+/// [rand_xoshiro]: https://crates.io/crates/rand_xoshiro
+#[cfg_attr(doc_cfg, doc(cfg(feature = "small_rng")))]
+pub type PortableRng = Xoshiro256PlusPlus;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand_core::{RngCore, SeedableRng};
+
+    // These reference vectors must not change.
+
+    #[test]
+    fn test_from_seed() {
+        let mut rng = PortableRng::from_seed(
+            [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0,
+             3, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0]);
+        // Produced with the reference implementation of Xoshiro256++
+        let expected = [
+            41943041, 58720359, 3588806011781223, 3591011842654386,
+            9228616714210784205, 9973669472204895162,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+        assert_eq!(rng.next_u32(), 3262190407);
+        assert_eq!(rng.next_u32(), 2888540305);
+
+        let zero = PortableRng::from_seed([0; 32]);
+        assert_eq!(zero, PortableRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn test_seed_from_u64() {
+        let mut rng = PortableRng::seed_from_u64(42);
+        let mut buf = [0u64; 4];
+        buf.iter_mut().for_each(|x| *x = rng.next_u64());
+        assert_eq!(buf, [15021278609987233951, 5881210131331364753, 18149643915985481100, 12933668939759105464]);
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut rng = PortableRng::seed_from_u64(7);
+        let mut buf = [0u8; 13];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [61, 145, 174, 42, 0, 26, 44, 14, 20, 158, 78, 250, 221]);
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A tree of reproducible generators
+
+use core::fmt;
+
+use crate::rngs::{SeedDeriver, StdRng};
+use crate::{CryptoRng, Error, RngCore, SeedableRng, SplitRng};
+
+/// A node in a tree of reproducible generators.
+///
+/// Each node is itself a generator (of type `R`), and may [`branch`] into
+/// child nodes identified by a label. The seed of a child depends only on
+/// the seed of its parent and its label (via [`SeedDeriver`]); it does not
+/// depend on how much output was consumed from the parent, nor on which
+/// other branches exist. Adding a branch, or consuming more values from
+/// one, thus never perturbs any sibling.
+///
+/// This is useful for deterministic procedural generation, where content is
+/// naturally organised as a hierarchy, e.g. `world / region / chunk`.
+///
+/// ```
+/// use rand::rngs::RngTree;
+/// use rand::Rng;
+///
+/// let world: RngTree = RngTree::from_u64(1234);
+/// let mut forest = world.branch("region-3").branch("forest");
+/// let trees: u32 = forest.gen_range(10..20);
+///
+/// // The same path always yields the same generator
+/// let mut again = world.branch("region-3").branch("forest");
+/// assert_eq!(again.gen_range(10..20), trees);
+/// ```
+///
+/// Nodes also implement [`SplitRng`], splitting off unlabelled children
+/// numbered in order of creation; this suits recursive algorithms, where
+/// labelling each sub-problem would be awkward.
+///
+/// [`branch`]: RngTree::branch
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std_rng")))]
+#[derive(Clone)]
+pub struct RngTree<R = StdRng> {
+    deriver: SeedDeriver,
+    rng: R,
+    splits: u64,
+}
+
+// Custom Debug implementation that does not expose the deriver
+impl<R: fmt::Debug> fmt::Debug for RngTree<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RngTree")
+            .field("rng", &self.rng)
+            .field("splits", &self.splits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: SeedableRng> RngTree<R> {
+    /// Construct a root node from a master seed.
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self::from_deriver(SeedDeriver::new(seed))
+    }
+
+    /// Construct a root node from a `u64` master seed.
+    ///
+    /// See [`SeedDeriver::from_u64`].
+    pub fn from_u64(state: u64) -> Self {
+        Self::from_deriver(SeedDeriver::from_u64(state))
+    }
+
+    /// Construct a node from a `SeedDeriver`.
+    ///
+    /// Branches of the node derive their seeds from `deriver` as by
+    /// [`SeedDeriver::child`].
+    pub fn from_deriver(deriver: SeedDeriver) -> Self {
+        let rng = deriver.derive("");
+        RngTree {
+            deriver,
+            rng,
+            splits: 0,
+        }
+    }
+
+    /// Get the child node with the given `label`.
+    ///
+    /// The result is independent of the state of `self`: branching twice
+    /// with the same label yields two identical nodes.
+    pub fn branch<L: AsRef<[u8]> + ?Sized>(&self, label: &L) -> Self {
+        Self::from_deriver(self.deriver.child(label))
+    }
+
+    /// Get the child node identified by an integer.
+    ///
+    /// This is convenient for indexed collections (e.g. chunk coordinates)
+    /// and equivalent to `self.branch(&index.to_le_bytes())`.
+    pub fn branch_index(&self, index: u64) -> Self {
+        self.branch(&index.to_le_bytes())
+    }
+
+    /// Get the seed deriver of this node
+    pub fn deriver(&self) -> &SeedDeriver {
+        &self.deriver
+    }
+}
+
+impl<R: RngCore> RngCore for RngTree<R> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for RngTree<R> {}
+
+impl<R: SeedableRng + RngCore> SplitRng for RngTree<R> {
+    /// Split off the next unlabelled child node.
+    ///
+    /// The `i`-th split of a node is derived from its seed and `i` only,
+    /// distinct from all labelled branches. Splitting neither consumes nor
+    /// perturbs the output of `self`.
+    fn split(&mut self) -> Self {
+        let child = Self::from_deriver(self.deriver.split(self.splits));
+        self.splits = self.splits.wrapping_add(1);
+        child
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Rng;
+
+    #[test]
+    fn test_branch() {
+        let root = RngTree::<StdRng>::from_u64(216);
+        let a = root.branch("a");
+        let b = root.branch("b");
+
+        // Consuming output of the root or siblings does not affect branches
+        let mut root2 = root.clone();
+        let mut a2 = a.clone();
+        let _: [u64; 10] = root2.gen();
+        let _: [u64; 10] = a2.gen();
+        let mut b2 = root2.branch("b");
+        assert_eq!(b.clone().next_u64(), b2.next_u64());
+        assert_eq!(a.branch("x").next_u64(), a2.branch("x").next_u64());
+
+        // Nodes are distinct
+        let mut values = [
+            root.clone().next_u64(),
+            a.clone().next_u64(),
+            b.clone().next_u64(),
+            a.branch("b").next_u64(),
+            root.branch_index(0).next_u64(),
+            root.branch_index(1).next_u64(),
+        ];
+        values.sort_unstable();
+        assert!(values.windows(2).all(|w| w[0] != w[1]));
+
+        assert_eq!(root.branch_index(7).next_u64(), root.branch(&7u64.to_le_bytes()).next_u64());
+
+        // The deriver is not exposed
+        #[cfg(feature = "std")]
+        assert!(!std::format!("{:?}", root).contains("deriver"));
+    }
+
+    #[test]
+    fn test_split() {
+        let mut root = RngTree::<StdRng>::from_u64(272);
+        let mut reference = root.clone();
+        let mut a = root.split();
+        let mut b = root.split();
+        // The output of the parent is not perturbed
+        assert_eq!(root.next_u64(), reference.next_u64());
+        // Splitting is deterministic
+        assert_eq!(reference.split().next_u64(), a.clone().next_u64());
+
+        // Nested splits are distinct from each other and from branches
+        let mut values = [
+            root.next_u64(),
+            a.next_u64(),
+            b.next_u64(),
+            a.split().next_u64(),
+            a.split().next_u64(),
+            b.split().next_u64(),
+            root.clone().branch_index(0).next_u64(),
+            root.clone().branch("").next_u64(),
+        ];
+        values.sort_unstable();
+        assert!(values.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_generic() {
+        let root = RngTree::<rand_pcg::Pcg32>::new([3; 32]);
+        assert_eq!(root.deriver(), &SeedDeriver::new([3; 32]));
+        let seed = SeedDeriver::new([3; 32]).child("x").derive_seed("");
+        let mut expected = rand_pcg::Pcg32::from_seed(seed);
+        assert_eq!(root.branch("x").next_u64(), expected.next_u64());
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Derivation of labelled seeds from a master seed
+
+use core::fmt;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{RngCore, SeedBytes, SeedableRng};
+
+/// Stream used to output derived seeds
+const SEED_STREAM: u64 = u64::MAX;
+/// Stream used to output the keys of child derivers
+const CHILD_STREAM: u64 = u64::MAX - 1;
+/// Stream used to output the keys of derivers split off by index
+const SPLIT_STREAM: u64 = u64::MAX - 2;
+
+/// Derives independent, named seeds from a single master seed.
+///
+/// Each call to [`SeedDeriver::derive`] hashes the master seed together with
+/// a label, yielding a seed which depends only on these two inputs. This
+/// gives stable sub-streams: seeding one generator per subsystem from its
+/// own label means that a subsystem consuming more (or less) randomness
+/// does not shift the values seen by any other subsystem, and subsystems may
+/// be added or removed without affecting the rest.
+///
+/// ```
+/// use rand::rngs::{SeedDeriver, StdRng};
+/// use rand::Rng;
+///
+/// let deriver = SeedDeriver::new([42; 32]);
+/// let mut physics: StdRng = deriver.derive("physics");
+/// let mut ai: StdRng = deriver.derive("ai");
+/// let (_x, _y): (f64, u32) = (physics.gen(), ai.gen());
+///
+/// // Derivation is deterministic
+/// let seed: [u8; 32] = deriver.derive_seed("ai");
+/// assert_eq!(seed, deriver.derive_seed::<[u8; 32], _>("ai"));
+/// ```
+///
+/// # Construction
+///
+/// The derivation is a keyed hash built from the ChaCha20 block function:
+/// the label (prefixed by its length) is absorbed eight bytes at a time, each
+/// step re-keying ChaCha20 with the output of the previous one, and the
+/// derived seed is read from the final key. Distinct labels therefore yield
+/// (computationally) independent seeds, and seeds reveal nothing about the
+/// master seed or each other.
+///
+/// The derivation is value-stable: unlike [`StdRng`], its algorithm will
+/// not change in future versions (except in a major release).
+///
+/// [`StdRng`]: crate::rngs::StdRng
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std_rng")))]
+#[derive(Clone, PartialEq, Eq)]
+pub struct SeedDeriver {
+    key: [u8; 32],
+}
+
+// Custom Debug implementation that does not expose the master seed
+impl fmt::Debug for SeedDeriver {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "SeedDeriver {{ .. }}")
+    }
+}
+
+impl SeedDeriver {
+    /// Construct from a master seed.
+    pub fn new(master_seed: [u8; 32]) -> Self {
+        SeedDeriver { key: master_seed }
+    }
+
+    /// Construct from a `u64` master seed.
+    ///
+    /// The seed is expanded as by [`SeedableRng::seed_from_u64`]; this is
+    /// convenient but limits the number of distinct master seeds.
+    pub fn from_u64(state: u64) -> Self {
+        let mut rng = ChaCha20Rng::seed_from_u64(state);
+        let mut key = [0; 32];
+        rng.fill_bytes(&mut key);
+        SeedDeriver { key }
+    }
+
+    /// Construct a seedable generator from the seed derived for `label`.
+    pub fn derive<R: SeedableRng, L: AsRef<[u8]> + ?Sized>(&self, label: &L) -> R {
+        R::from_seed(self.derive_seed(label))
+    }
+
+    /// Get the seed derived for `label`.
+    ///
+    /// The seed may be of any length; shorter seeds are prefixes of longer
+    /// seeds derived for the same label.
+    pub fn derive_seed<S, L>(&self, label: &L) -> S
+    where
+        S: SeedBytes,
+        L: AsRef<[u8]> + ?Sized,
+    {
+        let mut seed = S::zeroed();
+        self.output(label.as_ref(), SEED_STREAM, seed.as_mut());
+        seed
+    }
+
+    /// Get a new deriver whose master seed is derived for `label`.
+    ///
+    /// This allows building hierarchies of labels. The child is independent
+    /// of any seed derived by `derive` from this deriver, including for the
+    /// same label.
+    pub fn child<L: AsRef<[u8]> + ?Sized>(&self, label: &L) -> SeedDeriver {
+        let mut key = [0; 32];
+        self.output(label.as_ref(), CHILD_STREAM, &mut key);
+        SeedDeriver { key }
+    }
+
+    /// Get a new deriver for the `index`-th split of this deriver.
+    ///
+    /// Split derivers are distinct from all derivers returned by `child`.
+    pub(crate) fn split(&self, index: u64) -> SeedDeriver {
+        let mut key = [0; 32];
+        self.output(&index.to_le_bytes(), SPLIT_STREAM, &mut key);
+        SeedDeriver { key }
+    }
+
+    fn output(&self, label: &[u8], stream: u64, dest: &mut [u8]) {
+        // Prefixing the length makes the encoding of labels prefix-free
+        let mut key = prf(&self.key, label.len() as u64);
+        for chunk in label.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            key = prf(&key, u64::from_le_bytes(word));
+        }
+        let mut rng = ChaCha20Rng::from_seed(key);
+        rng.set_stream(stream);
+        rng.fill_bytes(dest);
+    }
+}
+
+/// The first block of ChaCha20 with the given key and stream
+fn prf(key: &[u8; 32], stream: u64) -> [u8; 32] {
+    let mut rng = ChaCha20Rng::from_seed(*key);
+    rng.set_stream(stream);
+    let mut out = [0; 32];
+    rng.fill_bytes(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::StdRng;
+
+    fn seed32(deriver: &SeedDeriver, label: &[u8]) -> [u8; 32] {
+        deriver.derive_seed(label)
+    }
+
+    #[test]
+    fn test_derive() {
+        let deriver = SeedDeriver::new([7; 32]);
+        let a = seed32(&deriver, b"physics");
+        let b = seed32(&deriver, b"ai");
+        assert_ne!(a, b);
+        assert_eq!(a, seed32(&deriver.clone(), b"physics"));
+        assert_eq!(a, deriver.derive_seed::<[u8; 32], _>("physics"));
+
+        // Labels which differ only by trailing zeros or padding
+        let labels: [&[u8]; 5] = [b"", b"\0", b"12345678", b"12345678\0", b"123456789"];
+        for (i, x) in labels.iter().enumerate() {
+            for y in &labels[..i] {
+                assert_ne!(seed32(&deriver, x), seed32(&deriver, y));
+            }
+        }
+
+        // Different master seeds
+        assert_ne!(a, seed32(&SeedDeriver::new([8; 32]), b"physics"));
+        assert_ne!(a, seed32(&SeedDeriver::from_u64(7), b"physics"));
+
+        // Shorter seeds are prefixes
+        let short: [u8; 16] = deriver.derive_seed("physics");
+        assert_eq!(short, a[..16]);
+
+        let mut rng1: StdRng = deriver.derive("ai");
+        let mut rng2 = StdRng::from_seed(b);
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+
+    #[test]
+    fn test_child() {
+        let deriver = SeedDeriver::from_u64(215);
+        let child = deriver.child("world");
+        assert_eq!(child, deriver.child("world"));
+        assert_ne!(child, deriver.child("world2"));
+        assert_ne!(child.key, seed32(&deriver, b"world"));
+        assert_ne!(seed32(&child, b"x"), seed32(&deriver, b"x"));
+        #[cfg(feature = "std")]
+        assert_eq!(std::format!("{:?}", child), "SeedDeriver { .. }");
+    }
+
+    #[test]
+    fn test_stable() {
+        // Derived seeds are value-stable
+        let deriver = SeedDeriver::from_u64(215);
+        let seed: [u8; 8] = deriver.derive_seed("physics");
+        assert_eq!(seed, [25, 92, 73, 172, 48, 250, 87, 8]);
+    }
+}
@@ -8,7 +8,7 @@
 
 //! A small fast RNG
 
-use rand_core::{Error, RngCore, SeedableRng};
+use rand_core::{Error, ParallelRng, RngCore, SeedableRng};
 
 #[cfg(target_pointer_width = "64")]
 type Rng = super::xoshiro256plusplus::Xoshiro256PlusPlus;
@@ -31,8 +31,8 @@ type Rng = super::xoshiro128plusplus::Xoshiro128PlusPlus;
 ///
 /// The algorithm is deterministic but should not be considered reproducible
 /// due to dependence on platform and possible replacement in future
-/// library versions. For a reproducible generator, use a named PRNG from an
-/// external crate, e.g. [rand_xoshiro] or [rand_chacha].
+/// library versions. For a reproducible generator, use [`PortableRng`] or a
+/// named PRNG from an external crate, e.g. [rand_xoshiro] or [rand_chacha].
 /// Refer also to [The Book](https://rust-random.github.io/book/guide-rngs.html).
 ///
 /// The PRNG algorithm in `SmallRng` is chosen to be efficient on the current
@@ -73,6 +73,7 @@ type Rng = super::xoshiro128plusplus::Xoshiro128PlusPlus;
 /// ```
 ///
 /// [`StdRng`]: crate::rngs::StdRng
+/// [`PortableRng`]: crate::rngs::PortableRng
 /// [`thread_rng`]: crate::thread_rng
 /// [rand_chacha]: https://crates.io/crates/rand_chacha
 /// [rand_xoshiro]: https://crates.io/crates/rand_xoshiro
@@ -120,3 +121,10 @@ impl SeedableRng for SmallRng {
         SmallRng(Rng::seed_from_u64(state))
     }
 }
+
+impl ParallelRng for SmallRng {
+    #[inline(always)]
+    fn jump(&mut self) {
+        self.0.jump();
+    }
+}
@@ -8,7 +8,7 @@
 
 //! The standard RNG
 
-use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use crate::{CryptoRng, Error, ParallelRng, RngCore, SeedableRng};
 
 pub(crate) use rand_chacha::ChaCha12Core as Core;
 
@@ -69,6 +69,18 @@ impl SeedableRng for StdRng {
     }
 }
 
+impl ParallelRng for StdRng {
+    #[inline(always)]
+    fn jump(&mut self) {
+        self.0.jump();
+    }
+
+    #[inline(always)]
+    fn fork(&mut self) -> Self {
+        StdRng(self.0.fork())
+    }
+}
+
 impl CryptoRng for StdRng {}
 
 
@@ -95,4 +107,13 @@ mod test {
 
         assert_eq!([x0, x1], target);
     }
+
+    #[test]
+    #[cfg(feature = "getrandom")]
+    fn test_stdrng_from_entropy() {
+        let mut rng0 = StdRng::from_entropy();
+        let mut rng1 = StdRng::try_from_entropy().unwrap();
+        // Fails with probability 2^-64
+        assert_ne!(rng0.next_u64(), rng1.next_u64());
+    }
 }
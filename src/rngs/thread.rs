@@ -8,15 +8,18 @@
 
 //! Thread-local random number generator
 
-use core::cell::UnsafeCell;
-use std::rc::Rc;
+use core::cell::{RefCell, UnsafeCell};
+use std::collections::BTreeMap;
+use std::rc::{Rc, Weak};
+use std::string::String;
 use std::thread_local;
 use std::fmt;
 
 use super::std::Core;
-use crate::rngs::adapter::ReseedingRng;
-use crate::rngs::OsRng;
+use crate::rngs::adapter::{ReseedingRng, ReseedingStats};
+use crate::rngs::{OsRng, StdRng};
 use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use rand_core::block::{BlockRngCore, CryptoBlockRng};
 
 // Rationale for using `UnsafeCell` in `ThreadRng`:
 //
@@ -51,8 +54,8 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 /// of security and performance.
 ///
 /// `ThreadRng` is automatically seeded from [`OsRng`] with periodic reseeding
-/// (every 64 kiB, as well as "soon" after a fork on Unix — see [`ReseedingRng`]
-/// documentation for details).
+/// (every 64 kiB, as well as before any output following a fork on Unix — see
+/// [`ReseedingRng`] documentation for details).
 ///
 /// Security must be considered relative to a threat model and validation
 /// requirements. `ThreadRng` attempts to meet basic security considerations
@@ -61,6 +64,11 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 /// leaking internal secrets e.g. via [`Debug`] implementation or serialization.
 /// Memory is not zeroized on drop.
 ///
+/// The generator may be replaced for the current thread using
+/// [`thread_rng_with`], e.g. to replay code using `thread_rng` with a fixed
+/// seed in tests, or [`thread_rng_seeded`], which additionally allows its
+/// state to be saved and restored via [`thread_rng_snapshot`].
+///
 /// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
 /// [`StdRng`]: crate::rngs::StdRng
 #[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
@@ -68,6 +76,11 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 pub struct ThreadRng {
     // Rc is explicitly !Send and !Sync
     rng: Rc<UnsafeCell<ReseedingRng<Core, OsRng>>>,
+    // Generator installed by `thread_rng_with`, if any, taking precedence
+    // over `rng`. Since user code may use `ThreadRng` internally, this uses a
+    // `RefCell` rather than an `UnsafeCell`. The reference is weak so that
+    // the generator is no longer used once its guard is dropped.
+    custom: Option<Weak<RefCell<dyn RngCore>>>,
 }
 
 /// Debug implementation does not leak internal state
@@ -90,6 +103,20 @@ thread_local!(
     }
 );
 
+thread_local!(
+    static THREAD_RNG_OVERRIDE: RefCell<Option<Rc<RefCell<dyn RngCore>>>> = RefCell::new(None);
+);
+
+thread_local!(
+    // The override installed by `thread_rng_seeded`, if it is the active one.
+    // This shares its allocation with `THREAD_RNG_OVERRIDE`.
+    static THREAD_RNG_SEEDED: RefCell<Option<Rc<RefCell<StdRng>>>> = RefCell::new(None);
+);
+
+thread_local!(
+    static NAMED_RNGS: RefCell<BTreeMap<String, Rc<RefCell<StdRng>>>> = RefCell::new(BTreeMap::new());
+);
+
 /// Access the thread-local generator
 ///
 /// Returns a reference to the local [`ThreadRng`], initializing the generator
@@ -110,7 +137,359 @@ thread_local!(
 #[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
 pub fn thread_rng() -> ThreadRng {
     let rng = THREAD_RNG_KEY.with(|t| t.clone());
-    ThreadRng { rng }
+    let custom = current_override().as_ref().map(Rc::downgrade);
+    ThreadRng { rng, custom }
+}
+
+fn current_override() -> Option<Rc<RefCell<dyn RngCore>>> {
+    // The override may already be destroyed when used in a thread-local
+    // destructor; fall back to the default generator in that case.
+    THREAD_RNG_OVERRIDE
+        .try_with(|o| o.borrow().clone())
+        .unwrap_or(None)
+}
+
+/// Get statistics on the thread-local generator of the current thread
+///
+/// This reports the number of bytes generated since the generator was last
+/// seeded, the number of reseeds and the time of the last reseed; see
+/// [`ReseedingStats`]. This may be used to monitor consumption of
+/// randomness, e.g. in services making heavy use of [`thread_rng`].
+///
+/// Only the default generator is reported on; generators installed via
+/// [`thread_rng_with`] are not included. Calling this function initializes
+/// the thread-local generator if it was not used yet on this thread.
+///
+/// ```
+/// let stats = rand::thread_rng_stats();
+/// println!("{} bytes since last reseed", stats.bytes_since_seed);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_stats() -> ReseedingStats {
+    THREAD_RNG_KEY.with(|t| {
+        // SAFETY: no mutable reference exists outside of the `RngCore`
+        // methods of `ThreadRng`, which do not call this function
+        let rng = unsafe { &*t.get() };
+        rng.stats()
+    })
+}
+
+/// Replace the generator used by [`thread_rng`] on the current thread
+///
+/// Until the returned guard is dropped, all [`ThreadRng`] handles obtained
+/// via [`thread_rng`] (and hence [`random`]) on this thread use `rng` instead
+/// of the default generator. This allows deterministic replay of code which
+/// internally uses `thread_rng`, e.g. in tests:
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::{Rng, SeedableRng};
+///
+/// fn roll_die() -> u8 {
+///     rand::thread_rng().gen_range(1..=6)
+/// }
+///
+/// let a = {
+///     let _guard = rand::thread_rng_with(StdRng::seed_from_u64(42));
+///     (roll_die(), roll_die())
+/// };
+/// let b = {
+///     let _guard = rand::thread_rng_with(StdRng::seed_from_u64(42));
+///     (roll_die(), roll_die())
+/// };
+/// assert_eq!(a, b);
+/// ```
+///
+/// Handles obtained *before* this call (and clones of them) keep using the
+/// generator they were created with. Overrides may be nested; dropping a
+/// guard restores the generator which was in use when it was created. Handles
+/// obtained while the override was active stop using it when the guard is
+/// dropped, continuing with the generator restored by the guard.
+///
+/// Since [`ThreadRng`] implements [`CryptoRng`], so must `rng`. Note however
+/// that a generator with a fixed seed is predictable: overrides of this kind
+/// are intended for testing and reproducing results, not for production use.
+///
+/// [`random`]: crate::random
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_with<R: RngCore + CryptoRng + 'static>(rng: R) -> ThreadRngGuard {
+    install_override(Rc::new(RefCell::new(rng)), None)
+}
+
+/// Replace the algorithm used by [`thread_rng`] on the current thread
+///
+/// This installs a generator using the block RNG core `C`, seeded from
+/// [`OsRng`] and reseeded exactly as the default generator is (see
+/// [`ReseedingRng`]), for example to use ChaCha20 rather than the default
+/// ChaCha12:
+///
+/// ```
+/// use rand::Rng;
+/// use rand_chacha::ChaCha20Core;
+///
+/// let _guard = rand::thread_rng_with_core::<ChaCha20Core>();
+/// let key: [u8; 32] = rand::thread_rng().gen();
+/// # let _ = key;
+/// ```
+///
+/// Unlike other overrides, this is intended for production use: since `C`
+/// is a [`CryptoBlockRng`], [`ThreadRng`] remains a CSPRNG. Otherwise the
+/// same rules as for [`thread_rng_with`] apply; in particular the override
+/// only applies to the current thread, thus should be installed at the start
+/// of each thread using `thread_rng`. Generators installed this way are not
+/// reported on by [`thread_rng_stats`].
+///
+/// # Panics
+///
+/// Panics if seeding from [`OsRng`] fails, as [`thread_rng`] does.
+///
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+/// [`CryptoBlockRng`]: rand_core::block::CryptoBlockRng
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_with_core<C>() -> ThreadRngGuard
+where
+    C: BlockRngCore<Item = u32> + SeedableRng + CryptoBlockRng + 'static,
+{
+    let core = C::from_rng(OsRng).unwrap_or_else(|err|
+            panic!("could not initialize thread_rng: {}", err));
+    thread_rng_with(ReseedingRng::new(core, THREAD_RNG_RESEED_THRESHOLD, OsRng))
+}
+
+/// Replace the generator used by [`thread_rng`] on the current thread with
+/// a [`StdRng`] seeded from `seed`
+///
+/// This behaves as [`thread_rng_with`]`(StdRng::seed_from_u64(seed))`, except
+/// that the state of the generator is included in [`thread_rng_snapshot`] and
+/// may be restored via [`restore_thread_rng`]. This may be used for a
+/// deterministic "demo mode" of a game, or to save and load game state
+/// including its randomness.
+///
+/// The same caveats as for [`thread_rng_with`] apply.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_seeded(seed: u64) -> ThreadRngGuard {
+    let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(seed)));
+    install_override(rng.clone(), Some(rng))
+}
+
+fn install_override(
+    rng: Rc<RefCell<dyn RngCore>>, seeded: Option<Rc<RefCell<StdRng>>>,
+) -> ThreadRngGuard {
+    let previous = THREAD_RNG_OVERRIDE.with(|o| o.borrow_mut().replace(rng));
+    let previous_seeded = THREAD_RNG_SEEDED.with(|s| s.replace(seeded));
+    ThreadRngGuard {
+        previous,
+        previous_seeded,
+    }
+}
+
+/// Guard restoring the previous generator of [`thread_rng`] when dropped
+///
+/// This `struct` is created by [`thread_rng_with`].
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[must_use = "the override is removed when the guard is dropped"]
+pub struct ThreadRngGuard {
+    // Rc is explicitly !Send and !Sync, tying the guard to this thread
+    previous: Option<Rc<RefCell<dyn RngCore>>>,
+    previous_seeded: Option<Rc<RefCell<StdRng>>>,
+}
+
+impl Drop for ThreadRngGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let _ = THREAD_RNG_OVERRIDE.try_with(|o| *o.borrow_mut() = previous);
+        let previous_seeded = self.previous_seeded.take();
+        let _ = THREAD_RNG_SEEDED.try_with(|s| *s.borrow_mut() = previous_seeded);
+    }
+}
+
+impl fmt::Debug for ThreadRngGuard {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "ThreadRngGuard {{ .. }}")
+    }
+}
+
+/// Access a named thread-local generator
+///
+/// Named generators are independent streams of random numbers, e.g. for
+/// separate game systems, such that consuming more values from one stream
+/// does not affect the others. Each is a [`StdRng`] which is seeded from
+/// [`thread_rng`] on first use on each thread; thus if [`thread_rng_seeded`]
+/// is used beforehand, named generators are deterministic too.
+///
+/// All [`NamedRng`] handles with the same `name` on a thread share one
+/// generator. The states of all named generators of a thread are included in
+/// [`thread_rng_snapshot`].
+///
+/// ```
+/// use rand::Rng;
+///
+/// let _guard = rand::thread_rng_seeded(7);
+/// let loot: u32 = rand::named_rng("loot").gen_range(0..100);
+/// let weather: bool = rand::named_rng("weather").gen();
+/// # let _ = (loot, weather);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn named_rng(name: &str) -> NamedRng {
+    if let Some(rng) = NAMED_RNGS.with(|m| m.borrow().get(name).cloned()) {
+        return NamedRng { rng };
+    }
+    // Seed outside of the borrow, in case a custom `thread_rng` uses `named_rng`
+    let seeded = StdRng::from_rng(thread_rng()).unwrap_or_else(|err|
+            panic!("could not initialize named_rng: {}", err));
+    let rng = NAMED_RNGS.with(|m| {
+        m.borrow_mut()
+            .entry(name.into())
+            .or_insert_with(|| Rc::new(RefCell::new(seeded)))
+            .clone()
+    });
+    NamedRng { rng }
+}
+
+/// A reference to a named thread-local generator
+///
+/// This `struct` is created by [`named_rng`]. Like [`ThreadRng`], the handle
+/// cannot be passed between threads.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Clone)]
+pub struct NamedRng {
+    rng: Rc<RefCell<StdRng>>,
+}
+
+/// Debug implementation does not leak internal state
+impl fmt::Debug for NamedRng {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "NamedRng {{ .. }}")
+    }
+}
+
+impl RngCore for NamedRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.borrow_mut().next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.borrow_mut().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.borrow_mut().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.borrow_mut().try_fill_bytes(dest)
+    }
+}
+
+/// A snapshot of the deterministic thread-local generators of a thread
+///
+/// This `struct` is created by [`thread_rng_snapshot`] and may be restored
+/// via [`restore_thread_rng`], any number of times.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Clone)]
+pub struct ThreadRngSnapshot {
+    seeded: Option<StdRng>,
+    named: BTreeMap<String, StdRng>,
+}
+
+/// Debug implementation does not leak internal state
+impl fmt::Debug for ThreadRngSnapshot {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "ThreadRngSnapshot {{ .. }}")
+    }
+}
+
+/// Save the state of the thread-local generators of the current thread
+///
+/// The snapshot includes the state of the generator installed by
+/// [`thread_rng_seeded`], if it is the active override of [`thread_rng`], and
+/// of all generators created by [`named_rng`] on this thread. The default
+/// generator of [`thread_rng`] and generators installed via
+/// [`thread_rng_with`] are not included, since their state is private.
+///
+/// ```
+/// use rand::Rng;
+///
+/// let _guard = rand::thread_rng_seeded(42);
+/// let saved = rand::thread_rng_snapshot();
+/// let a: (u64, u64) = (rand::random(), rand::named_rng("ai").gen());
+///
+/// rand::restore_thread_rng(&saved);
+/// let b: (u64, u64) = (rand::random(), rand::named_rng("ai").gen());
+/// assert_eq!(a, b);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_snapshot() -> ThreadRngSnapshot {
+    let seeded = THREAD_RNG_SEEDED.with(|s| s.borrow().as_ref().map(|rng| rng.borrow().clone()));
+    let named = NAMED_RNGS.with(|m| {
+        m.borrow()
+            .iter()
+            .map(|(name, rng)| (name.clone(), rng.borrow().clone()))
+            .collect()
+    });
+    ThreadRngSnapshot { seeded, named }
+}
+
+/// Restore the thread-local generators of the current thread from a snapshot
+///
+/// The active generator installed by [`thread_rng_seeded`] and the set of
+/// named generators are reset to their state in `snapshot`, including for
+/// existing [`ThreadRng`] and [`NamedRng`] handles. Named generators which
+/// did not exist when the snapshot was taken are removed (existing handles
+/// to them keep working independently).
+///
+/// # Panics
+///
+/// Panics if `snapshot` includes the state of a generator installed by
+/// [`thread_rng_seeded`] while no such generator is active.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn restore_thread_rng(snapshot: &ThreadRngSnapshot) {
+    if let Some(state) = &snapshot.seeded {
+        let rng = THREAD_RNG_SEEDED.with(|s| s.borrow().clone())
+            .expect("cannot restore thread_rng: no thread_rng_seeded generator is active");
+        *rng.borrow_mut() = state.clone();
+    }
+    NAMED_RNGS.with(|m| {
+        let mut m = m.borrow_mut();
+        m.retain(|name, _| snapshot.named.contains_key(name));
+        for (name, state) in &snapshot.named {
+            match m.get(name) {
+                Some(rng) => *rng.borrow_mut() = state.clone(),
+                None => {
+                    m.insert(name.clone(), Rc::new(RefCell::new(state.clone())));
+                }
+            }
+        }
+    });
+}
+
+impl ThreadRng {
+    /// Immediately reseed the generator from [`OsRng`]
+    ///
+    /// This may be used to force a reseed at specific points, e.g. after
+    /// handling untrusted input. Output buffered before the reseed is
+    /// discarded. This affects the default generator of the current thread
+    /// only, not a generator installed via [`thread_rng_with`].
+    pub fn reseed(&mut self) -> Result<(), Error> {
+        // SAFETY: We must make sure to stop using `rng` before anyone else
+        // creates another mutable reference
+        let rng = unsafe { &mut *self.rng.get() };
+        rng.reseed()
+    }
+
+    // The override used by this handle, if any
+    #[inline(always)]
+    fn custom(&mut self) -> Option<Rc<RefCell<dyn RngCore>>> {
+        let custom = self.custom.as_ref()?.upgrade();
+        if custom.is_some() {
+            return custom;
+        }
+        // The guard of the override was dropped: continue as a new handle
+        let current = current_override();
+        self.custom = current.as_ref().map(Rc::downgrade);
+        current
+    }
 }
 
 impl Default for ThreadRng {
@@ -122,6 +501,9 @@ impl Default for ThreadRng {
 impl RngCore for ThreadRng {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
+        if let Some(custom) = self.custom() {
+            return custom.borrow_mut().next_u32();
+        }
         // SAFETY: We must make sure to stop using `rng` before anyone else
         // creates another mutable reference
         let rng = unsafe { &mut *self.rng.get() };
@@ -130,6 +512,9 @@ impl RngCore for ThreadRng {
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
+        if let Some(custom) = self.custom() {
+            return custom.borrow_mut().next_u64();
+        }
         // SAFETY: We must make sure to stop using `rng` before anyone else
         // creates another mutable reference
         let rng = unsafe { &mut *self.rng.get() };
@@ -137,6 +522,9 @@ impl RngCore for ThreadRng {
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Some(custom) = self.custom() {
+            return custom.borrow_mut().fill_bytes(dest);
+        }
         // SAFETY: We must make sure to stop using `rng` before anyone else
         // creates another mutable reference
         let rng = unsafe { &mut *self.rng.get() };
@@ -144,6 +532,9 @@ impl RngCore for ThreadRng {
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if let Some(custom) = self.custom() {
+            return custom.borrow_mut().try_fill_bytes(dest);
+        }
         // SAFETY: We must make sure to stop using `rng` before anyone else
         // creates another mutable reference
         let rng = unsafe { &mut *self.rng.get() };
@@ -153,6 +544,59 @@ impl RngCore for ThreadRng {
 
 impl CryptoRng for ThreadRng {}
 
+/// A `Send + Sync` handle to the thread-local generator
+///
+/// `GlobalRng` is a zero-sized handle which may be moved between threads,
+/// held across `.await` points and shared with e.g. rayon closures. Each
+/// call uses the thread-local generator of the thread it is made on, as
+/// obtained by [`thread_rng`]: thus generators are striped per thread and
+/// no locking is required. It is the recommended handle for async tasks,
+/// which may be resumed on different threads of an executor.
+///
+/// As a consequence, the generator used depends on the thread: a generator
+/// installed for the current thread via [`thread_rng_with`] or
+/// [`thread_rng_seeded`] is used by `GlobalRng` too (such generators
+/// implement [`CryptoRng`], as `GlobalRng` does), while output is not
+/// reproducible where tasks move between threads. Each call accesses
+/// thread-local storage, thus where many random numbers are needed within a
+/// synchronous section of code, [`thread_rng`] is slightly faster.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::GlobalRng;
+/// use rand::Rng;
+///
+/// let mut rng = GlobalRng;
+/// let handle = std::thread::spawn(move || rng.gen_range(0..10));
+/// assert!(handle.join().unwrap() < 10);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlobalRng;
+
+impl RngCore for GlobalRng {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        thread_rng().next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        thread_rng().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        thread_rng().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        thread_rng().try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for GlobalRng {}
+
 
 #[cfg(test)]
 mod test {
@@ -164,10 +608,179 @@ mod test {
         assert_eq!(r.gen_range(0..1), 0);
     }
 
+    #[test]
+    fn test_thread_rng_with() {
+        use crate::rngs::StdRng;
+        use crate::{RngCore, SeedableRng};
+
+        let mut expected = StdRng::seed_from_u64(213);
+        let mut inner_expected = StdRng::seed_from_u64(214);
+        let mut before = crate::thread_rng();
+        let mut stale = {
+            let _guard = super::thread_rng_with(StdRng::seed_from_u64(213));
+            let mut r = crate::thread_rng();
+            assert_eq!(r.next_u64(), expected.next_u64());
+            // Handles share the installed generator
+            assert_eq!(r.clone().next_u64(), expected.next_u64());
+            assert_eq!(crate::thread_rng().next_u64(), expected.next_u64());
+
+            let mut inner_handle = {
+                let _inner = super::thread_rng_with(StdRng::seed_from_u64(214));
+                let mut inner_handle = crate::thread_rng();
+                assert_eq!(inner_handle.next_u64(), inner_expected.next_u64());
+                inner_handle
+            };
+            assert_eq!(crate::thread_rng().next_u64(), expected.next_u64());
+            // Handles obtained under the inner override revert to the outer
+            assert_eq!(inner_handle.next_u64(), expected.next_u64());
+
+            // Other threads are unaffected
+            let v = std::thread::spawn(|| crate::thread_rng().next_u64()).join().unwrap();
+            let next = expected.clone().next_u64();
+            assert_ne!(v, next);
+
+            // Existing handles are unaffected
+            assert_ne!(before.next_u64(), next);
+            r
+        };
+        // After the guard is dropped, no handle uses the override
+        let next = expected.next_u64();
+        assert_ne!(crate::thread_rng().next_u64(), next);
+        assert_ne!(stale.next_u64(), next);
+        assert_ne!(stale.next_u64(), expected.next_u64());
+    }
+
+    #[test]
+    fn test_thread_rng_with_core() {
+        use crate::RngCore;
+        use rand_chacha::ChaCha20Core;
+
+        let bytes = super::thread_rng_stats().bytes_since_seed;
+        {
+            let _guard = super::thread_rng_with_core::<ChaCha20Core>();
+            let mut buf = [0u8; 4096];
+            crate::thread_rng().fill_bytes(&mut buf);
+            assert!(buf.iter().any(|&b| b != 0));
+            // The default generator is not used
+            assert_eq!(super::thread_rng_stats().bytes_since_seed, bytes);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::Rng;
+
+        // Run on a new thread for a clean set of named generators
+        std::thread::spawn(|| {
+            let _guard = super::thread_rng_seeded(240);
+            let mut main = crate::thread_rng();
+            let mut named = super::named_rng("a");
+            let saved = super::thread_rng_snapshot();
+
+            let a: [u64; 3] = [main.gen(), named.gen(), super::named_rng("b").gen()];
+            let b_state = super::thread_rng_snapshot();
+            super::restore_thread_rng(&saved);
+            // "b" was created after the snapshot, so it is recreated, seeded
+            // from the restored `thread_rng`
+            let b: [u64; 3] = [main.gen(), named.gen(), super::named_rng("b").gen()];
+            assert_eq!(a, b);
+
+            // Snapshots may be restored repeatedly
+            super::restore_thread_rng(&b_state);
+            let c: u64 = super::named_rng("b").gen();
+            super::restore_thread_rng(&b_state);
+            assert_eq!(super::named_rng("b").gen::<u64>(), c);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_restore_unseeded() {
+        let saved = {
+            let _guard = super::thread_rng_seeded(241);
+            super::thread_rng_snapshot()
+        };
+        super::restore_thread_rng(&saved);
+    }
+
+    #[test]
+    fn test_thread_rng_stats() {
+        use crate::RngCore;
+        // Run on a new thread to get a fresh generator
+        std::thread::spawn(|| {
+            let before = crate::thread_rng_stats();
+            assert_eq!(before.bytes_since_seed, 0);
+            assert_eq!(before.reseeds, 0);
+
+            let mut buf = [0u8; 1000];
+            crate::thread_rng().fill_bytes(&mut buf);
+            assert!(crate::thread_rng_stats().bytes_since_seed >= 1000);
+
+            let mut buf = [0u8; 100 * 1024];
+            crate::thread_rng().fill_bytes(&mut buf);
+            let after = crate::thread_rng_stats();
+            assert_eq!(after.reseeds, 1);
+            assert!(after.last_reseed.is_some());
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn test_debug_output() {
         // We don't care about the exact output here, but it must not include
         // private CSPRNG state or the cache stored by BlockRng!
         assert_eq!(std::format!("{:?}", crate::thread_rng()), "ThreadRng { .. }");
     }
+
+    #[test]
+    fn test_global_rng() {
+        use super::GlobalRng;
+        use crate::Rng;
+
+        fn is_send_sync<T: Send + Sync>(_: T) {}
+        is_send_sync(GlobalRng);
+
+        std::thread::spawn(|| {
+            let mut rng = GlobalRng;
+            let _guard = crate::thread_rng_seeded(262);
+            let x: u64 = rng.gen();
+            let _guard = crate::thread_rng_seeded(262);
+            assert_eq!(crate::thread_rng().gen::<u64>(), x);
+        })
+        .join()
+        .unwrap();
+
+        std::thread::spawn(|| {
+            let mut rng = GlobalRng;
+            let guard = crate::thread_rng_seeded(262);
+            let _: u64 = rng.gen();
+            drop(guard);
+            // Once the guard is dropped, GlobalRng no longer follows the override
+            let y: u64 = rng.gen();
+            let _guard = crate::thread_rng_seeded(262);
+            let _: u64 = crate::thread_rng().gen();
+            assert_ne!(y, crate::thread_rng().gen::<u64>());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_thread_rng_reseed() {
+        use crate::RngCore;
+        std::thread::spawn(|| {
+            let mut rng = crate::thread_rng();
+            rng.next_u32();
+            let before = crate::thread_rng_stats().reseeds;
+            rng.reseed().unwrap();
+            let stats = crate::thread_rng_stats();
+            assert_eq!(stats.reseeds, before + 1);
+            assert_eq!(stats.bytes_since_seed, 0);
+        })
+        .join()
+        .unwrap();
+    }
 }
@@ -9,7 +9,7 @@
 #[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
 use rand_core::impls::{next_u64_via_u32, fill_bytes_via_next};
 use rand_core::le::read_u32_into;
-use rand_core::{SeedableRng, RngCore, Error};
+use rand_core::{SeedableRng, RngCore, Error, ParallelRng};
 
 /// A xoshiro128++ random number generator.
 ///
@@ -19,12 +19,66 @@ use rand_core::{SeedableRng, RngCore, Error};
 /// The algorithm used here is translated from [the `xoshiro128plusplus.c`
 /// reference source code](http://xoshiro.di.unimi.it/xoshiro128plusplus.c) by
 /// David Blackman and Sebastiano Vigna.
+///
+/// The period is `2^128 - 1`. Use [`jump`](Self::jump) and
+/// [`long_jump`](Self::long_jump) to split a generator into many
+/// non-overlapping substreams, e.g. one per thread of a parallel simulation.
+///
+/// This is the algorithm behind [`SmallRng`](crate::rngs::SmallRng) on
+/// 32-bit platforms. Unlike `SmallRng`, the output of this type for a given
+/// seed is fixed.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "small_rng")))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct Xoshiro128PlusPlus {
     s: [u32; 4],
 }
 
+impl Xoshiro128PlusPlus {
+    /// Jump forward, equivalently to `2^64` calls to `next_u32()`.
+    ///
+    /// This can be used to generate `2^64` non-overlapping subsequences for
+    /// parallel computations.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand::rngs::Xoshiro128PlusPlus;
+    ///
+    /// let rng1 = Xoshiro128PlusPlus::seed_from_u64(0);
+    /// let mut rng2 = rng1.clone();
+    /// rng2.jump();
+    /// let mut rng3 = rng2.clone();
+    /// rng3.jump();
+    /// ```
+    pub fn jump(&mut self) {
+        self.apply_jump(&[0x8764000b, 0xf542d2d3, 0x6fa035c3, 0x77f2db5b]);
+    }
+
+    /// Jump forward, equivalently to `2^96` calls to `next_u32()`.
+    ///
+    /// This can be used to generate `2^32` starting points, from each of
+    /// which [`jump`](Self::jump) will generate `2^32` non-overlapping
+    /// subsequences for parallel distributed computations.
+    pub fn long_jump(&mut self) {
+        self.apply_jump(&[0xb523952e, 0x0b6f099f, 0xccf5a0ef, 0x1c580662]);
+    }
+
+    fn apply_jump(&mut self, polynomial: &[u32; 4]) {
+        let mut s = [0; 4];
+        for &word in polynomial {
+            for b in 0..32 {
+                if word & (1 << b) != 0 {
+                    for (s, x) in s.iter_mut().zip(&self.s) {
+                        *s ^= x;
+                    }
+                }
+                self.next_u32();
+            }
+        }
+        self.s = s;
+    }
+}
+
 impl SeedableRng for Xoshiro128PlusPlus {
     type Seed = [u8; 16];
 
@@ -97,6 +151,14 @@ impl RngCore for Xoshiro128PlusPlus {
     }
 }
 
+impl ParallelRng for Xoshiro128PlusPlus {
+    /// Equivalent to [`Xoshiro128PlusPlus::jump`].
+    #[inline]
+    fn jump(&mut self) {
+        Xoshiro128PlusPlus::jump(self);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,4 +177,22 @@ mod tests {
             assert_eq!(rng.next_u32(), e);
         }
     }
+
+    #[test]
+    fn jump() {
+        // Jumping commutes with stepping the generator
+        let mut rng1 = Xoshiro128PlusPlus::seed_from_u64(1);
+        let mut rng2 = rng1.clone();
+        rng1.jump();
+        rng1.next_u32();
+        rng2.next_u32();
+        rng2.jump();
+        assert_eq!(rng1, rng2);
+
+        let mut rng = Xoshiro128PlusPlus::seed_from_u64(1);
+        rng.jump();
+        assert_eq!(rng.next_u32(), 1383713344);
+        rng.long_jump();
+        assert_eq!(rng.next_u32(), 2313515514);
+    }
 }
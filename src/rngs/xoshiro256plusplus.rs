@@ -9,7 +9,7 @@
 #[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
 use rand_core::impls::fill_bytes_via_next;
 use rand_core::le::read_u64_into;
-use rand_core::{SeedableRng, RngCore, Error};
+use rand_core::{SeedableRng, RngCore, Error, ParallelRng};
 
 /// A xoshiro256++ random number generator.
 ///
@@ -19,12 +19,66 @@ use rand_core::{SeedableRng, RngCore, Error};
 /// The algorithm used here is translated from [the `xoshiro256plusplus.c`
 /// reference source code](http://xoshiro.di.unimi.it/xoshiro256plusplus.c) by
 /// David Blackman and Sebastiano Vigna.
+///
+/// The period is `2^256 - 1`. Use [`jump`](Self::jump) and
+/// [`long_jump`](Self::long_jump) to split a generator into many
+/// non-overlapping substreams, e.g. one per thread of a parallel simulation.
+///
+/// This is the algorithm behind [`SmallRng`](crate::rngs::SmallRng) on
+/// 64-bit platforms, and is aliased as [`PortableRng`](crate::rngs::PortableRng).
+/// Unlike `SmallRng`, the output of this type for a given seed is fixed.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "small_rng")))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct Xoshiro256PlusPlus {
     s: [u64; 4],
 }
 
+impl Xoshiro256PlusPlus {
+    /// Jump forward, equivalently to `2^128` calls to `next_u64()`.
+    ///
+    /// This can be used to generate `2^128` non-overlapping subsequences for
+    /// parallel computations.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand::rngs::Xoshiro256PlusPlus;
+    ///
+    /// let rng1 = Xoshiro256PlusPlus::seed_from_u64(0);
+    /// let mut rng2 = rng1.clone();
+    /// rng2.jump();
+    /// let mut rng3 = rng2.clone();
+    /// rng3.jump();
+    /// ```
+    pub fn jump(&mut self) {
+        self.apply_jump(&[0x180ec6d33cfd0aba, 0xd5a61266f0c9392c, 0xa9582618e03fc9aa, 0x39abdc4529b1661c]);
+    }
+
+    /// Jump forward, equivalently to `2^192` calls to `next_u64()`.
+    ///
+    /// This can be used to generate `2^64` starting points, from each of
+    /// which [`jump`](Self::jump) will generate `2^64` non-overlapping
+    /// subsequences for parallel distributed computations.
+    pub fn long_jump(&mut self) {
+        self.apply_jump(&[0x76e15d3efefdcbbf, 0xc5004e441c522fb3, 0x77710069854ee241, 0x39109bb02acbe635]);
+    }
+
+    fn apply_jump(&mut self, polynomial: &[u64; 4]) {
+        let mut s = [0; 4];
+        for &word in polynomial {
+            for b in 0..64 {
+                if word & (1 << b) != 0 {
+                    for (s, x) in s.iter_mut().zip(&self.s) {
+                        *s ^= x;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = s;
+    }
+}
+
 impl SeedableRng for Xoshiro256PlusPlus {
     type Seed = [u8; 32];
 
@@ -99,6 +153,14 @@ impl RngCore for Xoshiro256PlusPlus {
     }
 }
 
+impl ParallelRng for Xoshiro256PlusPlus {
+    /// Equivalent to [`Xoshiro256PlusPlus::jump`].
+    #[inline]
+    fn jump(&mut self) {
+        Xoshiro256PlusPlus::jump(self);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +181,32 @@ mod tests {
             assert_eq!(rng.next_u64(), e);
         }
     }
+
+    #[test]
+    fn jump() {
+        // Jumping commutes with stepping the generator
+        let mut rng1 = Xoshiro256PlusPlus::seed_from_u64(1);
+        let mut rng2 = rng1.clone();
+        rng1.jump();
+        rng1.next_u64();
+        rng2.next_u64();
+        rng2.jump();
+        assert_eq!(rng1, rng2);
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        rng.jump();
+        assert_eq!(rng.next_u64(), 15779930236080080313);
+        rng.long_jump();
+        assert_eq!(rng.next_u64(), 4223013328586911466);
+    }
+
+    #[test]
+    fn fork() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        let mut expected = rng.clone();
+        let child = rng.fork();
+        assert_eq!(child, expected);
+        expected.jump();
+        assert_eq!(rng, expected);
+    }
 }
@@ -0,0 +1,186 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Low-discrepancy orderings of indices
+
+use core::iter::FusedIterator;
+
+use crate::Rng;
+
+/// Iterate over `0..n` in bit-reversed order.
+///
+/// Index `i` of the output is the bit-reversal of `i` as a `k`-bit number,
+/// where `2^k` is the smallest power of two not less than `n` (values not
+/// less than `n` are skipped). E.g. for `n = 8` the order is
+/// `0, 4, 2, 6, 1, 5, 3, 7`. Every prefix of this order is spread evenly
+/// over `0..n`: the first half of the output takes every second index, the
+/// first quarter every fourth index and so on. This makes it useful for
+/// progressive rendering or loading of data, where each prefix should be a
+/// representative sample while a random shuffle would leave gaps and
+/// clusters.
+///
+/// See [`bit_reversed_jittered`] for a randomized variant.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::bit_reversed;
+///
+/// let order: Vec<usize> = bit_reversed(6).collect();
+/// assert_eq!(order, [0, 4, 2, 1, 5, 3]);
+/// ```
+pub fn bit_reversed(n: usize) -> BitReversed {
+    BitReversed::new(n, 0)
+}
+
+/// Iterate over `0..n` in a randomized bit-reversed order.
+///
+/// This is as [`bit_reversed`], except that a random `k`-bit mask is XORed
+/// onto each bit-reversed index (a random digital shift). The order is thus
+/// random, but every prefix of it remains spread evenly over `0..n`: the
+/// first `2^j` outputs (for `2^j <= n`, and up to skipped values) fall into
+/// distinct blocks of `2^(k-j)` consecutive indices.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::bit_reversed_jittered;
+///
+/// let mut order: Vec<usize> = bit_reversed_jittered(&mut rand::thread_rng(), 8).collect();
+/// // The first two indices are in different halves of 0..8
+/// assert_ne!(order[0] < 4, order[1] < 4);
+/// order.sort();
+/// assert_eq!(order, [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+pub fn bit_reversed_jittered<R: Rng + ?Sized>(rng: &mut R, n: usize) -> BitReversed {
+    let bits = index_bits(n);
+    let mask = if bits == 0 {
+        0
+    } else {
+        // Use `u64` for portability; `bits <= usize::BITS` thus this fits
+        (rng.gen::<u64>() >> (64 - bits)) as usize
+    };
+    BitReversed::new(n, mask)
+}
+
+// The number of bits `k` with `2^k >= n`
+fn index_bits(n: usize) -> u32 {
+    if n <= 1 {
+        0
+    } else {
+        usize::BITS - (n - 1).leading_zeros()
+    }
+}
+
+/// An iterator over `0..n` in (randomized) bit-reversed order.
+///
+/// This struct is created by [`bit_reversed`] or [`bit_reversed_jittered`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitReversed {
+    n: usize,
+    bits: u32,
+    mask: usize,
+    // The next `k`-bit counter to reverse
+    counter: usize,
+    remaining: usize,
+}
+
+impl BitReversed {
+    fn new(n: usize, mask: usize) -> Self {
+        BitReversed {
+            n,
+            bits: index_bits(n),
+            mask,
+            counter: 0,
+            remaining: n,
+        }
+    }
+}
+
+impl Iterator for BitReversed {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // At most half of the counters yield values not less than `n`, and
+        // the counter cannot overflow before `n` values were yielded.
+        loop {
+            let reversed = if self.bits == 0 {
+                0
+            } else {
+                self.counter.reverse_bits() >> (usize::BITS - self.bits)
+            };
+            self.counter += 1;
+            let index = reversed ^ self.mask;
+            if index < self.n {
+                self.remaining -= 1;
+                return Some(index);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for BitReversed {}
+impl FusedIterator for BitReversed {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+    #[test]
+    fn test_bit_reversed() {
+        assert!(bit_reversed(8).eq([0, 4, 2, 6, 1, 5, 3, 7].iter().cloned()));
+        assert!(bit_reversed(5).eq([0, 4, 2, 1, 3].iter().cloned()));
+        assert!(bit_reversed(1).eq(0..1));
+        assert_eq!(bit_reversed(0).next(), None);
+
+        let mut rng = crate::test::rng(255);
+        for &n in &[0, 1, 2, 3, 17, 64, 1000] {
+            for iter in &[bit_reversed(n), bit_reversed_jittered(&mut rng, n)] {
+                assert_eq!(iter.len(), n);
+                let mut seen = [false; 1024];
+                for i in iter.clone() {
+                    assert!(!seen[i]);
+                    seen[i] = true;
+                }
+                assert!(seen[..n].iter().all(|&s| s));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_prefixes() {
+        let mut rng = crate::test::rng(256);
+        let order: Vec<usize> = bit_reversed_jittered(&mut rng, 64).collect();
+        // Each prefix of length 2^j hits each block of 64 / 2^j indices
+        for j in 0..=6 {
+            let len = 1 << j;
+            let mut blocks: Vec<usize> = order[..len].iter().map(|i| i / (64 / len)).collect();
+            blocks.sort_unstable();
+            assert!(blocks.into_iter().eq(0..len));
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(257);
+        let mut buf = [0; 6];
+        for (x, i) in buf.iter_mut().zip(bit_reversed_jittered(&mut rng, 6)) {
+            *x = i;
+        }
+        assert_eq!(buf, [3, 1, 5, 2, 0, 4]);
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bootstrap resampling
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use crate::distributions::{Distribution, Uniform};
+use crate::Rng;
+
+/// Evaluates `statistic` over `n_resamples` bootstrap resamples of `data`.
+///
+/// Each resample consists of `data.len()` elements drawn from `data`
+/// uniformly at random *with* replacement. The statistic is passed the
+/// resample as a slice of references into `data`, so elements are never
+/// cloned; a single buffer of `data.len()` references is reused for all
+/// resamples.
+///
+/// This is the lazy [`Bootstrap`] iterator, collected into a `Vec`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::bootstrap;
+///
+/// let data = [2.0, 4.0, 4.0, 5.0, 7.0, 9.0];
+/// let mut means = bootstrap(&mut rand::thread_rng(), &data, 1000, |xs| {
+///     xs.iter().copied().sum::<f64>() / xs.len() as f64
+/// });
+///
+/// // Approximate 90% confidence interval for the mean
+/// means.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// let (lo, hi) = (means[50], means[949]);
+/// assert!(lo <= hi);
+/// ```
+pub fn bootstrap<R, T, S, F>(rng: &mut R, data: &[T], n_resamples: usize, statistic: F) -> Vec<S>
+where
+    R: Rng + ?Sized,
+    F: FnMut(&[&T]) -> S,
+{
+    Bootstrap::new(rng, data, n_resamples, statistic).collect()
+}
+
+/// An iterator evaluating a statistic over bootstrap resamples of a slice.
+///
+/// Each item is the result of the statistic applied to a fresh resample of
+/// `data.len()` elements, drawn with replacement. See [`bootstrap`].
+///
+/// Indices are sampled from a single pre-constructed [`Uniform`]
+/// distribution, a whole resample at a time, which is considerably cheaper
+/// than one `gen_range` call per element. As elsewhere in this module,
+/// indices are sampled as `u32` where possible for portability.
+#[derive(Debug)]
+pub struct Bootstrap<'a, T, R, F> {
+    data: &'a [T],
+    rng: R,
+    index: IndexSampler,
+    buf: Vec<&'a T>,
+    remaining: usize,
+    statistic: F,
+}
+
+#[derive(Debug)]
+enum IndexSampler {
+    Empty,
+    U32(Uniform<u32>),
+    USize(Uniform<usize>),
+}
+
+impl<'a, T, R: Rng, F> Bootstrap<'a, T, R, F> {
+    /// Create an iterator over `n_resamples` evaluations of `statistic` on
+    /// resamples of `data`.
+    pub fn new<S>(rng: R, data: &'a [T], n_resamples: usize, statistic: F) -> Self
+    where
+        F: FnMut(&[&T]) -> S,
+    {
+        let len = data.len();
+        let index = if len == 0 {
+            IndexSampler::Empty
+        } else if len <= (u32::MAX as usize) {
+            IndexSampler::U32(Uniform::new(0, len as u32).unwrap())
+        } else {
+            IndexSampler::USize(Uniform::new(0, len).unwrap())
+        };
+        Bootstrap {
+            data,
+            rng,
+            index,
+            buf: Vec::with_capacity(len),
+            remaining: n_resamples,
+            statistic,
+        }
+    }
+
+    fn resample(&mut self) {
+        let data = self.data;
+        let rng = &mut self.rng;
+        self.buf.clear();
+        match &self.index {
+            IndexSampler::Empty => {}
+            IndexSampler::U32(distr) => self
+                .buf
+                .extend((0..data.len()).map(|_| &data[distr.sample(rng) as usize])),
+            IndexSampler::USize(distr) => self
+                .buf
+                .extend((0..data.len()).map(|_| &data[distr.sample(rng)])),
+        }
+    }
+}
+
+impl<'a, T, R, F, S> Iterator for Bootstrap<'a, T, R, F>
+where
+    R: Rng,
+    F: FnMut(&[&T]) -> S,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.resample();
+        Some((self.statistic)(&self.buf))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T, R, F, S> ExactSizeIterator for Bootstrap<'a, T, R, F>
+where
+    R: Rng,
+    F: FnMut(&[&T]) -> S,
+{
+}
+
+impl<'a, T, R, F, S> FusedIterator for Bootstrap<'a, T, R, F>
+where
+    R: Rng,
+    F: FnMut(&[&T]) -> S,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap() {
+        let mut r = crate::test::rng(206);
+        let data = [1u32, 2, 3, 4, 5, 6, 7, 8];
+
+        let mut calls = 0;
+        let sums = bootstrap(&mut r, &data, 100, |xs| {
+            calls += 1;
+            assert_eq!(xs.len(), data.len());
+            xs.iter().map(|&&x| x).sum::<u32>()
+        });
+        assert_eq!(calls, 100);
+        assert_eq!(sums.len(), 100);
+        assert!(sums.iter().all(|&s| (8..=64).contains(&s)));
+        // Resamples are almost surely not all identical to the data
+        assert!(sums.iter().any(|&s| s != 36));
+
+        let mut iter = Bootstrap::new(&mut r, &data, 3, |xs| xs.len());
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next(), Some(8));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.nth(1), Some(8));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_bootstrap_empty() {
+        let mut r = crate::test::rng(207);
+        let data: [u8; 0] = [];
+        assert_eq!(bootstrap(&mut r, &data, 2, |xs| xs.len()), [0, 0]);
+        assert_eq!(bootstrap(&mut r, &[1], 0, |xs| xs.len()), []);
+    }
+
+    #[test]
+    fn test_bootstrap_stable() {
+        let mut r = crate::test::rng(208);
+        let data = ['a', 'b', 'c', 'd'];
+        let resamples = bootstrap(&mut r, &data, 2, |xs| {
+            let mut s = [' '; 4];
+            for (c, &&x) in s.iter_mut().zip(xs.iter()) {
+                *c = x;
+            }
+            s
+        });
+        assert_eq!(resamples, [['c', 'a', 'a', 'd'], ['a', 'c', 'a', 'a']]);
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Boolean sequences with an exact number of successes
+
+use core::iter::FusedIterator;
+
+use crate::distributions::BernoulliError;
+use crate::Rng;
+
+/// Generate `n` booleans with exactly `round(p * n)` of them `true`.
+///
+/// Unlike `n` independent [`Bernoulli`] trials, where the number of `true`
+/// values varies randomly, the count is fixed and only the positions of the
+/// `true` values are random: each of the possible sequences is equally
+/// likely. This is useful where a budget must be respected exactly, e.g. to
+/// shed exactly 10% of a batch of requests.
+///
+/// Values are generated lazily by the returned [`ExactBernoulli`] iterator
+/// using selection sampling, taking one random number per value and `O(1)`
+/// memory. Use [`ExactBernoulli::with_count`] to specify the count directly.
+///
+/// Fails if `p` is not in `[0, 1]`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::exact_bernoulli;
+///
+/// let shed = exact_bernoulli(rand::thread_rng(), 1000, 0.1).unwrap();
+/// assert_eq!(shed.filter(|&drop| drop).count(), 100);
+/// ```
+///
+/// [`Bernoulli`]: crate::distributions::Bernoulli
+pub fn exact_bernoulli<R: Rng>(rng: R, n: usize, p: f64) -> Result<ExactBernoulli<R>, BernoulliError> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err(BernoulliError::InvalidProbability);
+    }
+    // `p * n + 0.5` is non-negative, so truncation rounds to nearest
+    let count = ((p * n as f64 + 0.5) as usize).min(n);
+    Ok(ExactBernoulli::with_count(rng, n, count))
+}
+
+/// An iterator over booleans with an exact number of `true` values.
+///
+/// This struct is created by [`exact_bernoulli`] or
+/// [`ExactBernoulli::with_count`].
+#[derive(Debug)]
+pub struct ExactBernoulli<R> {
+    rng: R,
+    remaining: usize,
+    remaining_true: usize,
+}
+
+impl<R: Rng> ExactBernoulli<R> {
+    /// Create an iterator over `n` booleans of which exactly `count` are
+    /// `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count > n`.
+    pub fn with_count(rng: R, n: usize, count: usize) -> Self {
+        assert!(count <= n, "count must not exceed n");
+        ExactBernoulli {
+            rng,
+            remaining: n,
+            remaining_true: count,
+        }
+    }
+}
+
+impl<R: Rng> Iterator for ExactBernoulli<R> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // Each remaining position is `true` with equal probability. When all
+        // or none of the remaining values are `true`, no randomness is used.
+        let value = if self.remaining_true == 0 {
+            false
+        } else if self.remaining_true == self.remaining {
+            true
+        } else {
+            super::gen_index(&mut self.rng, self.remaining) < self.remaining_true
+        };
+        self.remaining -= 1;
+        self.remaining_true -= value as usize;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<R: Rng> ExactSizeIterator for ExactBernoulli<R> {}
+impl<R: Rng> FusedIterator for ExactBernoulli<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exact_bernoulli() {
+        let mut rng = crate::test::rng(243);
+        for &(n, p, count) in &[
+            (0, 0.5, 0),
+            (1, 0.5, 1),
+            (10, 0.0, 0),
+            (10, 1.0, 10),
+            (10, 0.25, 3),
+            (1000, 0.1, 100),
+        ] {
+            let seq = exact_bernoulli(&mut rng, n, p).unwrap();
+            assert_eq!(seq.len(), n);
+            assert_eq!(seq.filter(|&b| b).count(), count);
+        }
+        assert!(exact_bernoulli(&mut rng, 10, 1.5).is_err());
+        assert!(exact_bernoulli(&mut rng, 10, f64::NAN).is_err());
+
+        // Each position is equally likely to be `true`
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let mut seq = ExactBernoulli::with_count(&mut rng, 4, 1);
+            counts[seq.position(|b| b).unwrap()] += 1;
+        }
+        // Expect 1000 of each
+        assert!(counts.iter().all(|c| (900..1100).contains(c)), "{:?}", counts);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_count_too_large() {
+        ExactBernoulli::with_count(crate::test::rng(244), 3, 4);
+    }
+
+    #[test]
+    fn value_stability() {
+        let seq = ExactBernoulli::with_count(crate::test::rng(245), 8, 3);
+        let mut buf = [false; 8];
+        for (x, b) in buf.iter_mut().zip(seq) {
+            *x = b;
+        }
+        assert_eq!(buf, [false, false, true, false, false, true, false, true]);
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A keyed permutation of `0..n`
+
+use crate::Rng;
+
+const ROUNDS: usize = 4;
+
+/// A keyed bijection on `0..n`, mapping numbers to "random-looking" numbers
+/// reversibly and without tables.
+///
+/// The permutation is determined by `n` and a 32-byte key, given by
+/// [`KeyedPermutation::from_seed`] or sampled by [`KeyedPermutation::new`].
+/// It may be used e.g. to map sequential ids to ids that do not reveal the
+/// sequence, to iterate over a range in random order (see
+/// [`random_order`]), or to pick a random subset of a huge range by
+/// permuting `0..k`.
+///
+/// This is a balanced Feistel network of four rounds on the smallest even
+/// number of bits covering `0..n`, restricted to `0..n` by cycle walking:
+/// values not less than `n` are permuted again until they fall into `0..n`.
+/// This takes fewer than four rounds of the network on average. Both
+/// [`permute`] and [`inverse`] take `O(1)` time and the permutation uses
+/// constant memory.
+///
+/// The permutation is *not* cryptographically secure; the round function
+/// is a fast integer hash (the SplitMix64 finalizer). Its output for a given
+/// seed is stable across platforms and versions of Rand.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::KeyedPermutation;
+///
+/// let perm = KeyedPermutation::from_seed(1_000_000, [42; 32]);
+/// let public_id = perm.permute(17);
+/// assert!(public_id < 1_000_000);
+/// assert_eq!(perm.inverse(public_id), 17);
+/// ```
+///
+/// [`random_order`]: crate::seq::random_order
+/// [`permute`]: KeyedPermutation::permute
+/// [`inverse`]: KeyedPermutation::inverse
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyedPermutation {
+    n: u64,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl KeyedPermutation {
+    /// Construct a permutation of `0..n` with a random key
+    pub fn new<R: Rng + ?Sized>(rng: &mut R, n: u64) -> Self {
+        let mut keys = [0; ROUNDS];
+        for key in keys.iter_mut() {
+            *key = rng.gen();
+        }
+        Self::from_keys(n, keys)
+    }
+
+    /// Construct the permutation of `0..n` given by `seed`
+    pub fn from_seed(n: u64, seed: [u8; 32]) -> Self {
+        let mut keys = [0; ROUNDS];
+        for (key, chunk) in keys.iter_mut().zip(seed.chunks_exact(8)) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            *key = u64::from_le_bytes(bytes);
+        }
+        Self::from_keys(n, keys)
+    }
+
+    fn from_keys(n: u64, keys: [u64; ROUNDS]) -> Self {
+        // The number of bits `k` with `2^k >= n`, rounded up to a positive
+        // even number
+        let bits = if n <= 1 { 0 } else { u64::BITS - (n - 1).leading_zeros() };
+        let half_bits = ((bits + 1) / 2).max(1);
+        KeyedPermutation { n, half_bits, keys }
+    }
+
+    /// Returns `n`, the size of the domain `0..n`
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Map `x` to its image in `0..n`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x >= n`.
+    pub fn permute(&self, x: u64) -> u64 {
+        assert!(x < self.n, "value out of range of permutation");
+        let mut x = self.encrypt(x);
+        while x >= self.n {
+            x = self.encrypt(x);
+        }
+        x
+    }
+
+    /// Map `y` back to the `x` with `permute(x) == y`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y >= n`.
+    pub fn inverse(&self, y: u64) -> u64 {
+        assert!(y < self.n, "value out of range of permutation");
+        let mut y = self.decrypt(y);
+        while y >= self.n {
+            y = self.decrypt(y);
+        }
+        y
+    }
+
+    fn mask(&self) -> u64 {
+        (1 << self.half_bits) - 1
+    }
+
+    fn encrypt(&self, x: u64) -> u64 {
+        let mask = self.mask();
+        let (mut left, mut right) = (x >> self.half_bits, x & mask);
+        for &key in &self.keys {
+            let next = left ^ (round(right, key) & mask);
+            left = right;
+            right = next;
+        }
+        (left << self.half_bits) | right
+    }
+
+    fn decrypt(&self, y: u64) -> u64 {
+        let mask = self.mask();
+        let (mut left, mut right) = (y >> self.half_bits, y & mask);
+        for &key in self.keys.iter().rev() {
+            let prev = right ^ (round(left, key) & mask);
+            right = left;
+            left = prev;
+        }
+        (left << self.half_bits) | right
+    }
+}
+
+// Custom Debug implementation that does not expose the key
+impl core::fmt::Debug for KeyedPermutation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyedPermutation").field("n", &self.n).finish_non_exhaustive()
+    }
+}
+
+// The SplitMix64 finalizer applied to the keyed input
+fn round(x: u64, key: u64) -> u64 {
+    let mut z = (x ^ key).wrapping_mul(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keyed_permutation() {
+        let mut rng = crate::test::rng(261);
+        for &n in &[1, 2, 3, 4, 5, 100, 1000] {
+            let perm = KeyedPermutation::new(&mut rng, n);
+            assert_eq!(perm.n(), n);
+            let mut seen = [false; 1000];
+            for x in 0..n {
+                let y = perm.permute(x);
+                assert!(!seen[y as usize]);
+                seen[y as usize] = true;
+                assert_eq!(perm.inverse(y), x);
+            }
+        }
+
+        let perm = KeyedPermutation::new(&mut rng, u64::MAX);
+        for &x in &[0, 1, 12345, u64::MAX - 1] {
+            assert_eq!(perm.inverse(perm.permute(x)), x);
+        }
+        assert_eq!(KeyedPermutation::from_seed(50, [3; 32]), KeyedPermutation::from_seed(50, [3; 32]));
+        assert_ne!(KeyedPermutation::from_seed(50, [3; 32]), KeyedPermutation::from_seed(50, [4; 32]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_keyed_permutation_out_of_range() {
+        KeyedPermutation::from_seed(10, [0; 32]).permute(10);
+    }
+
+    #[test]
+    fn value_stability() {
+        let perm = KeyedPermutation::from_seed(1000, [7; 32]);
+        let mut buf = [0; 5];
+        for (x, y) in buf.iter_mut().zip(0..) {
+            *x = perm.permute(y);
+        }
+        assert_eq!(buf, [25, 788, 91, 846, 186]);
+    }
+}
@@ -16,7 +16,7 @@
 use alloc::collections::BTreeSet;
 #[cfg(feature = "std")] use std::collections::HashSet;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 use crate::distributions::WeightedError;
 
 #[cfg(feature = "alloc")]
@@ -258,6 +258,92 @@ where R: Rng + ?Sized {
     }
 }
 
+/// Randomly sample exactly `amount` distinct indices from `0..length`,
+/// lazily.
+///
+/// Unlike [`sample`], indices are generated on demand by the returned
+/// iterator, using Floyd's combination algorithm: each index requires one
+/// random number and an insertion into a set of the indices yielded so far.
+/// Memory use is thus proportional to the number of indices consumed, and
+/// stopping early saves work. This is appropriate for selecting a few
+/// indices from a large range; for larger `amount`, [`sample`] is faster.
+///
+/// The set of indices is uniformly random, but the order is not: in
+/// particular, late indices are more likely to be large. If random order is
+/// required, collect and shuffle the indices.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index;
+///
+/// let mut rng = rand::thread_rng();
+/// for i in index::sample_iter(&mut rng, usize::MAX, 3) {
+///     assert!(i < usize::MAX);
+/// }
+/// ```
+pub fn sample_iter<R>(rng: &mut R, length: usize, amount: usize) -> IndexSampleIter<'_, R>
+where R: Rng + ?Sized {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    IndexSampleIter {
+        rng,
+        next: length - amount,
+        length,
+        selected: IndexSet::new(),
+    }
+}
+
+#[cfg(feature = "std")]
+type IndexSet = HashSet<usize>;
+#[cfg(not(feature = "std"))]
+type IndexSet = BTreeSet<usize>;
+
+/// An iterator over distinct random indices, created by [`sample_iter`].
+#[derive(Debug)]
+pub struct IndexSampleIter<'a, R: ?Sized> {
+    rng: &'a mut R,
+    next: usize,
+    length: usize,
+    selected: IndexSet,
+}
+
+impl<'a, R: Rng + ?Sized> Iterator for IndexSampleIter<'a, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next == self.length {
+            return None;
+        }
+        let j = self.next;
+        self.next += 1;
+        // Sample as u32 where possible, for portability
+        let t = if self.length <= u32::MAX as usize {
+            self.rng.gen_range(0..=j as u32) as usize
+        } else {
+            self.rng.gen_range(0..=j)
+        };
+        let index = if self.selected.insert(t) {
+            t
+        } else {
+            // t was selected previously, while j cannot have been
+            self.selected.insert(j);
+            j
+        };
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.length - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<'a, R: Rng + ?Sized> ExactSizeIterator for IndexSampleIter<'a, R> {}
+
 /// Randomly sample exactly `amount` distinct indices from `0..length`, and
 /// return them in an arbitrary order (there is no guarantee of shuffling or
 /// ordering). The weights are to be provided by the input function `weights`,
@@ -281,16 +367,154 @@ where
     X: Into<f64>,
 {
     if length > (core::u32::MAX as usize) {
-        sample_efraimidis_spirakis(rng, length, weight, amount)
+        sample_efraimidis_spirakis(rng, length, weight, amount, false)
     } else {
         assert!(amount <= core::u32::MAX as usize);
         let amount = amount as u32;
         let length = length as u32;
-        sample_efraimidis_spirakis(rng, length, weight, amount)
+        sample_efraimidis_spirakis(rng, length, weight, amount, false)
+    }
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length` by
+/// weight, and return them in rank order.
+///
+/// This is as [`sample_weighted`], except that the result is ordered: the
+/// first index is distributed as a single weighted sample from `0..length`,
+/// the second as a weighted sample from the remaining indices and so on.
+/// Equivalently, each index `i` is assigned the key `weight(i) / e_i` with
+/// `e_i` exponentially distributed, and the indices of the `amount` largest
+/// keys are returned in decreasing order of key (the top-k of the
+/// perturbed weights, as used by e.g. stochastic beam search). Indices with
+/// zero weight are ranked last, in arbitrary order.
+///
+/// This implementation uses `O(length + amount)` space and
+/// `O(length + amount log amount)` time.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_weighted_ranked;
+///
+/// let scores = [0.5, 0.1, 2.0, 0.0, 1.4];
+/// let beam = sample_weighted_ranked(&mut rand::thread_rng(), scores.len(), |i| scores[i], 3).unwrap();
+/// assert_eq!(beam.len(), 3);
+/// // Index 3 has zero weight and is thus never among the top 3
+/// assert!(!beam.iter().any(|i| i == 3));
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn sample_weighted_ranked<R, F, X>(
+    rng: &mut R, length: usize, weight: F, amount: usize,
+) -> Result<IndexVec, WeightedError>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> X,
+    X: Into<f64>,
+{
+    if length > (u32::MAX as usize) {
+        sample_efraimidis_spirakis(rng, length, weight, amount, true)
+    } else {
+        assert!(amount <= u32::MAX as usize);
+        let amount = amount as u32;
+        let length = length as u32;
+        sample_efraimidis_spirakis(rng, length, weight, amount, true)
     }
 }
 
 
+/// Sample `amount` indices from `0..length` with probability proportional
+/// to size, using systematic sampling.
+///
+/// The weights of all indices are laid out consecutively on the interval
+/// `[0, total)`, where `total` is the sum of weights. This interval is split
+/// into `amount` steps of length `total / amount`, and a point is chosen at
+/// the same uniformly random offset within each step; the result holds the
+/// indices whose weight covers each point, in increasing order.
+///
+/// Thus the expected number of times index `i` is sampled is exactly
+/// `amount * weight(i) / total`, and the number only deviates from this by
+/// less than one. Indices with weight greater than `total / amount` are
+/// sampled at least once and may be sampled several times; all others are
+/// sampled at most once. Compared to independent weighted sampling, this
+/// spreads the sample evenly over the weights (as used in survey statistics
+/// and for load balancing), but the selection of indices is not independent:
+/// which indices may be sampled together depends on their order.
+///
+/// The weights are provided by the function `weight`, which is called twice
+/// for each index. This implementation uses `O(amount)` space and
+/// `O(length + amount)` time.
+///
+/// Fails if `amount > 0` and there are no indices or all weights are zero,
+/// or if any weight is negative or not finite.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_systematic;
+///
+/// // Sample 10 servers proportionally to their capacity
+/// let capacity = [10.0, 25.0, 5.0, 60.0];
+/// let sample = sample_systematic(&mut rand::thread_rng(), 4, |i| capacity[i], 10).unwrap();
+/// assert_eq!(sample.len(), 10);
+/// // Server 3 has 60% of the capacity, thus is sampled exactly 6 times
+/// assert_eq!(sample.iter().filter(|&&i| i == 3).count(), 6);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn sample_systematic<R, F, X>(
+    rng: &mut R, length: usize, weight: F, amount: usize,
+) -> Result<Vec<usize>, WeightedError>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> X,
+    X: Into<f64>,
+{
+    let mut total = 0.0;
+    for i in 0..length {
+        let w = weight(i).into();
+        if !(w >= 0.0 && w.is_finite()) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        total += w;
+    }
+    if amount == 0 {
+        return Ok(Vec::new());
+    }
+    if length == 0 {
+        return Err(WeightedError::NoItem);
+    }
+    if !(total > 0.0) || !total.is_finite() {
+        return Err(if total == 0.0 {
+            WeightedError::AllWeightsZero
+        } else {
+            WeightedError::InvalidWeight
+        });
+    }
+
+    let step = total / amount as f64;
+    let offset = rng.gen::<f64>() * step;
+    let mut result = Vec::with_capacity(amount);
+    let mut cumulative = 0.0;
+    let mut last = 0;
+    for i in 0..length {
+        let w = weight(i).into();
+        if w == 0.0 {
+            continue;
+        }
+        cumulative += w;
+        last = i;
+        while result.len() < amount && offset + result.len() as f64 * step < cumulative {
+            result.push(i);
+        }
+    }
+    // Rounding may leave the last points just past the cumulative total
+    result.resize(amount, last);
+    Ok(result)
+}
+
 /// Randomly sample exactly `amount` distinct indices from `0..length`, and
 /// return them in an arbitrary order (there is no guarantee of shuffling or
 /// ordering). The weights are to be provided by the input function `weights`,
@@ -300,10 +524,12 @@ where
 /// in this paper: https://doi.org/10.1016/j.ipl.2005.11.003
 /// It uses `O(length + amount)` space and `O(length)` time.
 ///
+/// If `ranked`, the indices are returned in decreasing order of key.
+///
 /// Panics if `amount > length`.
 #[cfg(feature = "std")]
 fn sample_efraimidis_spirakis<R, F, X, N>(
-    rng: &mut R, length: N, weight: F, amount: N,
+    rng: &mut R, length: N, weight: F, amount: N, ranked: bool,
 ) -> Result<IndexVec, WeightedError>
 where
     R: Rng + ?Sized,
@@ -365,9 +591,15 @@ where
         = candidates.select_nth_unstable(length.as_usize() - amount.as_usize());
 
     let mut result: Vec<N> = Vec::with_capacity(amount.as_usize());
-    result.push(mid.index);
-    for element in greater {
-        result.push(element.index);
+    if ranked {
+        greater.sort_unstable_by(|a, b| b.cmp(a));
+        result.extend(greater.iter().map(|element| element.index));
+        result.push(mid.index);
+    } else {
+        result.push(mid.index);
+        for element in greater {
+            result.push(element.index);
+        }
     }
     Ok(IndexVec::from(result))
 }
@@ -537,6 +769,41 @@ mod test {
         assert!(1 << 25 < sum && sum < (1 << 25) * 25);
     }
 
+    #[test]
+    fn test_sample_iter() {
+        let mut r = crate::test::rng(232);
+        assert_eq!(sample_iter(&mut r, 0, 0).next(), None);
+        let mut all: Vec<usize> = sample_iter(&mut r, 10, 10).collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+
+        let mut iter = sample_iter(&mut r, usize::MAX, 20);
+        assert_eq!(iter.len(), 20);
+        let mut first: Vec<usize> = iter.by_ref().take(5).collect();
+        assert_eq!(iter.len(), 15);
+        first.sort_unstable();
+        first.dedup();
+        assert_eq!(first.len(), 5);
+        assert!(first.iter().all(|&i| i < usize::MAX));
+
+        // Each index is equally likely
+        let mut counts = [0; 5];
+        for _ in 0..1000 {
+            for i in sample_iter(&mut r, 5, 2) {
+                counts[i] += 1;
+            }
+        }
+        // Expect 400 of each
+        assert!(counts.iter().all(|c| (340..460).contains(c)), "{:?}", counts);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_iter_too_many() {
+        let mut r = crate::test::rng(233);
+        sample_iter(&mut r, 3, 4);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_sample_alg() {
@@ -626,4 +893,81 @@ mod test {
             103718, 963490, 826426, 509103, 736396, 807036, 5327, 632573,
         ]); // rejection
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sample_weighted_ranked() {
+        let mut rng = crate::test::rng(259);
+        let weights = [1.0, 2.0, 3.0, 4.0, 0.0];
+        let v = sample_weighted_ranked(&mut rng, 5, |i| weights[i], 5).unwrap();
+        assert_eq!(v.index(4), 4);
+
+        // The first index is distributed as a single weighted sample
+        let mut counts = [0; 4];
+        for _ in 0..10000 {
+            let v = sample_weighted_ranked(&mut rng, 4, |i| weights[i], 2).unwrap();
+            assert_ne!(v.index(0), v.index(1));
+            counts[v.index(0)] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            // Expect 1000 * weight
+            let expected = 1000.0 * weights[i];
+            assert!((c as f64 - expected).abs() < 5.0 * expected.sqrt(), "{:?}", counts);
+        }
+
+        assert!(sample_weighted_ranked(&mut rng, 2, |_| -1.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_sample_systematic() {
+        let mut rng = crate::test::rng(263);
+        let weights = [1.0, 0.0, 2.5, 0.5, 4.0, 2.0];
+        let mut counts = [0.0f64; 6];
+        for _ in 0..1000 {
+            let v = sample_systematic(&mut rng, 6, |i| weights[i], 4).unwrap();
+            assert_eq!(v.len(), 4);
+            assert!(v.windows(2).all(|w| w[0] <= w[1]));
+            for i in v {
+                counts[i] += 1.0;
+            }
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            // Expect 4000 * weight / 10; index 4 is always sampled once or twice
+            let expected = 400.0 * weights[i];
+            assert!((c - expected).abs() <= 50.0, "{:?}", counts);
+        }
+
+        let v = sample_systematic(&mut rng, 3, |_| 1u32, 6).unwrap();
+        assert_eq!(v, [0, 0, 1, 1, 2, 2]);
+        assert!(sample_systematic(&mut rng, 3, |_| 1.0, 0).unwrap().is_empty());
+        assert_eq!(sample_systematic(&mut rng, 0, |_| 1.0, 1), Err(WeightedError::NoItem));
+        assert_eq!(sample_systematic(&mut rng, 2, |_| 0.0, 1), Err(WeightedError::AllWeightsZero));
+        assert_eq!(sample_systematic(&mut rng, 2, |i| i as f64 - 0.5, 1), Err(WeightedError::InvalidWeight));
+        assert_eq!(sample_systematic(&mut rng, 2, |_| f64::NAN, 1), Err(WeightedError::InvalidWeight));
+    }
+
+    #[test]
+    fn value_stability_sample_systematic() {
+        let mut rng = crate::test::rng(264);
+        let v = sample_systematic(&mut rng, 10, |i| i as f64, 4).unwrap();
+        assert_eq!(v, [1, 5, 7, 8]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn value_stability_sample_weighted_ranked() {
+        let mut rng = crate::test::rng(260);
+        let v = sample_weighted_ranked(&mut rng, 10, |i| i as f64, 4).unwrap();
+        assert_eq!(v.into_vec(), [8, 6, 9, 5]);
+    }
+
+    #[test]
+    fn value_stability_sample_iter() {
+        let mut rng = crate::test::rng(410);
+        let mut buf = [0; 6];
+        for (x, i) in buf.iter_mut().zip(sample_iter(&mut rng, 10, 6)) {
+            *x = i;
+        }
+        assert_eq!(buf, [0, 5, 6, 4, 8, 9]);
+    }
 }
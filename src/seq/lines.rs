@@ -0,0 +1,173 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sampling lines and records from readers
+
+use std::io::{self, BufRead};
+use std::mem;
+use std::string::String;
+use std::vec::Vec;
+
+use super::gen_index;
+use crate::Rng;
+
+/// Choose `amount` lines of `reader` uniformly at random, in one pass.
+///
+/// Lines are split as by [`BufRead::lines`], i.e. without their line ending
+/// (`\n` or `\r\n`), and must be valid UTF-8. Each subset of `amount` lines
+/// is equally likely to be chosen (reservoir sampling); if the reader has
+/// fewer lines, all lines are returned.
+///
+/// Unlike `reader.lines().choose_multiple(rng, amount)`, this allocates only
+/// for the chosen lines: a line read into a buffer is either discarded and
+/// the buffer reused, or swapped into the result, in which case the line it
+/// replaces is reused.
+///
+/// Although the lines are chosen randomly, their order in the result is
+/// neither stable nor fully random. If random ordering is desired, shuffle
+/// the result.
+///
+/// Complexity is `O(n)` where `n` is the number of bytes read.
+///
+/// # Errors
+///
+/// Fails if reading fails or a line is not valid UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::choose_lines;
+///
+/// let text = "alpha\nbeta\ngamma\ndelta\n";
+/// let lines = choose_lines(&mut rand::thread_rng(), text.as_bytes(), 2).unwrap();
+/// assert_eq!(lines.len(), 2);
+/// assert!(lines.iter().all(|line| text.lines().any(|l| l == line)));
+/// ```
+pub fn choose_lines<R, B>(rng: &mut R, mut reader: B, amount: usize) -> io::Result<Vec<String>>
+where
+    R: Rng + ?Sized,
+    B: BufRead,
+{
+    reservoir(rng, amount, |line: &mut String| {
+        line.clear();
+        if reader.read_line(line)? == 0 {
+            return Ok(false);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Ok(true)
+    })
+}
+
+/// Choose `amount` records of `reader`, separated by `delimiter`, uniformly
+/// at random, in one pass.
+///
+/// This is as [`choose_lines`], except that records are arbitrary bytes
+/// delimited by `delimiter` as for [`BufRead::read_until`]. Records are
+/// returned without their delimiter.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::choose_records;
+///
+/// let data = b"id1\0id2\0id3";
+/// let records = choose_records(&mut rand::thread_rng(), &data[..], b'\0', 1).unwrap();
+/// assert_eq!(records[0].len(), 3);
+/// ```
+pub fn choose_records<R, B>(
+    rng: &mut R, mut reader: B, delimiter: u8, amount: usize,
+) -> io::Result<Vec<Vec<u8>>>
+where
+    R: Rng + ?Sized,
+    B: BufRead,
+{
+    reservoir(rng, amount, |record: &mut Vec<u8>| {
+        record.clear();
+        if reader.read_until(delimiter, record)? == 0 {
+            return Ok(false);
+        }
+        if record.last() == Some(&delimiter) {
+            record.pop();
+        }
+        Ok(true)
+    })
+}
+
+// Reservoir sampling of the items produced by `read`, which reads the next
+// item into the given buffer or returns `false` when there are no more items
+fn reservoir<R, T, F>(rng: &mut R, amount: usize, mut read: F) -> io::Result<Vec<T>>
+where
+    R: Rng + ?Sized,
+    T: Default,
+    F: FnMut(&mut T) -> io::Result<bool>,
+{
+    let mut reservoir = Vec::new();
+    let mut buf = T::default();
+    let mut seen = 0;
+    while read(&mut buf)? {
+        if reservoir.len() < amount {
+            reservoir.push(mem::take(&mut buf));
+        } else {
+            let k = gen_index(rng, seen + 1);
+            if let Some(slot) = reservoir.get_mut(k) {
+                mem::swap(slot, &mut buf);
+            }
+        }
+        seen += 1;
+    }
+    Ok(reservoir)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_choose_lines() {
+        let mut rng = crate::test::rng(262);
+        let text = "0\r\n1\n2\n3\n4\n5\n6\n7\n8\n9";
+        let mut lines = choose_lines(&mut rng, text.as_bytes(), 20).unwrap();
+        lines.sort();
+        assert!(lines.iter().map(|l| l.as_str()).eq(text.lines()));
+        assert!(choose_lines(&mut rng, text.as_bytes(), 0).unwrap().is_empty());
+        assert!(choose_lines(&mut rng, &b""[..], 3).unwrap().is_empty());
+        assert!(choose_lines(&mut rng, &b"a\n\xff\n"[..], 1).is_err());
+
+        // Each line is chosen with probability 3 / 10
+        let mut counts = [0; 10];
+        for _ in 0..1000 {
+            for line in choose_lines(&mut rng, text.as_bytes(), 3).unwrap() {
+                counts[line.parse::<usize>().unwrap()] += 1;
+            }
+        }
+        // Expect 300 of each
+        assert!(counts.iter().all(|c| (240..360).contains(c)), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_choose_records() {
+        let mut rng = crate::test::rng(263);
+        let mut records = choose_records(&mut rng, &b"a,bb,,c,"[..], b',', 5).unwrap();
+        records.sort();
+        assert_eq!(records, [&b""[..], b"a", b"bb", b"c"]);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(264);
+        let text: String = (0..100).map(|i| i.to_string() + "\n").collect();
+        let lines = choose_lines(&mut rng, text.as_bytes(), 3).unwrap();
+        assert_eq!(lines, ["4", "26", "20"]);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bit masks with an exact number of bits set
+
+use super::gen_index;
+use crate::Rng;
+
+/// Generate a mask of the low `n` bits of a `u64` with exactly `k` bits set.
+///
+/// Each of the `n choose k` such masks is equally likely; bits `n` and above
+/// are zero. This is useful e.g. to select a random subset of `k` out of `n`
+/// features or flags.
+///
+/// The mask is generated with Floyd's combination algorithm, taking
+/// `min(k, n - k)` random numbers.
+///
+/// See also [`random_mask_bytes`] for longer masks, and
+/// [`ExactBernoulli::with_count`] for a `Vec<bool>` or other collection.
+///
+/// # Panics
+///
+/// Panics if `n > 64` or `k > n`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::random_mask_u64;
+///
+/// let mask = random_mask_u64(&mut rand::thread_rng(), 10, 3);
+/// assert_eq!(mask.count_ones(), 3);
+/// assert!(mask < 1 << 10);
+/// ```
+///
+/// [`ExactBernoulli::with_count`]: super::ExactBernoulli::with_count
+pub fn random_mask_u64<R: Rng + ?Sized>(rng: &mut R, n: u32, k: u32) -> u64 {
+    assert!(n <= 64, "n must not exceed 64");
+    assert!(k <= n, "k must not exceed n");
+    let mut bytes = [0u8; 8];
+    set_bits(rng, &mut bytes, n as usize, k as usize);
+    u64::from_le_bytes(bytes)
+}
+
+/// Fill `dest` with a mask with exactly `k` bits set.
+///
+/// The mask has `8 * dest.len()` bits, with bit `i` being bit `i % 8` of
+/// byte `i / 8` (i.e. little-endian bit order). All bytes of `dest` are
+/// overwritten. Each of the possible masks is equally likely.
+///
+/// As [`random_mask_u64`], this takes `min(k, n - k)` random numbers where
+/// `n = 8 * dest.len()`, and no extra memory.
+///
+/// # Panics
+///
+/// Panics if `k > 8 * dest.len()`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::random_mask_bytes;
+///
+/// let mut mask = [0u8; 16];
+/// random_mask_bytes(&mut rand::thread_rng(), &mut mask, 100);
+/// assert_eq!(mask.iter().map(|b| b.count_ones()).sum::<u32>(), 100);
+/// ```
+pub fn random_mask_bytes<R: Rng + ?Sized>(rng: &mut R, dest: &mut [u8], k: usize) {
+    let n = dest.len().checked_mul(8).expect("mask length overflows usize");
+    assert!(k <= n, "k must not exceed the number of bits in dest");
+    set_bits(rng, dest, n, k);
+}
+
+// Set exactly `k` of the low `n` bits of `mask` (in little-endian bit
+// order), clearing all others. If more than half of the bits are to be set,
+// the complement is sampled and then inverted.
+fn set_bits<R: Rng + ?Sized>(rng: &mut R, mask: &mut [u8], n: usize, k: usize) {
+    mask.fill(0);
+    let invert = k > n / 2;
+    let amount = if invert { n - k } else { k };
+    // Floyd's combination algorithm
+    for j in n - amount..n {
+        let t = gen_index(rng, j + 1);
+        let i = if mask[t / 8] & (1 << (t % 8)) != 0 { j } else { t };
+        mask[i / 8] |= 1 << (i % 8);
+    }
+    if invert {
+        for i in 0..n {
+            mask[i / 8] ^= 1 << (i % 8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_random_mask_u64() {
+        let mut rng = crate::test::rng(264);
+        assert_eq!(random_mask_u64(&mut rng, 0, 0), 0);
+        assert_eq!(random_mask_u64(&mut rng, 64, 64), u64::MAX);
+        assert_eq!(random_mask_u64(&mut rng, 10, 10), 0x3ff);
+        for &(n, k) in &[(1, 0), (1, 1), (10, 3), (10, 8), (64, 1), (64, 40)] {
+            let mask = random_mask_u64(&mut rng, n, k);
+            assert_eq!(mask.count_ones(), k);
+            assert_eq!(mask >> (n - 1) >> 1, 0);
+        }
+
+        // All 6 masks of 2 out of 4 bits are equally likely, as are all 4
+        // masks of 3 out of 4 bits (sampled as the complement)
+        let mut counts = [0u32; 16];
+        for _ in 0..6000 {
+            counts[random_mask_u64(&mut rng, 4, 2) as usize] += 1;
+            counts[random_mask_u64(&mut rng, 4, 3) as usize] += 1;
+        }
+        for (mask, &c) in counts.iter().enumerate() {
+            match mask.count_ones() {
+                2 => assert!((c as f64 - 1000.0).abs() < 150.0, "{:b}: {}", mask, c),
+                3 => assert!((c as f64 - 1500.0).abs() < 150.0, "{:b}: {}", mask, c),
+                _ => assert_eq!(c, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_mask_bytes() {
+        let mut rng = crate::test::rng(265);
+        let mut mask = [0xaa; 5];
+        random_mask_bytes(&mut rng, &mut mask, 0);
+        assert_eq!(mask, [0; 5]);
+        for &k in &[1, 17, 30, 40] {
+            random_mask_bytes(&mut rng, &mut mask, k);
+            assert_eq!(mask.iter().map(|b| b.count_ones()).sum::<u32>(), k as u32);
+        }
+        random_mask_bytes(&mut rng, &mut [], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_mask_too_many() {
+        random_mask_bytes(&mut crate::test::rng(266), &mut [0; 2], 17);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(267);
+        assert_eq!(random_mask_u64(&mut rng, 16, 5), 8586);
+        let mut mask = [0; 3];
+        random_mask_bytes(&mut rng, &mut mask, 20);
+        assert_eq!(mask, [191, 205, 255]);
+    }
+}
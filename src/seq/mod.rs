@@ -13,7 +13,22 @@
 //! *   [`SliceRandom`] slice sampling and mutation
 //! *   [`IteratorRandom`] iterator sampling
 //! *   [`index::sample`] low-level API to choose multiple indices from
-//!     `0..length`
+//!     `0..length`, and [`index::sample_iter`] to do so lazily
+//! *   [`bootstrap`] resampling with replacement
+//! *   [`choose_lines`] and [`choose_records`] to choose lines or records of
+//!     a reader in one pass
+//! *   [`random_chunks`] and [`random_split`] to split slices at random, and
+//!     [`disjoint_ranges`] to place non-overlapping ranges at random
+//! *   [`shuffle_with_seed`] and [`unshuffle_with_seed`] for reversible
+//!     shuffling
+//! *   [`exact_bernoulli`] boolean sequences with an exact number of `true`
+//!     values
+//! *   [`random_mask_u64`] and [`random_mask_bytes`] bit masks with an exact
+//!     number of bits set
+//! *   [`bit_reversed`] and [`bit_reversed_jittered`] low-discrepancy
+//!     orderings of indices, e.g. for progressive rendering
+//! *   [`random_order`] to iterate over a range in random order using
+//!     constant memory, and the underlying [`KeyedPermutation`]
 //!
 //! Also see:
 //!
@@ -24,12 +39,44 @@
 //! `usize` indices are sampled as a `u32` where possible (also providing a
 //! small performance boost in some cases).
 
+mod bit_reversed;
+#[cfg(feature = "alloc")]
+mod bootstrap;
 mod coin_flipper;
+mod exact_bernoulli;
+mod feistel;
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod index;
 
 mod increasing_uniform;
+#[cfg(feature = "std")]
+mod lines;
+mod mask;
+mod random_order;
+#[cfg(feature = "std_rng")]
+mod seeded_shuffle;
+mod split;
+
+pub use self::bit_reversed::{bit_reversed, bit_reversed_jittered, BitReversed};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::bootstrap::{bootstrap, Bootstrap};
+pub use self::exact_bernoulli::{exact_bernoulli, ExactBernoulli};
+pub use self::feistel::KeyedPermutation;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use self::lines::{choose_lines, choose_records};
+pub use self::mask::{random_mask_bytes, random_mask_u64};
+pub use self::random_order::{random_order, RandomOrder};
+#[cfg(feature = "std_rng")]
+pub use self::seeded_shuffle::shuffle_with_seed;
+#[cfg(all(feature = "std_rng", feature = "alloc"))]
+pub use self::seeded_shuffle::unshuffle_with_seed;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::split::{disjoint_ranges, random_chunks};
+pub use self::split::{random_split, RandomSplit};
 
 #[cfg(feature = "alloc")]
 use core::ops::Index;
@@ -482,7 +529,8 @@ pub trait IteratorRandom: Iterator + Sized {
     ///
     /// Although the elements are selected randomly, the order of elements in
     /// the buffer is neither stable nor fully random. If random ordering is
-    /// desired, shuffle the result.
+    /// desired, shuffle the result; if the original order is desired, use
+    /// [`IteratorRandom::choose_multiple_ordered`].
     ///
     /// The length of the returned vector equals `amount` unless the iterator
     /// contains insufficient elements, in which case it equals the number of
@@ -517,6 +565,35 @@ pub trait IteratorRandom: Iterator + Sized {
         }
         reservoir
     }
+
+    /// Collects `amount` values at random from the iterator into a vector,
+    /// in the order in which they were yielded by the iterator.
+    ///
+    /// This selects the same elements as [`IteratorRandom::choose_multiple`]
+    /// (given the same RNG state), but then restores their original order,
+    /// e.g. to keep a sample of log lines or of a time series in sequence.
+    ///
+    /// Complexity is `O(n + m log m)` where `n` is the length of the iterator
+    /// and `m = amount`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::IteratorRandom;
+    ///
+    /// let sample = (0..1000).choose_multiple_ordered(&mut rand::thread_rng(), 10);
+    /// assert!(sample.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn choose_multiple_ordered<R>(self, rng: &mut R, amount: usize) -> Vec<Self::Item>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut reservoir = self.enumerate().choose_multiple(rng, amount);
+        reservoir.sort_unstable_by_key(|&(i, _)| i);
+        reservoir.into_iter().map(|(_, elem)| elem).collect()
+    }
 }
 
 impl<T> SliceRandom for [T] {
@@ -706,7 +783,7 @@ impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> ExactSizeIterator
 // possible, primarily in order to produce the same output on 32-bit and 64-bit
 // platforms.
 #[inline]
-fn gen_index<R: Rng + ?Sized>(rng: &mut R, ubound: usize) -> usize {
+pub(crate) fn gen_index<R: Rng + ?Sized>(rng: &mut R, ubound: usize) -> usize {
 
     if ubound <= (core::u32::MAX as usize) {
         rng.gen_range(0..ubound as u32) as usize
@@ -1132,6 +1209,33 @@ mod test {
             .all(|e| { **e >= min_val && **e <= max_val }));
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_choose_multiple_ordered() {
+        let mut r = crate::test::rng(231);
+        let sample = (0..100).choose_multiple_ordered(&mut r, 10);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.windows(2).all(|w| w[0] < w[1]));
+
+        // The same elements are selected as by choose_multiple
+        let mut unordered = (0..100).choose_multiple(&mut crate::test::rng(232), 10);
+        unordered.sort_unstable();
+        let ordered = (0..100).choose_multiple_ordered(&mut crate::test::rng(232), 10);
+        assert_eq!(ordered, unordered);
+
+        // Every element is selected at least once
+        let mut seen = [false; 20];
+        for _ in 0..100 {
+            for i in (0..20).choose_multiple_ordered(&mut r, 3) {
+                seen[i] = true;
+            }
+        }
+        assert!(seen.iter().all(|&x| x));
+
+        assert_eq!((0..5).choose_multiple_ordered(&mut r, 10), [0, 1, 2, 3, 4]);
+        assert!((0..5).choose_multiple_ordered(&mut r, 0).is_empty());
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     #[cfg_attr(miri, ignore)] // Miri is too slow
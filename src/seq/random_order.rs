@@ -0,0 +1,163 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Iteration over a range in random order
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use super::feistel::KeyedPermutation;
+use crate::Rng;
+
+/// Iterate over all elements of `range` in random order, using constant
+/// memory.
+///
+/// Each element is yielded exactly once. Rather than shuffling a list of
+/// the elements, the `i`-th output is computed by applying a randomly keyed
+/// permutation of `0..n` (a [`KeyedPermutation`]) to `i`. This allows shuffled iteration over ranges too large to
+/// shuffle in memory, e.g. the record numbers of a huge dataset, and random
+/// access to the order via [`Iterator::nth`].
+///
+/// The order is not uniformly distributed over all `n!` permutations, but
+/// "looks random" for all practical purposes other than cryptography. Its
+/// cost is a few multiplications per element.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::random_order;
+///
+/// let mut order: Vec<usize> = random_order(&mut rand::thread_rng(), 10..20).collect();
+/// order.sort();
+/// assert_eq!(order, (10..20).collect::<Vec<_>>());
+/// ```
+pub fn random_order<R: Rng + ?Sized>(rng: &mut R, range: Range<usize>) -> RandomOrder {
+    let n = range.end.saturating_sub(range.start);
+    RandomOrder {
+        start: range.start,
+        permutation: KeyedPermutation::new(rng, n as u64),
+        front: 0,
+        back: n as u64,
+    }
+}
+
+/// An iterator over a range in random order.
+///
+/// This struct is created by [`random_order`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomOrder {
+    start: usize,
+    permutation: KeyedPermutation,
+    // The remaining inputs of the permutation
+    front: u64,
+    back: u64,
+}
+
+impl RandomOrder {
+    /// Returns the length of the range
+    pub fn range_len(&self) -> usize {
+        self.permutation.n() as usize
+    }
+
+    fn get(&self, i: u64) -> usize {
+        self.start + self.permutation.permute(i) as usize
+    }
+}
+
+impl Iterator for RandomOrder {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.front == self.back {
+            return None;
+        }
+        let x = self.get(self.front);
+        self.front += 1;
+        Some(x)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        let remaining = self.back - self.front;
+        if n as u64 >= remaining {
+            self.front = self.back;
+            return None;
+        }
+        self.front += n as u64;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for RandomOrder {
+    fn next_back(&mut self) -> Option<usize> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl ExactSizeIterator for RandomOrder {}
+impl FusedIterator for RandomOrder {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_random_order() {
+        let mut rng = crate::test::rng(260);
+        for &n in &[0, 1, 2, 3, 4, 5, 17, 64, 1000] {
+            let order = random_order(&mut rng, 5..5 + n);
+            assert_eq!(order.len(), n);
+            assert_eq!(order.range_len(), n);
+            let mut seen = [false; 1000];
+            for i in order {
+                assert!(!seen[i - 5]);
+                seen[i - 5] = true;
+            }
+            assert!(seen[..n].iter().all(|&s| s));
+        }
+        let (start, end) = (7, 3);
+        assert_eq!(random_order(&mut rng, start..end).next(), None);
+
+        let order = random_order(&mut rng, 0..100);
+        assert!(order.clone().ne(0..100));
+        let mut iter = order.clone();
+        for _ in 0..42 {
+            iter.next();
+        }
+        assert_eq!(order.clone().nth(42), iter.next());
+        assert_eq!(order.clone().nth(100), None);
+        assert_eq!(order.clone().next_back(), order.clone().fold(None, |_, i| Some(i)));
+    }
+
+    #[test]
+    fn test_huge_range() {
+        let mut rng = crate::test::rng(261);
+        let mut order = random_order(&mut rng, 0..usize::MAX);
+        let (a, b) = (order.next().unwrap(), order.next().unwrap());
+        assert_ne!(a, b);
+        assert_eq!(order.len(), usize::MAX - 2);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(262);
+        let mut buf = [0; 8];
+        for (x, i) in buf.iter_mut().zip(random_order(&mut rng, 0..8)) {
+            *x = i;
+        }
+        assert_eq!(buf, [1, 7, 3, 2, 6, 4, 0, 5]);
+    }
+}
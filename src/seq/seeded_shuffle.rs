@@ -0,0 +1,99 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reversible shuffling with a seed
+
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+use rand_chacha::ChaCha12Rng;
+
+use crate::seq::SliceRandom;
+use crate::SeedableRng;
+
+/// Shuffle `data` in place, with the permutation determined by `seed`.
+///
+/// The same `seed` always applies the same permutation to slices of the
+/// same length; [`unshuffle_with_seed`] applies its inverse. This allows
+/// data to be deterministically scrambled and later restored.
+///
+/// The permutation is generated by [`SliceRandom::shuffle`] using a
+/// ChaCha12 generator seeded via [`SeedableRng::seed_from_u64`], hence is
+/// portable and value-stable (except in a major release). It is *not*
+/// suitable to encrypt data: the permutation is easily recovered from the
+/// seed, and a 64-bit seed may be found by brute force.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::{shuffle_with_seed, unshuffle_with_seed};
+///
+/// let mut data = [1, 2, 3, 4, 5, 6];
+/// shuffle_with_seed(&mut data, 42);
+/// unshuffle_with_seed(&mut data, 42);
+/// assert_eq!(data, [1, 2, 3, 4, 5, 6]);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std_rng")))]
+pub fn shuffle_with_seed<T>(data: &mut [T], seed: u64) {
+    data.shuffle(&mut ChaCha12Rng::seed_from_u64(seed));
+}
+
+/// Invert [`shuffle_with_seed`] with the same `seed`, restoring the original
+/// order of `data`.
+///
+/// This regenerates the permutation as a list of indices, thus allocates
+/// `O(n)` memory where `n = data.len()`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std_rng", feature = "alloc"))))]
+pub fn unshuffle_with_seed<T>(data: &mut [T], seed: u64) {
+    // Shuffling depends only on the length, so after the same shuffle, the
+    // element at position `i` originates from position `perm[i]`.
+    let mut perm: Vec<usize> = (0..data.len()).collect();
+    shuffle_with_seed(&mut perm, seed);
+
+    // Move each element to its origin, following cycles of the permutation
+    for i in 0..data.len() {
+        while perm[i] != i {
+            let j = perm[i];
+            data.swap(i, j);
+            perm.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_round_trip() {
+        for len in [0, 1, 2, 10, 1000] {
+            let original: Vec<usize> = (0..len).collect();
+            for seed in 0..5 {
+                let mut data = original.clone();
+                shuffle_with_seed(&mut data, seed);
+                unshuffle_with_seed(&mut data, seed);
+                assert_eq!(data, original);
+            }
+        }
+
+        // A different seed does not restore the data
+        let original: Vec<usize> = (0..100).collect();
+        let mut data = original.clone();
+        shuffle_with_seed(&mut data, 1);
+        unshuffle_with_seed(&mut data, 2);
+        assert_ne!(data, original);
+    }
+
+    #[test]
+    fn test_value_stability() {
+        let mut data = [0, 1, 2, 3, 4, 5, 6, 7];
+        shuffle_with_seed(&mut data, 234);
+        assert_eq!(data, [1, 3, 2, 5, 6, 7, 4, 0]);
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Splitting slices into chunks at random cut points
+
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+use core::iter::FusedIterator;
+#[cfg(feature = "alloc")] use core::ops::Range;
+
+use crate::distributions::{Bernoulli, BernoulliError, Distribution};
+#[cfg(feature = "alloc")] use crate::seq::{index, SliceRandom};
+use crate::Rng;
+
+/// Split `data` into `n_chunks` non-empty contiguous chunks at random.
+///
+/// The `n_chunks - 1` cut points are chosen uniformly at random among the
+/// `data.len() - 1` gaps between elements, without replacement; thus each
+/// way of splitting `data` into `n_chunks` non-empty chunks is equally
+/// likely. Chunks are returned in order; concatenated they equal `data`.
+///
+/// Complexity is that of [`index::sample`] plus sorting `n_chunks - 1` cut
+/// points.
+///
+/// # Panics
+///
+/// Panics if `n_chunks > data.len()`, or if `n_chunks == 0` while `data` is
+/// not empty.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::random_chunks;
+///
+/// let data: Vec<u32> = (0..100).collect();
+/// let folds = random_chunks(&mut rand::thread_rng(), &data, 5);
+/// assert_eq!(folds.len(), 5);
+/// assert_eq!(folds.concat(), data);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn random_chunks<'a, R, T>(rng: &mut R, data: &'a [T], n_chunks: usize) -> Vec<&'a [T]>
+where R: Rng + ?Sized {
+    if n_chunks == 0 {
+        assert!(data.is_empty(), "cannot split non-empty slice into 0 chunks");
+        return Vec::new();
+    }
+    assert!(n_chunks <= data.len(), "cannot split slice into more chunks than elements");
+
+    let mut cuts = index::sample(rng, data.len() - 1, n_chunks - 1).into_vec();
+    cuts.sort_unstable();
+
+    let mut chunks = Vec::with_capacity(n_chunks);
+    let mut start = 0;
+    for cut in cuts {
+        // Gap `cut` lies between elements `cut` and `cut + 1`
+        chunks.push(&data[start..=cut]);
+        start = cut + 1;
+    }
+    chunks.push(&data[start..]);
+    chunks
+}
+
+/// Place non-overlapping ranges of the given `lengths` in `0..n` at random.
+///
+/// Returns one range per entry of `lengths`, in the same order, such that
+/// `ranges[i].len() == lengths[i]` and no two ranges overlap. Each such
+/// placement is equally likely: the order of the ranges within `0..n` is a
+/// uniformly random permutation, and the free space is divided among the
+/// `lengths.len() + 1` gaps before, between and after the ranges by
+/// sampling the positions of "bars" among the free positions (the stars and
+/// bars method).
+///
+/// Complexity is that of [`index::sample`] plus sorting `lengths.len()`
+/// positions.
+///
+/// # Panics
+///
+/// Panics if the sum of `lengths` exceeds `n`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::disjoint_ranges;
+///
+/// // Inject three faults of different durations into a 1000-step run
+/// let faults = disjoint_ranges(&mut rand::thread_rng(), 1000, &[10, 50, 5]);
+/// assert_eq!(faults[1].len(), 50);
+/// assert!(faults[0].end <= faults[1].start || faults[1].end <= faults[0].start);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn disjoint_ranges<R>(rng: &mut R, n: usize, lengths: &[usize]) -> Vec<Range<usize>>
+where R: Rng + ?Sized {
+    let total = lengths
+        .iter()
+        .try_fold(0usize, |sum, &len| sum.checked_add(len))
+        .filter(|&total| total <= n)
+        .expect("total length of ranges exceeds n");
+    let k = lengths.len();
+
+    let mut order: Vec<usize> = (0..k).collect();
+    order.shuffle(rng);
+    let mut bars = index::sample(rng, n - total + k, k).into_vec();
+    bars.sort_unstable();
+
+    let mut ranges: Vec<Range<usize>> = lengths.iter().map(|_| 0..0).collect();
+    let mut used = 0;
+    for (j, (bar, &i)) in bars.into_iter().zip(order.iter()).enumerate() {
+        // The `j` bars before this one are not free positions
+        let start = bar - j + used;
+        ranges[i] = start..start + lengths[i];
+        used += lengths[i];
+    }
+    ranges
+}
+
+/// Split `data` into contiguous chunks, cutting at each gap between
+/// elements with probability `p`.
+///
+/// Chunks are yielded lazily and in order by the returned [`RandomSplit`]
+/// iterator; all chunks are non-empty, and an empty slice yields no chunks.
+/// The length of each chunk (except the last) thus follows a geometric
+/// distribution with mean `1 / p`.
+///
+/// Fails if `p` is not in `[0, 1]`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::random_split;
+///
+/// // Fuzz a chunked protocol: send a message in random fragments
+/// let message = b"hello, world";
+/// for fragment in random_split(rand::thread_rng(), message, 0.25).unwrap() {
+///     assert!(!fragment.is_empty());
+/// }
+/// ```
+pub fn random_split<R, T>(rng: R, data: &[T], p: f64) -> Result<RandomSplit<'_, T, R>, BernoulliError>
+where R: Rng {
+    Ok(RandomSplit {
+        data,
+        rng,
+        cut: Bernoulli::new(p)?,
+    })
+}
+
+/// An iterator over contiguous chunks of a slice, split at random.
+///
+/// This struct is created by [`random_split`].
+#[derive(Debug)]
+pub struct RandomSplit<'a, T, R> {
+    data: &'a [T],
+    rng: R,
+    cut: Bernoulli,
+}
+
+impl<'a, T, R: Rng> Iterator for RandomSplit<'a, T, R> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut len = 1;
+        while len < self.data.len() && !self.cut.sample(&mut self.rng) {
+            len += 1;
+        }
+        let (chunk, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.data.len().min(1), Some(self.data.len()))
+    }
+}
+
+impl<'a, T, R: Rng> FusedIterator for RandomSplit<'a, T, R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_random_chunks() {
+        let mut rng = crate::test::rng(233);
+        let data: Vec<usize> = (0..10).collect();
+        for n in 1..=10 {
+            let chunks = random_chunks(&mut rng, &data, n);
+            assert_eq!(chunks.len(), n);
+            assert!(chunks.iter().all(|c| !c.is_empty()));
+            assert_eq!(chunks.concat(), data);
+        }
+        assert!(random_chunks(&mut rng, &data[..0], 0).is_empty());
+
+        // Each of the 3 ways to split 4 elements into 2 chunks is equally
+        // likely
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[random_chunks(&mut rng, &data[..4], 2)[0].len() - 1] += 1;
+        }
+        // Expect 1000 of each
+        assert!(counts.iter().all(|c| (900..1100).contains(c)), "{:?}", counts);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_random_chunks_too_many() {
+        random_chunks(&mut crate::test::rng(234), &[1, 2, 3], 4);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_disjoint_ranges() {
+        let mut rng = crate::test::rng(257);
+        let lengths = [3, 0, 7, 1, 4];
+        for &n in &[15, 16, 100] {
+            let mut ranges = disjoint_ranges(&mut rng, n, &lengths);
+            for (r, &len) in ranges.iter().zip(&lengths) {
+                assert_eq!(r.len(), len);
+                assert!(r.end <= n);
+            }
+            ranges.sort_unstable_by_key(|r| (r.start, r.end));
+            assert!(ranges.windows(2).all(|w| w[0].end <= w[1].start), "{:?}", ranges);
+        }
+        assert!(disjoint_ranges(&mut rng, 5, &[]).is_empty());
+        assert_eq!(disjoint_ranges(&mut rng, 2, &[2])[0], 0..2);
+
+        // Each of the 6 placements of two unit ranges in 0..3 is equally
+        // likely
+        let mut counts = [[0; 3]; 3];
+        for _ in 0..6000 {
+            let ranges = disjoint_ranges(&mut rng, 3, &[1, 1]);
+            counts[ranges[0].start][ranges[1].start] += 1;
+        }
+        for (a, row) in counts.iter().enumerate() {
+            for (b, &c) in row.iter().enumerate() {
+                if a == b {
+                    assert_eq!(c, 0);
+                } else {
+                    // Expect 1000 of each
+                    assert!((900..1100).contains(&c), "{:?}", counts);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_disjoint_ranges_too_long() {
+        disjoint_ranges(&mut crate::test::rng(258), 10, &[6, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_disjoint_ranges_value_stability() {
+        let mut rng = crate::test::rng(259);
+        assert_eq!(disjoint_ranges(&mut rng, 20, &[2, 5, 3]), [2..4, 6..11, 13..16]);
+    }
+
+    #[test]
+    fn test_random_split() {
+        let mut rng = crate::test::rng(235);
+        let data = [0u8; 100];
+        let mut split = random_split(&mut rng, &data, 0.0).unwrap();
+        assert_eq!(split.next().map(|c| c.len()), Some(100));
+        assert_eq!(split.next(), None);
+
+        let split = random_split(&mut rng, &data, 1.0).unwrap();
+        assert!(split.map(|c| c.len()).eq(core::iter::repeat(1).take(100)));
+
+        let mut total = 0;
+        let mut n = 0;
+        for chunk in random_split(&mut rng, &data, 0.1).unwrap() {
+            total += chunk.len();
+            n += 1;
+        }
+        assert_eq!(total, 100);
+        // Expect 1 + 99 * 0.1 ≈ 11 chunks
+        assert!((4..20).contains(&n), "{}", n);
+
+        assert_eq!(random_split(&mut rng, &data[..0], 0.5).unwrap().next(), None);
+        assert!(random_split(&mut rng, &data, 1.5).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Loot tables
+//!
+//! This module provides:
+//!
+//! *   [`LootTable`], a distribution over weighted entries which may be
+//!     items, "nothing" or nested tables, constructed via a
+//!     [`LootTableBuilder`]
+//! *   [`Pity`], which draws from a table while guaranteeing a rare drop at
+//!     least every `n` draws
+//!
+//! For a flat table of values without nesting, [`WeightedChoice`] suffices.
+//!
+//! [`WeightedChoice`]: crate::distributions::WeightedChoice
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::distributions::{Distribution, WeightedError, WeightedIndex};
+use crate::Rng;
+
+/// An entry of a [`LootTable`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Entry<T> {
+    /// Drop this item
+    Item(T),
+    /// Drop nothing
+    Nothing,
+    /// Draw from the nested table (once)
+    Table(LootTable<T>),
+}
+
+/// A table of weighted entries, sampling `Option<T>`.
+///
+/// Each sample chooses one entry with probability proportional to its
+/// weight. An [`Entry::Item`] yields `Some(item)` (a clone), an
+/// [`Entry::Nothing`] yields `None` and an [`Entry::Table`] yields a sample
+/// of the nested table. Thus the probability of an item in a nested table is
+/// the product of the probabilities along its path, which makes it easy to
+/// adjust e.g. the chance of "any rare item" without rebalancing the weights
+/// of all rare items.
+///
+/// Weights are `u32`, thus sampling is exact and portable. Tables are
+/// constructed via [`LootTable::builder`] and validated on
+/// [`LootTableBuilder::build`].
+///
+/// Use [`Distribution::sample_iter`] for multiple independent draws, or
+/// [`Pity`] to guarantee rare drops.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::Distribution;
+/// use rand::table::LootTable;
+///
+/// let rare = LootTable::builder()
+///     .item("ring", 1)
+///     .item("crown", 1)
+///     .build()
+///     .unwrap();
+/// let table = LootTable::builder()
+///     .item("coin", 60)
+///     .nothing(30)
+///     .table(rare, 10)
+///     .build()
+///     .unwrap();
+///
+/// // "ring" and "crown" have probability 5% each
+/// let drop = table.sample(&mut rand::thread_rng());
+/// println!("{:?}", drop);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LootTable<T> {
+    entries: Vec<Entry<T>>,
+    index: WeightedIndex<u32>,
+}
+
+impl<T> LootTable<T> {
+    /// Start building a table
+    pub fn builder() -> LootTableBuilder<T> {
+        LootTableBuilder {
+            entries: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    /// Get the entries, in the order added
+    pub fn entries(&self) -> &[Entry<T>] {
+        &self.entries
+    }
+
+    /// Get the probability of choosing entry `index` of this table
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn probability(&self, index: usize) -> Option<f64> {
+        self.index.probability(index)
+    }
+}
+
+impl<T: Clone> Distribution<Option<T>> for LootTable<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<T> {
+        match &self.entries[self.index.sample(rng)] {
+            Entry::Item(item) => Some(item.clone()),
+            Entry::Nothing => None,
+            Entry::Table(table) => table.sample(rng),
+        }
+    }
+}
+
+/// A builder for [`LootTable`]
+///
+/// This struct is created by [`LootTable::builder`].
+#[derive(Clone, Debug)]
+pub struct LootTableBuilder<T> {
+    entries: Vec<Entry<T>>,
+    weights: Vec<u32>,
+}
+
+impl<T> LootTableBuilder<T> {
+    /// Add an entry with the given weight
+    pub fn entry(mut self, entry: Entry<T>, weight: u32) -> Self {
+        self.entries.push(entry);
+        self.weights.push(weight);
+        self
+    }
+
+    /// Add an item with the given weight
+    pub fn item(self, item: T, weight: u32) -> Self {
+        self.entry(Entry::Item(item), weight)
+    }
+
+    /// Add a "drop nothing" entry with the given weight
+    pub fn nothing(self, weight: u32) -> Self {
+        self.entry(Entry::Nothing, weight)
+    }
+
+    /// Add a nested table with the given weight
+    pub fn table(self, table: LootTable<T>, weight: u32) -> Self {
+        self.entry(Entry::Table(table), weight)
+    }
+
+    /// Build the table
+    ///
+    /// Errors are as for [`WeightedIndex::new`]: the table must not be empty
+    /// and the total weight must be positive. Entries of weight zero are
+    /// never chosen.
+    pub fn build(self) -> Result<LootTable<T>, WeightedError> {
+        let index = WeightedIndex::new(self.weights)?;
+        Ok(LootTable {
+            entries: self.entries,
+            index,
+        })
+    }
+}
+
+/// Draws from a [`LootTable`], guaranteeing a rare drop at least every `n`
+/// draws.
+///
+/// Drops are classified as rare by a predicate `is_rare`. After `n - 1`
+/// consecutive draws without a rare drop, the next draw is taken from the
+/// separate table `rare` instead, which should contain only rare items
+/// (commonly, it is the table nested within the main table for rare items).
+/// The counter is reset by any rare drop, whether guaranteed or not.
+///
+/// Note that the guarantee increases the overall rate of rare drops above
+/// that of the table: with a rare probability `p` per draw the rate becomes
+/// `p / (1 - (1 - p)^n)`. Use [`Pity::misses`] to display progress towards
+/// the guarantee.
+///
+/// Unlike a [`Distribution`], a `Pity` is stateful; drawing requires
+/// `&mut self`.
+///
+/// # Example
+///
+/// ```
+/// use rand::table::{LootTable, Pity};
+///
+/// let rare = LootTable::builder().item("gem", 1).build().unwrap();
+/// let table = LootTable::builder()
+///     .item("stone", 99)
+///     .table(rare.clone(), 1)
+///     .build()
+///     .unwrap();
+///
+/// let mut pity = Pity::new(table, rare, 10, |item: &&str| *item == "gem");
+/// let mut rng = rand::thread_rng();
+/// let drops: Vec<_> = (0..10).map(|_| pity.draw(&mut rng)).collect();
+/// assert!(drops.contains(&Some("gem")));
+/// ```
+pub struct Pity<T, F> {
+    table: LootTable<T>,
+    rare: LootTable<T>,
+    is_rare: F,
+    n: u32,
+    misses: u32,
+}
+
+impl<T: Clone, F: Fn(&T) -> bool> Pity<T, F> {
+    /// Construct, guaranteeing a rare drop every `n` draws
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub fn new(table: LootTable<T>, rare: LootTable<T>, n: u32, is_rare: F) -> Self {
+        assert!(n > 0, "Pity::new called with n = 0");
+        Pity {
+            table,
+            rare,
+            is_rare,
+            n,
+            misses: 0,
+        }
+    }
+
+    /// Draw once
+    pub fn draw<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<T> {
+        let table = if self.misses >= self.n - 1 {
+            &self.rare
+        } else {
+            &self.table
+        };
+        let drop = table.sample(rng);
+        if drop.as_ref().map_or(false, |item| (self.is_rare)(item)) {
+            self.misses = 0;
+        } else {
+            self.misses = self.misses.saturating_add(1);
+        }
+        drop
+    }
+
+    /// Get the number of draws since the last rare drop
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+
+    /// Reset the counter of draws since the last rare drop
+    pub fn reset(&mut self) {
+        self.misses = 0;
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Pity<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pity")
+            .field("table", &self.table)
+            .field("rare", &self.rare)
+            .field("n", &self.n)
+            .field("misses", &self.misses)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tables() -> (LootTable<char>, LootTable<char>) {
+        let rare = LootTable::builder().item('x', 1).item('y', 3).build().unwrap();
+        let table = LootTable::builder()
+            .item('a', 50)
+            .nothing(40)
+            .table(rare.clone(), 10)
+            .item('z', 0)
+            .build()
+            .unwrap();
+        (table, rare)
+    }
+
+    #[test]
+    fn test_loot_table() {
+        assert_eq!(
+            LootTable::<u8>::builder().build().unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            LootTable::<u8>::builder().nothing(0).build().unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+
+        let (table, _) = tables();
+        assert_eq!(table.entries().len(), 4);
+        assert_eq!(table.probability(1), Some(0.4));
+        assert_eq!(table.probability(4), None);
+
+        let mut rng = crate::test::rng(266);
+        let (mut a, mut none, mut x, mut y) = (0, 0, 0, 0);
+        for drop in table.sample_iter(&mut rng).take(10000) {
+            match drop {
+                Some('a') => a += 1,
+                None => none += 1,
+                Some('x') => x += 1,
+                Some('y') => y += 1,
+                _ => panic!("unexpected drop {:?}", drop),
+            }
+        }
+        assert!((a as f64 - 5000.0).abs() < 200.0, "{}", a);
+        assert!((none as f64 - 4000.0).abs() < 200.0, "{}", none);
+        assert!((x as f64 - 250.0).abs() < 60.0, "{}", x);
+        assert!((y as f64 - 750.0).abs() < 100.0, "{}", y);
+    }
+
+    #[test]
+    fn test_pity() {
+        let (table, rare) = tables();
+        let mut pity = Pity::new(table, rare, 5, |c: &char| *c == 'x' || *c == 'y');
+        let mut rng = crate::test::rng(267);
+        let mut since_rare = 0;
+        for _ in 0..1000 {
+            match pity.draw(&mut rng) {
+                Some('x') | Some('y') => since_rare = 0,
+                _ => since_rare += 1,
+            }
+            assert!(since_rare < 5);
+            assert_eq!(pity.misses(), since_rare);
+        }
+        pity.reset();
+        assert_eq!(pity.misses(), 0);
+
+        // With n = 1, every draw is from the rare table
+        let (table, rare) = tables();
+        let mut pity = Pity::new(table, rare, 1, |_: &char| false);
+        for _ in 0..10 {
+            assert!(matches!(pity.draw(&mut rng), Some('x') | Some('y')));
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(268);
+        let (table, _) = tables();
+        let mut buf = [None; 6];
+        for x in buf.iter_mut() {
+            *x = table.sample(&mut rng);
+        }
+        assert_eq!(buf, [Some('a'), None, Some('x'), Some('a'), None, None]);
+    }
+}